@@ -41,6 +41,7 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_types::prelude::*;
 use holochain_zome_types::zome::ZomeName;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 
 /// DnaUtilError type.
@@ -180,6 +181,8 @@ pub async fn compress(dna_work_dir: &impl AsRef<std::path::Path>) -> DnaUtilResu
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct ZomeJson {
     pub wasm_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_fn_groups: Option<BTreeSet<HostFnGroup>>,
 }
 
 /// Special Json Value Decode Helper
@@ -194,26 +197,46 @@ struct DnaDefJson {
     pub uuid: String,
     pub properties: serde_json::Value,
     pub zomes: BTreeMap<ZomeName, ZomeJson>,
+    #[serde(default, skip_serializing_if = "RequiredValidations::is_default")]
+    pub default_required_validations: RequiredValidations,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub entry_type_properties: BTreeMap<ZomeName, BTreeMap<String, serde_json::Value>>,
 }
 
 impl DnaDefJson {
     pub fn from_dna_def(dna: DnaDef) -> DnaUtilResult<DnaDefJson> {
         let properties: JsonValueDecodeHelper = dna.properties.try_into()?;
         let mut zomes = BTreeMap::new();
-        for zome_name in dna.zomes.into_iter().map(|(name, _)| name) {
+        for (zome_name, zome) in dna.zomes {
             let zome_file = format!("./{}.wasm", zome_name);
+            let host_fn_groups = match zome {
+                ZomeDef::Wasm(zome) => zome.host_fn_groups,
+                ZomeDef::Inline(_) => None,
+            };
             zomes.insert(
                 zome_name.clone(),
                 ZomeJson {
                     wasm_path: zome_file,
+                    host_fn_groups,
                 },
             );
         }
+        let mut entry_type_properties = BTreeMap::new();
+        for (zome_name, entry_types) in dna.entry_type_properties {
+            let mut zome_properties = BTreeMap::new();
+            for (entry_def_id, properties) in entry_types {
+                let properties: JsonValueDecodeHelper = properties.try_into()?;
+                zome_properties.insert(entry_def_id, properties.0);
+            }
+            entry_type_properties.insert(zome_name, zome_properties);
+        }
         Ok(Self {
             name: dna.name,
             uuid: dna.uuid,
             properties: properties.0,
             zomes,
+            default_required_validations: dna.default_required_validations,
+            entry_type_properties,
         })
     }
 
@@ -237,15 +260,35 @@ impl DnaDefJson {
 
             let wasm: DnaWasm = zome_content.into();
             let wasm_hash = holo_hash::WasmHash::with_data(&wasm).await;
-            zomes.push((zome_name.clone(), WasmZome { wasm_hash }.into()));
+            zomes.push((
+                zome_name.clone(),
+                WasmZome {
+                    wasm_hash,
+                    host_fn_groups: zome.host_fn_groups.clone(),
+                }
+                .into(),
+            ));
             wasm_list.push(wasm);
         }
 
+        let mut entry_type_properties = BTreeMap::new();
+        for (zome_name, entry_types) in self.entry_type_properties.iter() {
+            let mut zome_properties = BTreeMap::new();
+            for (entry_def_id, properties) in entry_types.iter() {
+                let properties: SerializedBytes =
+                    JsonValueDecodeHelper(properties.clone()).try_into()?;
+                zome_properties.insert(entry_def_id.clone(), properties);
+            }
+            entry_type_properties.insert(zome_name.clone(), zome_properties);
+        }
+
         let dna = DnaDef {
             name: self.name.clone(),
             uuid: self.uuid.clone(),
             properties,
             zomes,
+            default_required_validations: self.default_required_validations,
+            entry_type_properties,
         };
 
         Ok(DnaFile::new(dna, wasm_list).await?)
@@ -277,6 +320,18 @@ mod tests {
             .with_properties(SerializedBytes::try_from(properties).unwrap())
             .await
             .unwrap();
+        let (mut dna, wasm): (DnaDef, Vec<DnaWasm>) = dna_file.into();
+        let entry_properties = JsonValueDecodeHelper(serde_json::json!({ "max_length": 140 }));
+        dna.entry_type_properties.insert(
+            "test-zome-1".into(),
+            vec![(
+                "post".to_string(),
+                SerializedBytes::try_from(entry_properties).unwrap(),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let dna_file = DnaFile::new(dna, wasm).await.unwrap();
 
         let dna_filename = tmp_dir.path().join("test-dna.dna.gz");
         let content1 = dna_file.to_file_content().await.unwrap();
@@ -17,6 +17,8 @@ struct Ops {
     /// Force the admin port that hc uses to talk to holochain to a specific value.
     /// For example `hc -f=9000,9001 run`
     /// This must be set on each run or the port will change if it's in use.
+    /// On unix new setups only listen on an admin socket
+    /// unless an admin port is forced.
     #[structopt(short, long, value_delimiter = ",")]
     force_admin_ports: Vec<u16>,
     /// Set the path to the holochain binary.
@@ -5,16 +5,20 @@
 //! For simple calls like [`AdminRequest::ListDnas`] this is probably easier
 //! but if you want more control use [`CmdRunner::command`].
 use std::convert::TryInto;
+use std::net::IpAddr;
 use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::ensure;
+use holochain_conductor_api::state_dump::CellStateDump;
+use holochain_conductor_api::state_dump::DumpStateOptions;
 use holochain_conductor_api::AdminInterfaceConfig;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::InterfaceTlsConfig;
 use holochain_p2p::kitsune_p2p;
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
 use holochain_types::prelude::AgentPubKey;
@@ -27,11 +31,17 @@ use portpicker::is_free;
 use std::convert::TryFrom;
 
 use crate::cmds::Existing;
+#[cfg(unix)]
+use crate::config::admin_socket_path;
+#[cfg(unix)]
+use crate::config::read_config;
+use crate::config::read_token_file;
 use crate::expect_match;
 use crate::ports::get_admin_ports;
 use crate::run::run_async;
 use crate::CmdRunner;
 use structopt::StructOpt;
+use tokio::process::Child;
 
 #[doc(hidden)]
 #[derive(Debug, StructOpt)]
@@ -41,6 +51,12 @@ pub struct Call {
     /// If this is empty existing setups will be used.
     /// Cannot be combined with existing setups.
     pub running: Vec<u16>,
+    #[structopt(long, requires = "running")]
+    /// Path to a file holding an admin token to authenticate
+    /// the running conductors' admin interfaces with.
+    /// Setups authenticate with the token their conductor
+    /// writes to its environment directory.
+    pub admin_token_file: Option<PathBuf>,
     #[structopt(flatten)]
     pub existing: Existing,
     #[structopt(subcommand)]
@@ -55,6 +71,7 @@ pub struct Call {
 pub enum AdminRequestCli {
     AddAdminWs(AddAdminWs),
     AddAppWs(AddAppWs),
+    AddAppSocket(AddAppSocket),
     InstallApp(InstallApp),
     /// Calls AdminRequest::ListDnas.
     ListDnas,
@@ -66,6 +83,8 @@ pub enum AdminRequestCli {
     ListActiveApps,
     ActivateApp(ActivateApp),
     DeactivateApp(DeactivateApp),
+    UninstallApp(UninstallApp),
+    MigrateAgent(MigrateAgent),
     DumpState(DumpState),
     /// Calls AdminRequest::AddAgentInfo.
     /// [Unimplemented].
@@ -79,6 +98,13 @@ pub struct AddAdminWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    /// Require connections to authenticate with
+    /// a token before making any calls.
+    /// The conductor writes an admin token to `admin_token`
+    /// in its environment directory which can be passed
+    /// to `hc call --admin-token-file`.
+    #[structopt(long)]
+    pub require_token: bool,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -88,6 +114,54 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    /// Require connections to authenticate with
+    /// a token before making any calls.
+    #[structopt(long)]
+    pub require_token: bool,
+    /// The address to listen on.
+    /// Defaults to 127.0.0.1 so only local clients can connect.
+    #[structopt(long)]
+    pub bind_address: Option<IpAddr>,
+    /// Serve the interface over TLS with the PEM certificate at this path.
+    /// A self-signed certificate is generated if neither file exists.
+    #[structopt(long, requires = "tls-key")]
+    pub tls_cert: Option<PathBuf>,
+    /// The PEM private key for the TLS certificate.
+    #[structopt(long, requires = "tls-cert")]
+    pub tls_key: Option<PathBuf>,
+    /// Other host names or IP addresses clients use to reach the interface,
+    /// which a generated TLS certificate is also valid for.
+    #[structopt(long, requires = "tls-cert")]
+    pub tls_host: Vec<String>,
+}
+
+impl AddAppWs {
+    /// The TLS config for the interface, if a certificate and key were given.
+    fn tls(&self) -> Option<InterfaceTlsConfig> {
+        match (&self.tls_cert, &self.tls_key) {
+            (Some(cert_path), Some(key_path)) => Some(InterfaceTlsConfig {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                hosts: self.tls_host.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::AttachAppUnixSocket
+/// and adds another app interface listening
+/// on a Unix domain socket.
+pub struct AddAppSocket {
+    /// Path to create the socket at.
+    /// Only the user running the conductor
+    /// will be able to connect to it.
+    pub path: PathBuf,
+    /// Require connections to authenticate with
+    /// a token before making any calls.
+    #[structopt(long)]
+    pub require_token: bool,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -127,10 +201,30 @@ pub struct DeactivateApp {
     pub app_id: String,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::UninstallApp
+/// and removes the app, deleting its cells' data.
+pub struct UninstallApp {
+    /// The InstalledAppId to uninstall.
+    pub app_id: String,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::MigrateAgent
+/// and moves a cell in the app to a new version of its dna.
+pub struct MigrateAgent {
+    /// The InstalledAppId of the app the cell is in.
+    pub app_id: String,
+    /// The CellNick of the cell to migrate.
+    pub cell_nick: String,
+    #[structopt(parse(try_from_str = parse_dna_hash))]
+    /// The hash of the new dna, which must already be installed.
+    pub dna: DnaHash,
+}
+
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::DumpState
 /// and dumps the current cell's state.
-/// TODO: Add pretty print.
 /// TODO: Default to dumping all cell state.
 pub struct DumpState {
     #[structopt(parse(try_from_str = parse_dna_hash))]
@@ -139,6 +233,12 @@ pub struct DumpState {
     #[structopt(parse(try_from_str = parse_agent_key))]
     /// The agent half of the cell id to dump.
     pub agent_key: AgentPubKey,
+    #[structopt(short, long)]
+    /// Include the entries on the source chain.
+    pub entries: bool,
+    #[structopt(long)]
+    /// Print the whole dump as JSON instead of a summary.
+    pub json: bool,
 }
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::RequestAgentInfo
@@ -158,6 +258,7 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     let Call {
         existing,
         running,
+        admin_token_file,
         call,
     } = req;
     let cmds = if running.is_empty() {
@@ -166,30 +267,25 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
         } else {
             existing.load()?
         };
-        let ports = get_admin_ports(paths.clone()).await?;
-        let mut cmds = Vec::with_capacity(ports.len());
-        for (port, path) in ports.into_iter().zip(paths.into_iter()) {
-            match CmdRunner::try_new(port).await {
-                Ok(cmd) => cmds.push((cmd, None)),
-                Err(e) => match e.kind() {
-                    std::io::ErrorKind::ConnectionRefused => {
-                        let (port, holochain) = run_async(holochain_path, path, None).await?;
-                        cmds.push((CmdRunner::new(port).await, Some(holochain)))
-                    }
-                    _ => {
-                        bail!(
-                            "Failed to connect to running conductor or start one {:?}",
-                            e
-                        )
-                    }
-                },
-            }
+        let mut cmds = Vec::with_capacity(paths.len());
+        for path in paths {
+            let (mut cmd, holochain) = connect_or_run(holochain_path, path.clone()).await?;
+            cmd.authenticate_setup(path).await?;
+            cmds.push((cmd, holochain));
         }
         cmds
     } else {
+        let token = match admin_token_file {
+            Some(path) => Some(read_token_file(&path)?),
+            None => None,
+        };
         let mut cmds = Vec::with_capacity(running.len());
         for port in running {
-            cmds.push((CmdRunner::new(port).await, None));
+            let mut cmd = CmdRunner::new(port).await;
+            if let Some(token) = token.clone() {
+                cmd.authenticate(token).await?;
+            }
+            cmds.push((cmd, None));
         }
         cmds
     };
@@ -199,6 +295,62 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Connect to the conductor for this setup,
+/// starting it if it isn't already running.
+/// The admin socket is preferred over the admin port if the setup has one.
+async fn connect_or_run(
+    holochain_path: &Path,
+    path: PathBuf,
+) -> anyhow::Result<(CmdRunner, Option<Child>)> {
+    #[cfg(unix)]
+    {
+        let socket = read_config(path.clone())?
+            .as_ref()
+            .and_then(admin_socket_path);
+        if let Some(socket) = socket {
+            return match CmdRunner::try_new_unix(&socket).await {
+                Ok(cmd) => Ok((cmd, None)),
+                Err(e) if is_not_running(&e) => {
+                    let (_, holochain) = run_async(holochain_path, path, None).await?;
+                    Ok((CmdRunner::try_new_unix(&socket).await?, Some(holochain)))
+                }
+                Err(e) => bail!(
+                    "Failed to connect to running conductor or start one {:?}",
+                    e
+                ),
+            };
+        }
+    }
+    let port = get_admin_ports(vec![path.clone()])
+        .await?
+        .into_iter()
+        .next();
+    let connected = match port {
+        Some(port) => CmdRunner::try_new(port).await,
+        None => Err(std::io::ErrorKind::NotFound.into()),
+    };
+    match connected {
+        Ok(cmd) => Ok((cmd, None)),
+        Err(e) if is_not_running(&e) => {
+            let (port, holochain) = run_async(holochain_path, path, None).await?;
+            let port = port.ok_or_else(|| anyhow!("The conductor has no admin port"))?;
+            Ok((CmdRunner::try_new(port).await?, Some(holochain)))
+        }
+        Err(e) => bail!(
+            "Failed to connect to running conductor or start one {:?}",
+            e
+        ),
+    }
+}
+
+/// A stale socket or a closed port means the conductor isn't running.
+fn is_not_running(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::NotFound
+    )
+}
+
 async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Result<()> {
     match call {
         AdminRequestCli::AddAdminWs(args) => {
@@ -209,6 +361,10 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let port = attach_app_interface(cmd, args).await?;
             msg!("Added App port {}", port);
         }
+        AdminRequestCli::AddAppSocket(args) => {
+            let path = attach_app_unix_socket(cmd, args).await?;
+            msg!("Added App socket {}", path.display());
+        }
         AdminRequestCli::InstallApp(args) => {
             let app_id = args.app_id.clone();
             let cells = install_app(cmd, args).await?;
@@ -240,9 +396,23 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             deactivate_app(cmd, args).await?;
             msg!("Deactivated app: {:?}", app_id);
         }
+        AdminRequestCli::UninstallApp(args) => {
+            let app_id = args.app_id.clone();
+            uninstall_app(cmd, args).await?;
+            msg!("Uninstalled app: {:?}", app_id);
+        }
+        AdminRequestCli::MigrateAgent(args) => {
+            let cell_id = migrate_agent(cmd, args).await?;
+            msg!("Migrated agent to cell: {}", cell_id);
+        }
         AdminRequestCli::DumpState(args) => {
+            let json = args.json;
             let state = dump_state(cmd, args).await?;
-            msg!("DUMP STATE \n{}", state);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&state)?);
+            } else {
+                print_state_dump(&state);
+            }
         }
         AdminRequestCli::AddAgents => {
             todo!("Adding agent info via cli is coming soon")
@@ -308,7 +478,12 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
     let resp = cmd
         .command(AdminRequest::AddAdminInterfaces(vec![
             AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
+                driver: InterfaceDriver::Websocket {
+                    port,
+                    bind_address: None,
+                    tls: None,
+                },
+                require_token: args.require_token,
             },
         ]))
         .await?;
@@ -425,13 +600,46 @@ pub async fn deactivate_app(cmd: &mut CmdRunner, args: DeactivateApp) -> anyhow:
     Ok(())
 }
 
+/// Calls [`AdminRequest::UninstallApp`] and removes the app.
+pub async fn uninstall_app(cmd: &mut CmdRunner, args: UninstallApp) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::UninstallApp {
+            installed_app_id: args.app_id,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AppUninstalled),
+        "Failed to uninstall app, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::MigrateAgent`] and returns the new cell id.
+pub async fn migrate_agent(cmd: &mut CmdRunner, args: MigrateAgent) -> anyhow::Result<CellId> {
+    let resp = cmd
+        .command(AdminRequest::MigrateAgent {
+            installed_app_id: args.app_id,
+            cell_nick: args.cell_nick,
+            new_dna_hash: args.dna,
+            membrane_proof: None,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AgentMigrated, "Failed to migrate agent"))
+}
+
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     if let Some(port) = args.port {
         ensure!(is_free(port), "port {} is not free", port);
     }
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            require_token: args.require_token,
+            bind_address: args.bind_address,
+            tls: args.tls(),
+        })
         .await?;
     match resp {
         AdminResponse::AppInterfaceAttached { port } => Ok(port),
@@ -443,16 +651,88 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
     }
 }
 
+/// Calls [`AdminRequest::AttachAppUnixSocket`] and adds another app interface
+/// listening on a Unix domain socket.
+pub async fn attach_app_unix_socket(
+    cmd: &mut CmdRunner,
+    args: AddAppSocket,
+) -> anyhow::Result<PathBuf> {
+    let resp = cmd
+        .command(AdminRequest::AttachAppUnixSocket {
+            path: args.path.clone(),
+            require_token: args.require_token,
+        })
+        .await?;
+    match resp {
+        AdminResponse::AppUnixSocketAttached { path } => Ok(path),
+        _ => Err(anyhow!(
+            "Failed to attach app socket {}, got: {:?}",
+            args.path.display(),
+            resp
+        )),
+    }
+}
+
 /// Calls [`AdminRequest::DumpState`] and dumps the current cell's state.
-// TODO: Add pretty print.
 // TODO: Default to dumping all cell state.
-pub async fn dump_state(cmd: &mut CmdRunner, args: DumpState) -> anyhow::Result<String> {
+pub async fn dump_state(cmd: &mut CmdRunner, args: DumpState) -> anyhow::Result<CellStateDump> {
+    let options = DumpStateOptions {
+        include_entries: args.entries,
+    };
     let resp = cmd
         .command(AdminRequest::DumpState {
             cell_id: Box::new(args.into()),
+            options,
         })
         .await?;
-    Ok(expect_match!(resp => AdminResponse::StateDumped, "Failed to dump state"))
+    Ok(*expect_match!(resp => AdminResponse::StateDumped, "Failed to dump state"))
+}
+
+fn print_state_dump(state: &CellStateDump) {
+    msg!("State of cell {}", state.cell_id);
+    msg!("Source chain ({} elements):", state.source_chain.len());
+    for (i, element) in state.source_chain.iter().enumerate() {
+        let entry_type = element
+            .header
+            .entry_type()
+            .map(|t| format!(" {:?}", t))
+            .unwrap_or_default();
+        msg!(
+            "  {}: {:?}{} {}",
+            i,
+            element.header.header_type(),
+            entry_type,
+            element.header_address
+        );
+    }
+    let ops = &state.dht_ops;
+    msg!(
+        "Dht ops: {} authored, {} in validation limbo, {} in integration limbo, {} integrated",
+        ops.authored,
+        ops.validation_limbo,
+        ops.integration_limbo,
+        ops.integrated
+    );
+    let receipts: usize = state
+        .validation_receipts
+        .iter()
+        .map(|op| op.receipts.len())
+        .sum();
+    let without_receipts = state
+        .validation_receipts
+        .iter()
+        .filter(|op| op.receipts.is_empty())
+        .count();
+    msg!(
+        "Validation receipts: {} for {} authored ops, {} ops have none",
+        receipts,
+        state.validation_receipts.len(),
+        without_receipts
+    );
+    msg!("Databases:");
+    for db in &state.databases {
+        msg!("  {}: {} entries, {} bytes", db.db, db.entries, db.bytes);
+    }
 }
 
 /// Calls [`AdminRequest::AddAgentInfo`] with and adds the list of agent info.
@@ -492,7 +772,12 @@ fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
 impl From<CellId> for DumpState {
     fn from(cell_id: CellId) -> Self {
         let (dna, agent_key) = cell_id.into_dna_and_agent();
-        Self { agent_key, dna }
+        Self {
+            agent_key,
+            dna,
+            entries: false,
+            json: false,
+        }
     }
 }
 
@@ -1,22 +1,88 @@
 //! Helpers for creating, reading and writing [`ConductorConfig`]s.
+use std::path::Path;
 use std::path::PathBuf;
 
+use holochain_conductor_api::auth::InterfaceToken;
+use holochain_conductor_api::auth::ADMIN_TOKEN_FILE;
 use holochain_conductor_api::config::conductor::ConductorConfig;
+use holochain_conductor_api::AdminInterfaceConfig;
+use holochain_conductor_api::InterfaceDriver;
 
 /// Name of the file that conductor config is written to.
 pub const CONDUCTOR_CONFIG: &str = "conductor-config.yaml";
 
+/// Name of the Unix domain socket the admin interface listens on.
+pub const ADMIN_SOCKET: &str = "admin.sock";
+
 /// Create a new default [`ConductorConfig`] with environment path
 /// and keystore all in the same directory.
+/// On unix the admin interface only listens on the [`ADMIN_SOCKET`]
+/// in the same directory, a websocket admin port can be added with
+/// [`force_admin_port`](crate::force_admin_port).
+/// Elsewhere the admin interface listens on a port chosen by the OS.
 pub fn create_config(environment_path: PathBuf) -> ConductorConfig {
     let mut conductor_config = ConductorConfig::default();
     conductor_config.environment_path = environment_path.clone().into();
+    #[cfg(unix)]
+    let driver = InterfaceDriver::UnixSocket {
+        path: environment_path.join(ADMIN_SOCKET),
+    };
+    #[cfg(not(unix))]
+    let driver = InterfaceDriver::Websocket {
+        port: 0,
+        bind_address: None,
+        tls: None,
+    };
+    conductor_config.admin_interfaces = Some(vec![AdminInterfaceConfig {
+        driver,
+        require_token: false,
+    }]);
     let mut keystore_path = environment_path;
     keystore_path.push("keystore");
     conductor_config.keystore_path = Some(keystore_path);
     conductor_config
 }
 
+/// The path to the first admin interface in the config
+/// which is a Unix domain socket, if any.
+pub fn admin_socket_path(config: &ConductorConfig) -> Option<PathBuf> {
+    config
+        .admin_interfaces
+        .as_ref()?
+        .iter()
+        .find_map(|i| match &i.driver {
+            InterfaceDriver::UnixSocket { path } => Some(path.clone()),
+            _ => None,
+        })
+}
+
+/// Whether any admin interface in the config requires a token.
+pub fn requires_admin_token(config: &ConductorConfig) -> bool {
+    config
+        .admin_interfaces
+        .iter()
+        .flatten()
+        .any(|i| i.require_token)
+}
+
+/// The admin token the conductor wrote to its environment directory,
+/// if it has any admin interfaces which require a token.
+pub fn read_admin_token(config: &ConductorConfig) -> anyhow::Result<Option<InterfaceToken>> {
+    let environment_path: &PathBuf = config.environment_path.as_ref();
+    match read_token_file(&environment_path.join(ADMIN_TOKEN_FILE)) {
+        Ok(token) => Ok(Some(token)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Read an interface token from a file.
+pub fn read_token_file(path: &Path) -> std::io::Result<InterfaceToken> {
+    Ok(InterfaceToken(
+        std::fs::read_to_string(path)?.trim().to_string(),
+    ))
+}
+
 /// Write [`ConductorConfig`] to [`CONDUCTOR_CONFIG`]
 pub fn write_config(mut path: PathBuf, config: &ConductorConfig) -> PathBuf {
     path.push(CONDUCTOR_CONFIG);
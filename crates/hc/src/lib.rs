@@ -111,6 +111,7 @@
 use std::path::Path;
 use std::path::PathBuf;
 
+use holochain_conductor_api::auth::InterfaceToken;
 use holochain_conductor_api::{AdminRequest, AdminResponse};
 use holochain_websocket::WebsocketSender;
 use ports::get_admin_api;
@@ -160,6 +161,14 @@ impl CmdRunner {
         Ok(Self { client })
     }
 
+    /// Create a new connection for calling admin interface commands
+    /// over an admin interface listening on a Unix domain socket.
+    #[cfg(unix)]
+    pub async fn try_new_unix(path: &Path) -> std::io::Result<Self> {
+        let client = ports::get_admin_api_unix(path).await?;
+        Ok(Self { client })
+    }
+
     /// Create a command runner from a setup path.
     /// This expects holochain to be on the path.
     pub async fn from_setup(setup_path: PathBuf) -> anyhow::Result<(Self, tokio::process::Child)> {
@@ -172,11 +181,59 @@ impl CmdRunner {
         holochain_bin_path: &Path,
         setup_path: PathBuf,
     ) -> anyhow::Result<(Self, tokio::process::Child)> {
-        let conductor = run::run_async(holochain_bin_path, setup_path, None).await?;
-        let cmd = CmdRunner::try_new(conductor.0).await?;
+        let conductor = run::run_async(holochain_bin_path, setup_path.clone(), None).await?;
+        let cmd = CmdRunner::connect_setup(setup_path, conductor.0).await?;
         Ok((cmd, conductor.1))
     }
 
+    /// Connect to the running conductor for a setup, on its admin port
+    /// if it has one, otherwise on its admin socket.
+    /// The connection is authenticated if the admin interfaces require a token.
+    pub async fn connect_setup(setup_path: PathBuf, port: Option<u16>) -> anyhow::Result<Self> {
+        let mut cmd = match port {
+            Some(port) => Self::try_new(port).await?,
+            #[cfg(unix)]
+            None => {
+                let socket = config::read_config(setup_path.clone())?
+                    .as_ref()
+                    .and_then(config::admin_socket_path)
+                    .ok_or_else(|| anyhow::anyhow!("The setup has no admin interface"))?;
+                Self::try_new_unix(&socket).await?
+            }
+            #[cfg(not(unix))]
+            None => anyhow::bail!("The setup has no admin port"),
+        };
+        cmd.authenticate_setup(setup_path).await?;
+        Ok(cmd)
+    }
+
+    /// Authenticate with the admin token the conductor for this setup
+    /// wrote to its environment directory, if its admin interfaces require one.
+    pub async fn authenticate_setup(&mut self, setup_path: PathBuf) -> anyhow::Result<()> {
+        if let Some(conductor_config) = config::read_config(setup_path)?
+            .as_ref()
+            .filter(|c| config::requires_admin_token(c))
+        {
+            let token = config::read_admin_token(conductor_config)?
+                .ok_or_else(|| anyhow::anyhow!("The conductor has not written an admin token"))?;
+            self.authenticate(token).await?;
+        }
+        Ok(())
+    }
+
+    /// Authenticate this connection with an admin token,
+    /// for admin interfaces which require a token.
+    /// This must be done before any other request.
+    pub async fn authenticate(&mut self, token: InterfaceToken) -> anyhow::Result<()> {
+        let resp = self.command(AdminRequest::Authenticate { token }).await?;
+        anyhow::ensure!(
+            matches!(resp, AdminResponse::Authenticated),
+            "Failed to authenticate, got: {:?}",
+            resp
+        );
+        Ok(())
+    }
+
     /// Make an Admin request to this conductor.
     pub async fn command(&mut self, cmd: AdminRequest) -> anyhow::Result<AdminResponse> {
         let response: Result<AdminResponse, _> = self.client.request(cmd).await;
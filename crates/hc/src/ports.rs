@@ -1,17 +1,21 @@
 //! Helpers for working with websockets and ports.
+#[cfg(unix)]
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use holochain_conductor_api::{
     config::conductor::ConductorConfig, AdminInterfaceConfig, InterfaceDriver,
 };
+#[cfg(unix)]
+use holochain_websocket::websocket_connect_unix;
 use holochain_websocket::{websocket_connect, WebsocketConfig, WebsocketReceiver, WebsocketSender};
 use url2::prelude::*;
 
 use crate::config::read_config;
 use crate::config::write_config;
 
-/// Update the first admin interface to use this port.
+/// Update the first admin websocket interface to use this port.
 pub fn force_admin_port(path: PathBuf, port: u16) -> anyhow::Result<()> {
     let mut config = read_config(path.clone())?.expect("Failed to find config to force admin port");
     set_admin_port(&mut config, port);
@@ -24,13 +28,17 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
     let mut ports = Vec::new();
     for p in paths {
         if let Some(config) = read_config(p)? {
-            if let Some(ai) = config.admin_interfaces {
-                if let Some(AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port },
-                }) = ai.get(0)
-                {
-                    ports.push(*port)
-                }
+            if let Some(port) =
+                config
+                    .admin_interfaces
+                    .iter()
+                    .flatten()
+                    .find_map(|i| match i.driver {
+                        InterfaceDriver::Websocket { port, .. } => Some(port),
+                        _ => None,
+                    })
+            {
+                ports.push(port)
             }
         }
     }
@@ -42,6 +50,17 @@ pub(crate) async fn get_admin_api(port: u16) -> std::io::Result<WebsocketSender>
     websocket_client_by_port(port).await.map(|p| p.0)
 }
 
+/// Connect to an admin interface listening on a Unix domain socket.
+#[cfg(unix)]
+pub(crate) async fn get_admin_api_unix(path: &Path) -> std::io::Result<WebsocketSender> {
+    tracing::debug!(path = %path.display());
+    Ok(
+        websocket_connect_unix(path, Arc::new(WebsocketConfig::default()))
+            .await?
+            .0,
+    )
+}
+
 async fn websocket_client_by_port(
     port: u16,
 ) -> std::io::Result<(WebsocketSender, WebsocketReceiver)> {
@@ -52,44 +71,41 @@ async fn websocket_client_by_port(
     .await?)
 }
 
+/// Let the OS choose the port of the admin websocket interface, if there is one.
+/// Setups which only have an admin socket are left without a port.
 pub(crate) fn random_admin_port_if_busy(config: &mut ConductorConfig) {
-    match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
-        Some(AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
-        }) => {
-            if *port != 0 {
-                *port = 0;
-            }
-            // if !is_free(dbg!(*port)) {
-            //     dbg!("not free");
-            //     *port = pick_unused_port().expect("No ports free");
-            // }
-            // dbg!(*port)
-        }
-        None => {
-            // let port = pick_unused_port().expect("No ports free");
-            let port = 0;
-            config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
-            }]);
-        }
+    let websocket = config.admin_interfaces.as_mut().and_then(|i| {
+        i.iter_mut()
+            .find(|i| matches!(i.driver, InterfaceDriver::Websocket { .. }))
+    });
+    if let Some(AdminInterfaceConfig {
+        driver: InterfaceDriver::Websocket { port, .. },
+        ..
+    }) = websocket
+    {
+        *port = 0;
     }
 }
 
 pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
     let p = port;
     let port = AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port },
+        driver: InterfaceDriver::Websocket {
+            port,
+            bind_address: None,
+            tls: None,
+        },
+        require_token: false,
     };
-    match config
-        .admin_interfaces
-        .as_mut()
-        .and_then(|ai| ai.get_mut(0))
+    let admin_interfaces = config.admin_interfaces.get_or_insert_with(Vec::new);
+    match admin_interfaces
+        .iter_mut()
+        .find(|i| matches!(i.driver, InterfaceDriver::Websocket { .. }))
     {
         Some(admin_interface) => {
             *admin_interface = port;
         }
-        None => config.admin_interfaces = Some(vec![port]),
+        None => admin_interfaces.insert(0, port),
     }
     msg!("Admin port set to: {}", p);
 }
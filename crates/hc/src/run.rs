@@ -30,14 +30,22 @@ pub async fn run(
     force_admin_port: Option<u16>,
 ) -> anyhow::Result<()> {
     let (port, holochain) = run_async(holochain_path, setup_path.clone(), force_admin_port).await?;
-    msg!("Running conductor on admin port {}", port);
+    match port {
+        Some(port) => msg!("Running conductor on admin port {}", port),
+        None => msg!("Running conductor with an admin socket only"),
+    }
     for app_port in app_ports {
         msg!("Attaching app port {}", app_port);
-        let mut cmd = CmdRunner::try_new(port).await?;
+        let mut cmd = CmdRunner::connect_setup(setup_path.clone(), port).await?;
         attach_app_interface(
             &mut cmd,
             AddAppWs {
                 port: Some(app_port),
+                require_token: false,
+                bind_address: None,
+                tls_cert: None,
+                tls_key: None,
+                tls_host: Vec::new(),
             },
         )
         .await?;
@@ -56,11 +64,13 @@ pub async fn run(
 /// Can optionally force the admin port used. Otherwise
 /// the port in the config will be used if it's free or
 /// a random free port will be chosen.
+/// Returns the admin port, or `None` if the conductor
+/// only has an admin socket.
 pub async fn run_async(
     holochain_path: &Path,
     setup_path: PathBuf,
     force_admin_port: Option<u16>,
-) -> anyhow::Result<(u16, Child)> {
+) -> anyhow::Result<(Option<u16>, Child)> {
     let mut config = match read_config(setup_path.clone())? {
         Some(c) => c,
         None => create_config(setup_path.clone()),
@@ -77,14 +87,14 @@ pub async fn run_async(
     check_started(&mut child).await;
     let port = rx_config
         .await
-        .expect("Failed to get admin port from conductor");
+        .expect("Failed to get admin setup from conductor");
     Ok((port, child))
 }
 
 async fn start_holochain(
     holochain_path: &Path,
     config_path: PathBuf,
-    tx_config: oneshot::Sender<u16>,
+    tx_config: oneshot::Sender<Option<u16>>,
 ) -> Child {
     tracing::info!("\n\n----\nstarting holochain\n----\n\n");
     let mut cmd = Command::new(holochain_path);
@@ -110,7 +120,7 @@ async fn check_started(holochain: &mut Child) {
     }
 }
 
-fn spawn_output(holochain: &mut Child, config: oneshot::Sender<u16>) {
+fn spawn_output(holochain: &mut Child, config: oneshot::Sender<Option<u16>>) {
     let stdout = holochain.stdout.take();
     let stderr = holochain.stderr.take();
     tokio::task::spawn(async move {
@@ -124,12 +134,23 @@ fn spawn_output(holochain: &mut Child, config: oneshot::Sender<u16>) {
                         (true, Some(port)) => {
                             if let Some(config) = config.take() {
                                 config
-                                    .send(port)
+                                    .send(Some(port))
                                     .expect("Failed to send admin port from config");
                             }
                             continue;
                         }
-                        (true, None) => continue,
+                        (true, None) => {
+                            // The setup ended without an admin port
+                            // so the conductor only has an admin socket.
+                            if !needs_setup {
+                                if let Some(config) = config.take() {
+                                    config
+                                        .send(None)
+                                        .expect("Failed to send admin setup from config");
+                                }
+                            }
+                            continue;
+                        }
                         (false, _) => (),
                     }
                 }
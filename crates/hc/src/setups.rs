@@ -24,7 +24,7 @@ pub async fn default_with_network(
     } = create;
     let path = crate::generate::generate(network.map(|n| n.into_inner().into()), root, directory)?;
     let conductor = run_async(holochain_path, path.clone(), None).await?;
-    let mut cmd = CmdRunner::new(conductor.0).await;
+    let mut cmd = CmdRunner::connect_setup(path.clone(), conductor.0).await?;
     let install_app = InstallApp {
         app_id,
         agent_key: None,
@@ -1,6 +1,9 @@
 pub mod agent_info;
 pub mod call;
 pub mod call_remote;
+pub mod capability_claims;
+pub mod capability_grants;
+pub mod capability_info;
 pub mod create;
 pub mod create_link;
 pub mod delete;
@@ -12,6 +15,7 @@ pub mod get_agent_activity;
 pub mod get_details;
 pub mod get_link_details;
 pub mod get_links;
+pub mod get_links_page;
 pub mod property;
 pub mod query;
 pub mod random_bytes;
@@ -4,6 +4,9 @@ use crate::prelude::*;
 /// Make a Zome call in another Zome.
 /// The Zome can be in another Cell or the
 /// same Cell but must be installed on the same conductor.
+/// A call to another Cell runs on that Cell's source chain,
+/// is checked against that Cell's capability grants and
+/// must be allowed by the bridges of this Cell's app.
 ///
 /// ## Parameters
 /// - to_cell: The cell you want to call (If None will call the current cell).
@@ -21,7 +24,7 @@ pub fn call<I>(
 where
     I: serde::Serialize + std::fmt::Debug,
 {
-    // @todo is this secure to set this in the wasm rather than have the host inject it?
+    // The host replaces this with the agent of the calling cell.
     let provenance = agent_info()?.agent_latest_pubkey;
     host_call::<Call, ZomeCallResponse>(
         __call,
@@ -0,0 +1,14 @@
+use crate::prelude::*;
+
+/// List the CapClaims that are live on the local source chain.
+///
+/// Pass a tag to only return the claims with that tag, or `None` for every claim.
+///
+/// ```ignore
+/// let claims = capability_claims(Some("my_tag".into()))?;
+/// ```
+///
+/// As with `create_cap_claim` there is no guarantee that a listed claim is still usable.
+pub fn capability_claims(tag: Option<String>) -> ExternResult<Vec<CapClaim>> {
+    host_call::<Option<String>, Vec<CapClaim>>(__capability_claims, tag)
+}
@@ -0,0 +1,16 @@
+use crate::prelude::*;
+
+/// List the CapGrants that are live on the local source chain.
+///
+/// A grant is live if it has been committed and not since updated or deleted.
+/// Pass a tag to only return the grants with that tag, or `None` for every grant.
+///
+/// ```ignore
+/// let grants = capability_grants(Some("my_tag".into()))?;
+/// ```
+///
+/// Each grant is returned with the hash of the header that committed it so it can be passed to
+/// `update_cap_grant` or `delete_cap_grant`.
+pub fn capability_grants(tag: Option<String>) -> ExternResult<Vec<LiveCapGrant>> {
+    host_call::<Option<String>, Vec<LiveCapGrant>>(__capability_grants, tag)
+}
@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+/// Get the capability that authorized the current zome call.
+///
+/// The returned `CapInfo` includes the grant that matched the call and the `AgentPubKey` of the
+/// caller, so an extern can behave differently for different callers or grants.
+///
+/// ```ignore
+/// let cap_info = capability_info()?;
+/// match cap_info.grant {
+///     CapGrant::ChainAuthor(_) => { /* the chain author is calling their own cell */ },
+///     CapGrant::RemoteAgent(zome_call_cap_grant) => { /* ... */ },
+/// }
+/// ```
+///
+/// This is an error outside of a zome call, e.g. in callbacks like `init`, as they are called by
+/// the host without a capability.
+pub fn capability_info() -> ExternResult<CapInfo> {
+    host_call::<(), CapInfo>(__capability_info, ())
+}
@@ -6,10 +6,6 @@
 use crate::prelude::*;
 use holochain_zome_types::signal::AppSignal;
 
-// TODO: we could consider adding a (optional?) "type" parameter, so that
-// statically typed languages can more easily get a hint of what type to
-// deserialize to. This of course requires a corresponding change to the
-// Signal type.
 pub fn emit_signal<I>(input: I) -> ExternResult<()>
 where
     I: serde::Serialize + std::fmt::Debug,
@@ -17,3 +13,22 @@ where
     #[allow(clippy::unit_arg)]
     host_call::<AppSignal, ()>(__emit_signal, AppSignal::new(ExternIO::encode(input)?))
 }
+
+/// Emit a signal with an app-defined type.
+///
+/// Clients can subscribe to signals by type, and statically typed clients can
+/// use the type as a hint for what to deserialize the payload to.
+///
+/// ```ignore
+/// emit_typed_signal("message_received", &message)?;
+/// ```
+pub fn emit_typed_signal<T, I>(signal_type: T, input: I) -> ExternResult<()>
+where
+    T: Into<String>,
+    I: serde::Serialize + std::fmt::Debug,
+{
+    host_call::<AppSignal, ()>(
+        __emit_signal,
+        AppSignal::typed(signal_type.into(), ExternIO::encode(input)?),
+    )
+}
@@ -1 +1,20 @@
-//! @todo
+use crate::prelude::*;
+
+/// Get the properties the dna declares for one of the current zome's entry types.
+///
+/// Entry type properties are arbitrary application data set per entry def id in the dna, in the
+/// same way the dna `properties` are set for the whole dna. They are part of the dna hash so every
+/// agent sees the same properties, which makes them safe to use in validation.
+///
+/// Returns `None` if the dna doesn't declare properties for the entry type.
+///
+/// ```ignore
+/// let properties: Option<PostProperties> = entry_type_properties("post")?
+///     .map(PostProperties::try_from)
+///     .transpose()?;
+/// ```
+pub fn entry_type_properties<I: Into<EntryDefId>>(
+    entry_def_id: I,
+) -> ExternResult<Option<SerializedBytes>> {
+    host_call::<EntryDefId, Option<SerializedBytes>>(__entry_type_properties, entry_def_id.into())
+}
@@ -0,0 +1,37 @@
+use crate::prelude::*;
+
+/// Returns a page of the live links that reference a base entry hash,
+/// optionally filtered by tag in the same way as `get_links`.
+///
+/// Links are paged in the order they were created. The query can restrict them
+/// to a time range and limit how many are looked at for one page.
+/// Pass the `next` cursor of a page to the query for the following page.
+///
+/// e.g. to visit every link one hundred at a time:
+///
+/// ```ignore
+/// let mut query = LinkPageQuery::new().limit(std::num::NonZeroU32::new(100).unwrap());
+/// loop {
+///     let page = get_links_page(base.clone(), None, query.clone())?;
+///     // use page.links
+///     match page.next {
+///         Some(next) => query = query.after(next),
+///         None => break,
+///     }
+/// }
+/// ```
+///
+/// A page can have fewer links than the limit, even none, when some of the links
+/// it looked at were deleted. Only a `next` of `None` means there are no more pages.
+///
+/// @see get_links
+pub fn get_links_page(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    query: LinkPageQuery,
+) -> ExternResult<LinkPage> {
+    host_call::<GetLinksPageInput, LinkPage>(
+        __get_links_page,
+        GetLinksPageInput::new(base, link_tag, query),
+    )
+}
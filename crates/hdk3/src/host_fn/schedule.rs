@@ -1 +1,45 @@
-//! @todo
+use crate::prelude::*;
+
+/// Schedule a function in the current zome to be called by the host once the delay has elapsed.
+///
+/// The scheduled function must be an extern that accepts `()`:
+///
+/// ```ignore
+/// #[hdk_extern]
+/// fn tidy_up(_: ()) -> ExternResult<()> {
+///     // ...
+///     Ok(())
+/// }
+///
+/// schedule("tidy_up", core::time::Duration::from_secs(60))?;
+/// ```
+///
+/// The schedule is persisted by the host alongside the source chain so it survives restarts of
+/// the conductor. If the conductor was not running when the function was due it will be called
+/// as soon as the cell is running again.
+///
+/// The schedule is only persisted if the zome call that scheduled it completes successfully.
+/// Scheduling a function that is already scheduled replaces the existing schedule.
+pub fn schedule<F: Into<FunctionName>>(
+    fn_name: F,
+    delay: core::time::Duration,
+) -> ExternResult<()> {
+    host_call::<Schedule, ()>(__schedule, Schedule::once(fn_name.into(), delay))
+}
+
+/// Schedule a function in the current zome to be called by the host after the delay, and then
+/// every interval after that.
+///
+/// The same rules apply as for [`schedule`].
+/// To stop a recurring function, schedule it again with [`schedule`] so it runs one final time.
+/// The interval must be greater than zero.
+pub fn schedule_recurring<F: Into<FunctionName>>(
+    fn_name: F,
+    delay: core::time::Duration,
+    interval: core::time::Duration,
+) -> ExternResult<()> {
+    host_call::<Schedule, ()>(
+        __schedule,
+        Schedule::recurring(fn_name.into(), delay, interval),
+    )
+}
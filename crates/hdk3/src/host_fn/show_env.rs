@@ -1 +1,13 @@
-//! @todo
+use crate::prelude::*;
+
+/// Get information about the environment the current zome call is running in.
+/// There are no inputs to show_env.
+///
+/// The environment includes the dna hash, zome name, current agent, the version of the conductor
+/// hosting the cell and the network parameters of the dna.
+///
+/// The conductor version and network parameters can differ between agents so show_env is not
+/// available in deterministic callbacks such as validation.
+pub fn show_env() -> ExternResult<EnvInfo> {
+    host_call::<(), EnvInfo>(__show_env, ())
+}
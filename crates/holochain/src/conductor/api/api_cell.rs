@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use super::error::ConductorApiError;
 use super::error::ConductorApiResult;
+use crate::conductor::config::WasmLimitsConfig;
 use crate::conductor::entry_def_store::EntryDefBufferKey;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::conductor::ConductorHandle;
@@ -13,6 +14,7 @@ use async_trait::async_trait;
 use holo_hash::DnaHash;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::KeystoreSender;
+use holochain_p2p::dht_arc::DhtArc;
 use holochain_types::prelude::*;
 use tracing::*;
 
@@ -78,6 +80,10 @@ impl CellConductorApiT for CellConductorApi {
         self.conductor_handle.keystore()
     }
 
+    fn wasm_limits(&self) -> &WasmLimitsConfig {
+        self.conductor_handle.wasm_limits()
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.conductor_handle.signal_broadcaster().await
     }
@@ -107,6 +113,13 @@ impl CellConductorApiT for CellConductorApi {
         self.conductor_handle.get_entry_def(key).await
     }
 
+    async fn get_storage_arc(&self, agent: &AgentPubKey) -> ConductorApiResult<Option<DhtArc>> {
+        Ok(self
+            .conductor_handle
+            .get_storage_arc(self.cell_id.dna_hash(), agent)
+            .await?)
+    }
+
     fn into_call_zome_handle(self) -> CellConductorReadHandle {
         Arc::new(self)
     }
@@ -137,6 +150,9 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
     /// Request access to this conductor's keystore
     fn keystore(&self) -> &KeystoreSender;
 
+    /// The limits on the resources zome calls and callbacks can use
+    fn wasm_limits(&self) -> &WasmLimitsConfig;
+
     /// Access the broadcast Sender which will send a Signal across every
     /// attached app interface
     async fn signal_broadcaster(&self) -> SignalBroadcaster;
@@ -153,6 +169,10 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
     /// Get a [EntryDef] from the [EntryDefBuf]
     async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
 
+    /// Get the storage arc an agent is advertising in this cell's network,
+    /// if we hold its agent info
+    async fn get_storage_arc(&self, agent: &AgentPubKey) -> ConductorApiResult<Option<DhtArc>>;
+
     /// Turn this into a call zome handle
     fn into_call_zome_handle(self) -> CellConductorReadHandle;
 }
@@ -164,7 +184,9 @@ pub trait CellConductorReadHandleT: Send + Sync {
     /// Get this cell id
     fn cell_id(&self) -> &CellId;
 
-    /// Invoke a zome function on a Cell
+    /// Invoke a zome function on a Cell.
+    /// Calls to this Cell share the workspace of the calling zome call.
+    /// Calls to other Cells run in their own workspace.
     async fn call_zome(
         &self,
         call: ZomeCall,
@@ -191,7 +213,10 @@ impl CellConductorReadHandleT for CellConductorApi {
                 .call_zome_with_workspace(call, workspace_lock.clone())
                 .await
         } else {
-            self.conductor_handle.call_zome(call).await
+            // Another Cell must not see this call's workspace
+            self.conductor_handle
+                .bridge_call_zome(&self.cell_id, call)
+                .await
         }
     }
 
@@ -5,6 +5,7 @@ use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::SerializationError;
 
 use crate::conductor::error::CreateAppError;
+use crate::conductor::interface::auth::ConnectionAuth;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
 use crate::conductor::ConductorHandle;
@@ -29,6 +30,10 @@ pub trait AdminInterfaceApi: 'static + Send + Sync + Clone {
         request: AdminRequest,
     ) -> ConductorApiResult<AdminResponse>;
 
+    /// A copy of this API to handle a new client connection,
+    /// which must authenticate separately
+    fn for_connection(&self) -> Self;
+
     // -- provided -- //
 
     /// Deal with error cases produced by `handle_admin_request_inner`
@@ -49,11 +54,25 @@ pub trait AdminInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAdminInterfaceApi {
     /// Mutable access to the Conductor
     conductor_handle: ConductorHandle,
+    /// The authentication state of the connection this API is handling
+    auth: ConnectionAuth,
 }
 
 impl RealAdminInterfaceApi {
     pub(crate) fn new(conductor_handle: ConductorHandle) -> Self {
-        RealAdminInterfaceApi { conductor_handle }
+        RealAdminInterfaceApi {
+            conductor_handle,
+            auth: ConnectionAuth::default(),
+        }
+    }
+
+    /// Set whether connections must authenticate with an admin token
+    pub(crate) fn require_token(self, require_token: bool) -> Self {
+        let auth = ConnectionAuth::new(
+            self.conductor_handle.interface_tokens().clone(),
+            require_token,
+        );
+        RealAdminInterfaceApi { auth, ..self }
     }
 }
 
@@ -64,6 +83,11 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
         request: AdminRequest,
     ) -> ConductorApiResult<AdminResponse> {
         use AdminRequest::*;
+        if let Authenticate { token } = request {
+            self.auth.authenticate_admin(token)?;
+            return Ok(AdminResponse::Authenticated);
+        }
+        self.auth.check_admin()?;
         match request {
             AddAdminInterfaces(configs) => {
                 self.conductor_handle
@@ -115,6 +139,12 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     dnas,
                 } = *payload;
 
+                // Collect the cells each cell may call
+                let bridges: AppBridges = dnas
+                    .iter()
+                    .filter_map(|dna| Some((dna.nick.clone(), dna.bridges.clone()?)))
+                    .collect();
+
                 // Install Dnas
                 let tasks = dnas.into_iter().map(|dna_payload| async {
                     let InstallAppDnaPayload {
@@ -123,6 +153,7 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                         properties,
                         membrane_proof,
                         nick,
+                        bridges: _,
                     } = dna_payload;
                     if maybe_path.is_none() && maybe_hash.is_none() {
                         return Err(ConductorApiError::DnaReadError("Neither path nor hash specified in payload".to_string()))
@@ -161,7 +192,11 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 // Call genesis
                 self.conductor_handle
                     .clone()
-                    .install_app(installed_app_id.clone(), cell_ids_with_proofs.clone())
+                    .install_app(
+                        installed_app_id.clone(),
+                        cell_ids_with_proofs.clone(),
+                        bridges.clone(),
+                    )
                     .await?;
 
                 let cell_data = cell_ids_with_proofs
@@ -171,6 +206,7 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let app = InstalledApp {
                     installed_app_id,
                     cell_data,
+                    bridges,
                 };
                 Ok(AdminResponse::AppInstalled(app))
             }
@@ -226,18 +262,82 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDeactivated)
             }
-            AttachAppInterface { port } => {
+            UninstallApp { installed_app_id } => {
+                self.conductor_handle
+                    .uninstall_app(installed_app_id)
+                    .await?;
+                Ok(AdminResponse::AppUninstalled)
+            }
+            MigrateAgent {
+                installed_app_id,
+                cell_nick,
+                new_dna_hash,
+                membrane_proof,
+            } => {
+                let cell_id = self
+                    .conductor_handle
+                    .clone()
+                    .migrate_agent(installed_app_id, cell_nick, new_dna_hash, membrane_proof)
+                    .await?;
+                Ok(AdminResponse::AgentMigrated(cell_id))
+            }
+            AttachAppInterface {
+                port,
+                require_token,
+                bind_address,
+                tls,
+            } => {
                 let port = port.unwrap_or(0);
-                let port = self
+                let driver = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(port)
+                    .add_app_interface(
+                        InterfaceDriver::Websocket {
+                            port,
+                            bind_address,
+                            tls,
+                        },
+                        require_token,
+                    )
                     .await?;
-                Ok(AdminResponse::AppInterfaceAttached { port })
+                match driver {
+                    InterfaceDriver::Websocket { port, .. } => {
+                        Ok(AdminResponse::AppInterfaceAttached { port })
+                    }
+                    InterfaceDriver::UnixSocket { .. } => {
+                        unreachable!("A websocket driver was requested")
+                    }
+                }
             }
-            DumpState { cell_id } => {
-                let state = self.conductor_handle.dump_cell_state(&cell_id).await?;
-                Ok(AdminResponse::StateDumped(state))
+            AttachAppUnixSocket {
+                path,
+                require_token,
+            } => {
+                self.conductor_handle
+                    .clone()
+                    .add_app_interface(
+                        InterfaceDriver::UnixSocket { path: path.clone() },
+                        require_token,
+                    )
+                    .await?;
+                Ok(AdminResponse::AppUnixSocketAttached { path })
+            }
+            DumpState { cell_id, options } => {
+                let state = self
+                    .conductor_handle
+                    .dump_cell_state(&cell_id, options)
+                    .await?;
+                Ok(AdminResponse::StateDumped(Box::new(state)))
+            }
+            GetValidationReceipts {
+                cell_id,
+                dht_op_hash,
+            } => {
+                let receipts = self
+                    .conductor_handle
+                    .get_validation_receipts(&cell_id, &dht_op_hash)
+                    .await?;
+                Ok(AdminResponse::ValidationReceipts(receipts))
             }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
@@ -247,6 +347,22 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let r = self.conductor_handle.get_agent_infos(cell_id).await?;
                 Ok(AdminResponse::AgentInfoRequested(r))
             }
+            IssueInterfaceToken(payload) => {
+                let issued = self.conductor_handle.issue_interface_token(payload).await?;
+                Ok(AdminResponse::InterfaceTokenIssued(issued))
+            }
+            RevokeInterfaceToken { token } => {
+                self.conductor_handle.interface_tokens().revoke(&token);
+                Ok(AdminResponse::InterfaceTokenRevoked)
+            }
+            Authenticate { .. } => unreachable!("Handled above"),
+        }
+    }
+
+    fn for_connection(&self) -> Self {
+        RealAdminInterfaceApi {
+            conductor_handle: self.conductor_handle.clone(),
+            auth: self.auth.for_connection(),
         }
     }
 }
@@ -296,6 +412,9 @@ mod test {
     use super::*;
     use crate::conductor::Conductor;
     use anyhow::Result;
+    use holochain_conductor_api::auth::InterfaceToken;
+    use holochain_conductor_api::auth::IssueTokenPayload;
+    use holochain_conductor_api::auth::TokenScope;
     use holochain_lmdb::test_utils::test_environments;
     use holochain_types::app::InstallAppDnaPayload;
     use holochain_types::test_utils::fake_agent_pubkey_1;
@@ -461,6 +580,7 @@ mod test {
         let expected_cell_ids = InstalledApp {
             installed_app_id: "test-by-path".to_string(),
             cell_data: vec![InstalledCell::new(cell_id2.clone(), "".to_string())],
+            bridges: AppBridges::new(),
         };
         let path_install_payload = InstallAppPayload {
             dnas: vec![path_payload],
@@ -516,6 +636,88 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn admin_token_required() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let open_api = RealAdminInterfaceApi::new(handle.clone());
+        let admin_api = RealAdminInterfaceApi::new(handle.clone())
+            .require_token(true)
+            .for_connection();
+
+        let res = admin_api.handle_admin_request(AdminRequest::ListDnas).await;
+        assert_matches!(
+            res,
+            AdminResponse::Error(ExternalApiWireError::Unauthorized(_))
+        );
+
+        // App tokens can only be issued for installed apps
+        let res = open_api
+            .handle_admin_request(AdminRequest::IssueInterfaceToken(IssueTokenPayload {
+                scope: TokenScope::App(vec!["not installed".to_string()]),
+                expires_in_seconds: None,
+            }))
+            .await;
+        assert_matches!(res, AdminResponse::Error(_));
+
+        let token = match open_api
+            .handle_admin_request(AdminRequest::IssueInterfaceToken(IssueTokenPayload {
+                scope: TokenScope::Admin,
+                expires_in_seconds: Some(60),
+            }))
+            .await
+        {
+            AdminResponse::InterfaceTokenIssued(issued) => issued.token,
+            other => panic!("Unexpected response {:?}", other),
+        };
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::Authenticate {
+                token: InterfaceToken("wrong".to_string()),
+            })
+            .await;
+        assert_matches!(
+            res,
+            AdminResponse::Error(ExternalApiWireError::Unauthorized(_))
+        );
+        let res = admin_api
+            .handle_admin_request(AdminRequest::Authenticate {
+                token: token.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::Authenticated);
+        let res = admin_api.handle_admin_request(AdminRequest::ListDnas).await;
+        assert_matches!(res, AdminResponse::DnasListed(_));
+
+        // A new connection has to authenticate again
+        let res = admin_api
+            .for_connection()
+            .handle_admin_request(AdminRequest::ListDnas)
+            .await;
+        assert_matches!(
+            res,
+            AdminResponse::Error(ExternalApiWireError::Unauthorized(_))
+        );
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::RevokeInterfaceToken { token })
+            .await;
+        assert_matches!(res, AdminResponse::InterfaceTokenRevoked);
+        let res = admin_api.handle_admin_request(AdminRequest::ListDnas).await;
+        assert_matches!(
+            res,
+            AdminResponse::Error(ExternalApiWireError::Unauthorized(_))
+        );
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn dna_read_parses() -> Result<()> {
         let uuid = Uuid::new_v4();
@@ -2,14 +2,18 @@ use super::InterfaceApi;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
+use crate::conductor::error::ConductorError;
+use crate::conductor::interface::auth::ConnectionAuth;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::interface::ConnectionSignalFilter;
 use crate::conductor::state::AppInterfaceId;
 use crate::conductor::ConductorHandle;
 
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use std::collections::HashSet;
 
 pub use holochain_conductor_api::*;
 
@@ -22,6 +26,10 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
         request: AppRequest,
     ) -> ConductorApiResult<AppResponse>;
 
+    /// A copy of this API to handle a new client connection, with its own signal
+    /// subscriptions, along with the filter for the signals sent to that client
+    fn for_connection(&self) -> (Self, ConnectionSignalFilter);
+
     // -- provided -- //
 
     /// Deal with error cases produced by `handle_app_request_inner`
@@ -41,6 +49,10 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
     interface_id: AppInterfaceId,
+    /// The authentication state of the connection this API is handling
+    auth: ConnectionAuth,
+    /// The signal subscriptions of the connection this API is handling
+    signal_filter: ConnectionSignalFilter,
 }
 
 impl RealAppInterfaceApi {
@@ -49,6 +61,21 @@ impl RealAppInterfaceApi {
         Self {
             conductor_handle,
             interface_id,
+            auth: ConnectionAuth::default(),
+            signal_filter: ConnectionSignalFilter::default(),
+        }
+    }
+
+    /// Set whether connections must authenticate with an app token
+    pub fn require_token(self, require_token: bool) -> Self {
+        let auth = ConnectionAuth::new(
+            self.conductor_handle.interface_tokens().clone(),
+            require_token,
+        );
+        Self {
+            signal_filter: ConnectionSignalFilter::new(auth.clone()),
+            auth,
+            ..self
         }
     }
 }
@@ -61,11 +88,29 @@ impl AppInterfaceApi for RealAppInterfaceApi {
         request: AppRequest,
     ) -> ConductorApiResult<AppResponse> {
         match request {
-            AppRequest::AppInfo { installed_app_id } => Ok(AppResponse::AppInfo(
-                self.conductor_handle
-                    .get_app_info(&installed_app_id)
-                    .await?,
-            )),
+            AppRequest::Authenticate { token } => {
+                let mut cells = HashSet::new();
+                for installed_app_id in self.auth.app_scope(&token)? {
+                    // Apps which have since been uninstalled give access to nothing
+                    if let Some(app) = self
+                        .conductor_handle
+                        .get_app_info(&installed_app_id)
+                        .await?
+                    {
+                        cells.extend(app.cell_data.into_iter().map(|cell| cell.into_id()));
+                    }
+                }
+                self.auth.authenticate_app(token, cells);
+                Ok(AppResponse::Authenticated)
+            }
+            AppRequest::AppInfo { installed_app_id } => {
+                self.auth.check_app(&installed_app_id)?;
+                Ok(AppResponse::AppInfo(
+                    self.conductor_handle
+                        .get_app_info(&installed_app_id)
+                        .await?,
+                ))
+            }
             AppRequest::ZomeCallInvocation(call) => {
                 tracing::warn!(
                     "AppRequest::ZomeCallInvocation is deprecated, use AppRequest::ZomeCall (TODO: update conductor-api)"
@@ -82,6 +127,7 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     })
             }
             AppRequest::ZomeCall(call) => {
+                self.auth.check_cell(&call.cell_id)?;
                 match self.conductor_handle.call_zome(*call.clone()).await? {
                     Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCall(Box::new(output))),
                     Ok(ZomeCallResponse::Unauthorized(_, _, _, _)) => Ok(AppResponse::Error(
@@ -97,10 +143,40 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     Err(e) => Ok(AppResponse::Error(e.into())),
                 }
             }
-            AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
-            AppRequest::Crypto(_) => Ok(AppResponse::Unimplemented(request)),
+            AppRequest::SignalSubscription(subscription) => {
+                let installed_app_id = subscription.installed_app_id().clone();
+                self.auth.check_app(&installed_app_id)?;
+                let app = self
+                    .conductor_handle
+                    .get_app_info(&installed_app_id)
+                    .await?
+                    .ok_or(ConductorError::AppNotInstalled(installed_app_id))?;
+                let cells = app
+                    .cell_data
+                    .into_iter()
+                    .map(|cell| cell.into_id())
+                    .collect();
+                self.signal_filter.subscribe(subscription, cells);
+                Ok(AppResponse::SignalSubscriptionUpdated)
+            }
+            AppRequest::Crypto(_) => {
+                self.auth.check_authenticated()?;
+                Ok(AppResponse::Unimplemented(request))
+            }
         }
     }
+
+    fn for_connection(&self) -> (Self, ConnectionSignalFilter) {
+        let auth = self.auth.for_connection();
+        let signal_filter = ConnectionSignalFilter::new(auth.clone());
+        let api = Self {
+            conductor_handle: self.conductor_handle.clone(),
+            interface_id: self.interface_id.clone(),
+            auth,
+            signal_filter: signal_filter.clone(),
+        };
+        (api, signal_filter)
+    }
 }
 
 #[async_trait::async_trait]
@@ -36,6 +36,15 @@ pub enum ConductorApiError {
         call_cell_id: CellId,
     },
 
+    /// A Cell tried to call a Cell its app doesn't let it call.
+    #[error("The app of Cell {from_cell:?} does not allow it to call Cell {to_cell:?}")]
+    BridgeNotAllowed {
+        /// The Cell making the call
+        from_cell: CellId,
+        /// The Cell being called
+        to_cell: CellId,
+    },
+
     /// Conductor threw an error during API call.
     #[error("Conductor returned an error while using a ConductorApi: {0:?}")]
     ConductorError(#[from] ConductorError),
@@ -84,6 +93,10 @@ pub enum ConductorApiError {
 
     #[error(transparent)]
     SourceChainError(#[from] SourceChainError),
+
+    /// The connection is not authenticated for this request
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
 }
 
 /// All the serialization errors that can occur
@@ -107,6 +120,7 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::Unauthorized(e) => ExternalApiWireError::Unauthorized(e),
             e => ExternalApiWireError::internal(e),
         }
     }
@@ -4,12 +4,14 @@
 use super::CellConductorApiT;
 use super::ZomeCall;
 use crate::conductor::api::error::ConductorApiResult;
+use crate::conductor::config::WasmLimitsConfig;
 use crate::conductor::entry_def_store::EntryDefBufferKey;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
 use holochain_keystore::KeystoreSender;
+use holochain_p2p::dht_arc::DhtArc;
 use holochain_types::prelude::*;
 use mockall::mock;
 
@@ -33,11 +35,13 @@ mock! {
         fn sync_dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
 
         fn mock_keystore(&self) -> &KeystoreSender;
+        fn mock_wasm_limits(&self) -> &WasmLimitsConfig;
         fn mock_signal_broadcaster(&self) -> SignalBroadcaster;
         fn sync_get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile>;
         fn sync_get_this_dna(&self) -> ConductorApiResult<DnaFile>;
         fn sync_get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
         fn sync_get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
+        fn sync_get_storage_arc(&self, agent: &AgentPubKey) -> ConductorApiResult<Option<DhtArc>>;
         fn into_call_zome_handle(self) -> super::CellConductorReadHandle;
     }
 
@@ -72,6 +76,10 @@ impl CellConductorApiT for MockCellConductorApi {
         self.mock_keystore()
     }
 
+    fn wasm_limits(&self) -> &WasmLimitsConfig {
+        self.mock_wasm_limits()
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.mock_signal_broadcaster()
     }
@@ -92,6 +100,10 @@ impl CellConductorApiT for MockCellConductorApi {
         self.sync_get_entry_def(key)
     }
 
+    async fn get_storage_arc(&self, agent: &AgentPubKey) -> ConductorApiResult<Option<DhtArc>> {
+        self.sync_get_storage_arc(agent)
+    }
+
     fn into_call_zome_handle(self) -> super::CellConductorReadHandle {
         self.into_call_zome_handle()
     }
@@ -7,6 +7,7 @@
 use super::api::ZomeCall;
 use super::interface::SignalBroadcaster;
 use super::manager::ManagedTaskAdd;
+use super::manager::ManagedTaskResult;
 use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
@@ -15,8 +16,10 @@ use crate::conductor::entry_def_store::get_entry_def_from_ids;
 use crate::conductor::handle::ConductorHandle;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
+use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
@@ -24,6 +27,9 @@ use crate::core::workflow::error::WorkflowError;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
 use crate::core::workflow::incoming_dht_ops_workflow::incoming_dht_ops_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::run_migrate_agent_callback;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
 use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::light_to_op;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::CallZomeWorkspace;
@@ -38,7 +44,10 @@ use futures::future::FutureExt;
 use hash_type::AnyDht;
 use holo_hash::*;
 use holochain_cascade::authority;
+use holochain_conductor_api::state_dump::*;
 use holochain_lmdb::db::GetDb;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::db::INTEGRATION_LIMBO;
 use holochain_lmdb::env::EnvironmentRead;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::env::ReadManager;
@@ -102,6 +111,8 @@ where
     env: EnvironmentWrite,
     holochain_p2p_cell: P2pCell,
     queue_triggers: QueueTriggers,
+    /// Stops this Cell's queue consumers without stopping the Conductor
+    workflow_stop_broadcaster: sync::broadcast::Sender<()>,
 }
 
 impl Cell {
@@ -113,7 +124,7 @@ impl Cell {
         conductor_handle: ConductorHandle,
         env: EnvironmentWrite,
         mut holochain_p2p_cell: holochain_p2p::HolochainP2pCell,
-        managed_task_add_sender: sync::mpsc::Sender<ManagedTaskAdd>,
+        mut managed_task_add_sender: sync::mpsc::Sender<ManagedTaskAdd>,
         managed_task_stop_broadcaster: sync::broadcast::Sender<()>,
     ) -> CellResult<(Self, InitialQueueTriggers)> {
         let conductor_api = CellConductorApi::new(conductor_handle.clone(), id.clone());
@@ -126,12 +137,38 @@ impl Cell {
 
         if has_genesis {
             holochain_p2p_cell.join().await?;
+
+            // The queue consumers stop when either the Conductor shuts down
+            // or this Cell is removed.
+            let (workflow_stop_broadcaster, _) = sync::broadcast::channel(1);
+            let handle = tokio::task::spawn({
+                let mut conductor_stop = managed_task_stop_broadcaster.subscribe();
+                let mut cell_stop = workflow_stop_broadcaster.subscribe();
+                let workflow_stop_broadcaster = workflow_stop_broadcaster.clone();
+                async move {
+                    let conductor_stop = conductor_stop.recv();
+                    let cell_stop = cell_stop.recv();
+                    tokio::pin!(conductor_stop);
+                    tokio::pin!(cell_stop);
+                    if let futures::future::Either::Left(_) =
+                        futures::future::select(conductor_stop, cell_stop).await
+                    {
+                        workflow_stop_broadcaster.send(()).ok();
+                    }
+                    ManagedTaskResult::Ok(())
+                }
+            });
+            managed_task_add_sender
+                .send(ManagedTaskAdd::dont_handle(handle))
+                .await
+                .expect("Failed to manage workflow handle");
+
             let (queue_triggers, initial_queue_triggers) = spawn_queue_consumer_tasks(
                 &env,
                 holochain_p2p_cell.clone(),
                 conductor_api.clone(),
                 managed_task_add_sender,
-                managed_task_stop_broadcaster,
+                workflow_stop_broadcaster.clone(),
             )
             .await;
 
@@ -142,6 +179,7 @@ impl Cell {
                     env,
                     holochain_p2p_cell,
                     queue_triggers,
+                    workflow_stop_broadcaster,
                 },
                 initial_queue_triggers,
             ))
@@ -196,6 +234,11 @@ impl Cell {
         &self.id
     }
 
+    /// Stop the queue consumers running workflows for this Cell
+    pub(super) fn stop_workflows(&self) {
+        self.workflow_stop_broadcaster.send(()).ok();
+    }
+
     /// Access a network sender that is partially applied to this cell's DnaHash/AgentPubKey
     pub fn holochain_p2p_cell(&self) -> &holochain_p2p::HolochainP2pCell {
         &self.holochain_p2p_cell
@@ -409,12 +452,12 @@ impl Cell {
         Ok(())
     }
 
-    #[instrument(skip(self, _request_validation_receipt, _dht_hash, ops))]
+    #[instrument(skip(self, request_validation_receipt, _dht_hash, ops))]
     /// we are receiving a "publish" event from the network
     async fn handle_publish(
         &self,
         from_agent: AgentPubKey,
-        _request_validation_receipt: bool,
+        request_validation_receipt: bool,
         _dht_hash: holo_hash::AnyDhtHash,
         ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
     ) -> CellResult<()> {
@@ -423,6 +466,7 @@ impl Cell {
             self.queue_triggers.sys_validation.clone(),
             ops,
             Some(from_agent),
+            request_validation_receipt,
         )
         .await
         .map_err(Box::new)
@@ -550,8 +594,66 @@ impl Cell {
 
     /// a remote agent is sending us a validation receipt.
     #[tracing::instrument(skip(self))]
-    async fn handle_validation_receipt(&self, _receipt: SerializedBytes) -> CellResult<()> {
-        unimplemented!()
+    async fn handle_validation_receipt(&self, receipt: SerializedBytes) -> CellResult<()> {
+        let receipt: SignedValidationReceipt = receipt.try_into()?;
+        if !receipt.verify().await? {
+            warn!(
+                ?receipt,
+                "Dropping validation receipt with an invalid signature"
+            );
+            return Ok(());
+        }
+        let dht_op_hash = receipt.receipt.dht_op_hash.clone();
+
+        let env: EnvironmentRead = self.env.clone().into();
+        let authored_db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = AuthoredDhtOpsStore::new(env.clone(), authored_db);
+
+        // We only hold receipts for ops we authored
+        let basis = match authored_dht_ops.get(&dht_op_hash)? {
+            Some(authored) => authored.op.dht_basis().clone(),
+            None => {
+                warn!(
+                    ?dht_op_hash,
+                    "Dropping validation receipt for an op we didn't author"
+                );
+                return Ok(());
+            }
+        };
+
+        // Only an authority for the op's basis can vouch for it
+        let is_authority = self
+            .conductor_api
+            .get_storage_arc(&receipt.receipt.validator)
+            .await?
+            .map(|arc| arc.contains(basis.get_loc()))
+            .unwrap_or(false);
+        if !is_authority {
+            warn!(
+                ?receipt,
+                "Dropping validation receipt from a validator that isn't an authority for the op"
+            );
+            return Ok(());
+        }
+
+        let mut receipts = ValidationReceiptsBuf::new(&env)?;
+        receipts.add_if_unique(receipt)?;
+
+        // The count is read and written in the same transaction as the
+        // receipt so concurrent receipts can't overwrite each other's count.
+        // Valid receipts count towards the op being held by enough
+        // validators to stop publishing.
+        let writer: OneshotWriter = self.env.clone().into();
+        writer.with_writer(|writer| {
+            receipts.flush_to_txn_ref(writer)?;
+            let store = authored_dht_ops.store();
+            if let Some(mut authored) = store.get(writer, &dht_op_hash)? {
+                authored.receipt_count = receipts.count_valid(writer, &dht_op_hash)? as u32;
+                store.put(writer, &dht_op_hash, &authored)?;
+            }
+            Ok(())
+        })?;
+        Ok(())
     }
 
     #[instrument(skip(self, dht_arc, since, until))]
@@ -567,6 +669,8 @@ impl Cell {
         let integrated_dht_ops = IntegratedDhtOpsBuf::new(self.env().clone().into())?;
         let result: Vec<DhtOpHash> = integrated_dht_ops
             .query(&reader, Some(since), Some(until), Some(dht_arc))?
+            // Abandoned ops can't be gossiped because their entries are dropped
+            .filter(|(_, v)| Ok(v.validation_status != ValidationStatus::Abandoned))
             .map(|(k, _)| Ok(k))
             .collect()?;
         Ok(result)
@@ -598,7 +702,8 @@ impl Cell {
                         let cas = ElementBuf::rejected(self.env.clone().into())?;
                         light_to_op(val.op, &cas)?
                     }
-                    ValidationStatus::Abandoned => todo!("Add when abandoned store is added"),
+                    // We don't hold the entries for abandoned ops
+                    ValidationStatus::Abandoned => continue,
                 };
                 let basis = full_op.dht_basis();
                 out.push((basis, op_hash, full_op));
@@ -686,6 +791,7 @@ impl Cell {
             arc.clone().into(),
             args,
             self.queue_triggers.produce_dht_ops.clone(),
+            self.queue_triggers.schedule.clone(),
         )
         .await
         .map_err(Box::new)?)
@@ -718,7 +824,7 @@ impl Cell {
         let dna_def = dna_file.dna_def().clone();
 
         // Get the ribosome
-        let ribosome = RealRibosome::new(dna_file);
+        let ribosome = RealRibosome::new(dna_file).with_limits(conductor_api.wasm_limits().clone());
 
         // Run the workflow
         let args = InitializeZomesWorkflowArgs { dna_def, ribosome };
@@ -739,6 +845,50 @@ impl Cell {
         Ok(())
     }
 
+    /// Run the migrate_agent callback and link this Cell's chain to the
+    /// other end of the migration with a CloseChain or OpenChain header.
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn migrate_agent(
+        &self,
+        migrate_agent: MigrateAgent,
+        other_dna_hash: DnaHash,
+    ) -> CellResult<()> {
+        let workspace = CallZomeWorkspace::new(self.env().clone().into())
+            .map_err(WorkflowError::from)
+            .map_err(Box::new)?;
+        let ribosome = self.get_ribosome().await?;
+        let dna_def = ribosome.dna_file().dna_def().clone();
+        let args = MigrateAgentWorkflowArgs::new(dna_def, migrate_agent, other_dna_hash, ribosome);
+        let result = migrate_agent_workflow(workspace, self.env.clone().into(), args)
+            .await
+            .map_err(Box::new)?;
+        match result {
+            MigrateAgentResult::Pass => {}
+            r => return Err(CellError::MigrateAgentFailed(r)),
+        }
+        // Publish the new header
+        self.queue_triggers.produce_dht_ops.clone().trigger();
+        Ok(())
+    }
+
+    /// Run the migrate_agent callback without committing anything,
+    /// to check this Cell's zomes allow the migration.
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn check_migrate_agent(&self, migrate_agent: MigrateAgent) -> CellResult<()> {
+        let workspace = CallZomeWorkspace::new(self.env().clone().into())
+            .map_err(WorkflowError::from)
+            .map_err(Box::new)?;
+        let ribosome = self.get_ribosome().await?;
+        let dna_def = ribosome.dna_file().dna_def().clone();
+        let result = run_migrate_agent_callback(workspace, dna_def, migrate_agent, &ribosome)
+            .await
+            .map_err(Box::new)?;
+        match result {
+            MigrateAgentResult::Pass => Ok(()),
+            r => Err(CellError::MigrateAgentFailed(r)),
+        }
+    }
+
     /// Delete all data associated with this Cell by deleting the associated
     /// LMDB environment. Completely reverses Cell creation.
     #[tracing::instrument(skip(self))]
@@ -757,7 +907,9 @@ impl Cell {
     // TODO: reevaluate once Workflows are fully implemented (after B-01567)
     pub(crate) async fn get_ribosome(&self) -> CellResult<RealRibosome> {
         match self.conductor_api.get_dna(self.dna_hash()).await {
-            Some(dna) => Ok(RealRibosome::new(dna)),
+            Some(dna) => {
+                Ok(RealRibosome::new(dna).with_limits(self.conductor_api.wasm_limits().clone()))
+            }
             None => Err(CellError::DnaMissing),
         }
     }
@@ -768,6 +920,93 @@ impl Cell {
         &self.env
     }
 
+    /// Dump the state of this Cell's environment for debugging
+    pub(crate) fn dump_state(&self, options: DumpStateOptions) -> CellResult<CellStateDump> {
+        let env: EnvironmentRead = self.env.clone().into();
+
+        let source_chain = SourceChainBuf::new(env.clone())?;
+        let mut headers: Vec<SignedHeaderHashed> = source_chain.iter_back().collect()?;
+        headers.reverse();
+        let mut chain = Vec::with_capacity(headers.len());
+        for header in headers {
+            let entry = if options.include_entries {
+                source_chain
+                    .get_element(header.header_address())?
+                    .and_then(|element| element.entry().as_option().cloned())
+            } else {
+                None
+            };
+            let (header, signature) = header.into_header_and_signature();
+            let (header, header_address) = header.into_inner();
+            chain.push(SourceChainDumpElement {
+                header_address,
+                header,
+                signature,
+                entry,
+            });
+        }
+
+        let authored_dht_ops =
+            AuthoredDhtOpsStore::new(env.clone(), env.get_db(&*AUTHORED_DHT_OPS)?);
+        let validation_limbo = ValidationLimboStore::new(env.clone())?;
+        let integration_limbo =
+            IntegrationLimboStore::new(env.clone(), env.get_db(&*INTEGRATION_LIMBO)?);
+        let integrated_dht_ops = IntegratedDhtOpsBuf::new(env.clone())?;
+        let receipts = ValidationReceiptsBuf::new(&env)?;
+
+        let (dht_ops, validation_receipts) = {
+            let env_ref = env.guard();
+            let r = env_ref.reader()?;
+            let authored: Vec<DhtOpHash> = authored_dht_ops
+                .iter(&r)?
+                .map(|(k, _)| Ok(DhtOpHash::from_raw_39_panicky(k.to_vec())))
+                .collect()?;
+            let dht_ops = DhtOpsDump {
+                authored: authored.len(),
+                validation_limbo: validation_limbo.iter(&r)?.count()?,
+                integration_limbo: integration_limbo.iter(&r)?.count()?,
+                integrated: integrated_dht_ops.iter(&r)?.count()?,
+            };
+            let mut validation_receipts = Vec::with_capacity(authored.len());
+            for dht_op_hash in authored {
+                validation_receipts.push(OpReceiptsDump {
+                    receipts: receipts.list_receipts(&r, &dht_op_hash)?.collect()?,
+                    dht_op_hash,
+                });
+            }
+            (dht_ops, validation_receipts)
+        };
+
+        let databases = holochain_lmdb::db::db_stats(&env)?
+            .into_iter()
+            .map(|(db, stats)| DbSizeDump {
+                db: db.to_string(),
+                entries: stats.entries,
+                bytes: stats.bytes,
+            })
+            .collect();
+
+        Ok(CellStateDump {
+            cell_id: self.id.clone(),
+            source_chain: chain,
+            dht_ops,
+            validation_receipts,
+            databases,
+        })
+    }
+
+    /// List the validation receipts we have received for an op we authored.
+    pub(crate) fn get_validation_receipts(
+        &self,
+        dht_op_hash: &DhtOpHash,
+    ) -> CellResult<Vec<SignedValidationReceipt>> {
+        let env: EnvironmentRead = self.env.clone().into();
+        let receipts = ValidationReceiptsBuf::new(&env)?;
+        let env_ref = env.guard();
+        let r = env_ref.reader()?;
+        Ok(receipts.list_receipts(&r, dht_op_hash)?.collect()?)
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     /// Get the triggers for the cell
     /// Useful for testing when you want to
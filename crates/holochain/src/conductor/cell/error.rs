@@ -2,10 +2,12 @@ use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::workflow::error::WorkflowError;
 use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
 use crate::core::SourceChainError;
 use holochain_cascade::error::CascadeError;
+use holochain_keystore::KeystoreError;
 use holochain_lmdb::error::DatabaseError;
 use holochain_p2p::HolochainP2pError;
 use holochain_types::prelude::*;
@@ -48,6 +50,8 @@ pub enum CellError {
     SourceChainError(#[from] SourceChainError),
     #[error("The cell tried to run the initialize zomes callback but failed because {0:?}")]
     InitFailed(InitResult),
+    #[error("The cell tried to run the migrate agent callback but failed because {0:?}")]
+    MigrateAgentFailed(MigrateAgentResult),
     #[error(transparent)]
     HolochainP2pError(#[from] HolochainP2pError),
     #[error(transparent)]
@@ -56,6 +60,8 @@ pub enum CellError {
     SerializedBytesError(#[from] holochain_serialized_bytes::SerializedBytesError),
     #[error(transparent)]
     DhtOpConvertError(#[from] DhtOpConvertError),
+    #[error(transparent)]
+    KeystoreError(#[from] KeystoreError),
     #[error("Todo")]
     Todo,
 }
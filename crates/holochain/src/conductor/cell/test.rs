@@ -4,8 +4,17 @@ use crate::fixt::DnaFileFixturator;
 use crate::fixt::SignatureFixturator;
 use crate::test_utils::test_network;
 use ::fixt::prelude::*;
+use holo_hash::fixt::DhtOpHashFixturator;
 use holo_hash::HasHash;
+use holochain_keystore::KeystoreSenderExt;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::db::GetDb;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::env::WriteManager;
 use holochain_lmdb::test_utils::test_cell_env;
+use holochain_p2p::dht_arc::DhtArc;
+use holochain_state::dht_op_integration::AuthoredDhtOpsStore;
+use holochain_state::dht_op_integration::AuthoredDhtOpsValue;
 use holochain_types::prelude::*;
 use holochain_zome_types::header;
 use holochain_zome_types::HeaderHashed;
@@ -76,3 +85,167 @@ async fn test_cell_handle_publish() {
     stop_tx.send(()).unwrap();
     shutdown.await.unwrap();
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn test_cell_handle_validation_receipt() {
+    let cell_env = test_cell_env();
+    let env = cell_env.env();
+    let keystore = holochain_lmdb::test_utils::test_keystore();
+
+    let cell_id = fake_cell_id(1);
+    let dna = cell_id.dna_hash().clone();
+    let agent = cell_id.agent_pubkey().clone();
+
+    let test_network = test_network(Some(dna.clone()), Some(agent.clone())).await;
+    let holochain_p2p_cell = test_network.cell_network();
+
+    // An agent that isn't an authority for anything
+    let stranger = keystore
+        .clone()
+        .generate_sign_keypair_from_pure_entropy()
+        .await
+        .unwrap();
+
+    let mut mock_handler = crate::conductor::handle::MockConductorHandleT::new();
+    mock_handler
+        .expect_get_dna()
+        .returning(|_| Some(fixt!(DnaFile)));
+    {
+        let stranger = stranger.clone();
+        mock_handler
+            .expect_get_storage_arc()
+            .returning(move |_, agent| {
+                if *agent == stranger {
+                    Ok(None)
+                } else {
+                    Ok(Some(DhtArc::full(0)))
+                }
+            });
+    }
+
+    let mock_handler: crate::conductor::handle::ConductorHandle = Arc::new(mock_handler);
+
+    super::Cell::genesis(cell_id.clone(), mock_handler.clone(), env.clone(), None)
+        .await
+        .unwrap();
+
+    let (add_task_sender, shutdown) = spawn_task_manager();
+    let (stop_tx, _) = sync::broadcast::channel(1);
+
+    let (cell, _) = super::Cell::create(
+        cell_id,
+        mock_handler,
+        env.clone(),
+        holochain_p2p_cell,
+        add_task_sender,
+        stop_tx.clone(),
+    )
+    .await
+    .unwrap();
+
+    // Author an op
+    let header = header::Header::Dna(header::Dna {
+        author: agent.clone(),
+        timestamp: timestamp::now().into(),
+        hash: dna.clone(),
+    });
+    let op = DhtOp::StoreElement(fixt!(Signature), header.clone(), None);
+    let op_hash = DhtOpHashed::from_content_sync(op.clone()).into_hash();
+    {
+        let mut authored_dht_ops =
+            AuthoredDhtOpsStore::new(env.clone().into(), env.get_db(&*AUTHORED_DHT_OPS).unwrap());
+        authored_dht_ops
+            .put(
+                op_hash.clone(),
+                AuthoredDhtOpsValue::from_light(op.to_light()),
+            )
+            .unwrap();
+        env.guard()
+            .with_commit(|writer| authored_dht_ops.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    let receipt = |validation_result| {
+        let keystore = keystore.clone();
+        let dht_op_hash = op_hash.clone();
+        async move {
+            let validator = keystore
+                .clone()
+                .generate_sign_keypair_from_pure_entropy()
+                .await
+                .unwrap();
+            ValidationReceipt {
+                dht_op_hash,
+                validation_result,
+                validator,
+            }
+            .sign(&keystore)
+            .await
+            .unwrap()
+        }
+    };
+    let valid = receipt(ValidationResult::Valid).await;
+    let rejected = receipt(ValidationResult::Rejected).await;
+
+    // A duplicate receipt is only stored and counted once
+    for r in vec![valid.clone(), valid.clone(), rejected.clone()] {
+        cell.handle_validation_receipt(r.try_into().unwrap())
+            .await
+            .unwrap();
+    }
+
+    let mut receipts = cell.get_validation_receipts(&op_hash).unwrap();
+    receipts.sort();
+    let mut expected = vec![valid, rejected];
+    expected.sort();
+    assert_eq!(receipts, expected);
+
+    let authored_dht_ops =
+        AuthoredDhtOpsStore::new(env.clone().into(), env.get_db(&*AUTHORED_DHT_OPS).unwrap());
+    assert_eq!(
+        authored_dht_ops
+            .get(&op_hash)
+            .unwrap()
+            .unwrap()
+            .receipt_count,
+        1
+    );
+
+    // Receipts from validators that aren't authorities for the op are dropped
+    let from_stranger = ValidationReceipt {
+        dht_op_hash: op_hash.clone(),
+        validation_result: ValidationResult::Valid,
+        validator: stranger,
+    }
+    .sign(&keystore)
+    .await
+    .unwrap();
+    cell.handle_validation_receipt(from_stranger.try_into().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(cell.get_validation_receipts(&op_hash).unwrap().len(), 2);
+    assert_eq!(
+        authored_dht_ops
+            .get(&op_hash)
+            .unwrap()
+            .unwrap()
+            .receipt_count,
+        1
+    );
+
+    // Receipts for ops we didn't author are dropped
+    let other_op_hash = fixt!(DhtOpHash);
+    let mut other = receipt(ValidationResult::Valid).await;
+    other.receipt.dht_op_hash = other_op_hash.clone();
+    let other = other.receipt.sign(&keystore).await.unwrap();
+    cell.handle_validation_receipt(other.try_into().unwrap())
+        .await
+        .unwrap();
+    assert!(cell
+        .get_validation_receipts(&other_op_hash)
+        .unwrap()
+        .is_empty());
+
+    stop_tx.send(()).unwrap();
+    shutdown.await.unwrap();
+}
@@ -21,10 +21,11 @@ use super::entry_def_store::EntryDefBufferKey;
 use super::error::ConductorError;
 use super::error::CreateAppError;
 use super::handle::ConductorHandleImpl;
+use super::interface::auth::InterfaceTokens;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
-use super::interface::websocket::spawn_websocket_listener;
+use super::interface::websocket::spawn_interface_listener;
 use super::interface::websocket::SIGNAL_BUFFER_SIZE;
 use super::interface::SignalBroadcaster;
 use super::manager::keep_alive_task;
@@ -53,7 +54,12 @@ use fallible_iterator::FallibleIterator;
 use futures::future;
 use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
+use holo_hash::DhtOpHash;
 use holo_hash::DnaHash;
+use holochain_conductor_api::auth::TokenScope;
+use holochain_conductor_api::auth::ADMIN_TOKEN_FILE;
+use holochain_conductor_api::state_dump::CellStateDump;
+use holochain_conductor_api::state_dump::DumpStateOptions;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::test_keystore::spawn_test_keystore;
 use holochain_keystore::KeystoreSender;
@@ -68,11 +74,11 @@ use holochain_lmdb::env::ReadManager;
 use holochain_lmdb::exports::SingleStore;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
-use holochain_state::source_chain::SourceChainBuf;
 use holochain_state::wasm::WasmBuf;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::RwLock;
@@ -123,6 +129,10 @@ where
     /// The database for persisting [ConductorState]
     state_db: ConductorStateDb,
 
+    /// A copy of the [ConductorState] in the database, kept in step by
+    /// [Conductor::update_state], for checks that run on every zome call
+    state_cache: parking_lot::RwLock<ConductorState>,
+
     /// Set to true when `conductor.shutdown()` has been called, so that other
     /// tasks can check on the shutdown status
     shutting_down: bool,
@@ -244,26 +254,24 @@ where
     where
         DS: DnaStore + 'static,
     {
-        let admin_api = RealAdminInterfaceApi::new(handle);
+        if configs.iter().any(|config| config.require_token) {
+            self.write_admin_token(handle.interface_tokens())?;
+        }
         let stop_tx = self.managed_task_stop_broadcaster.clone();
 
         // Closure to process each admin config item
-        let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
-            let admin_api = admin_api.clone();
+        let spawn_from_config = |config: AdminInterfaceConfig| {
+            let AdminInterfaceConfig {
+                driver,
+                require_token,
+            } = config;
+            let admin_api = RealAdminInterfaceApi::new(handle.clone()).require_token(require_token);
             let stop_tx = stop_tx.clone();
             async move {
-                match driver {
-                    InterfaceDriver::Websocket { port } => {
-                        let listener = spawn_websocket_listener(port).await?;
-                        let port = listener.local_addr().port().unwrap_or(port);
-                        let handle: ManagedTaskHandle = spawn_admin_interface_task(
-                            listener,
-                            admin_api.clone(),
-                            stop_tx.subscribe(),
-                        )?;
-                        InterfaceResult::Ok((port, handle))
-                    }
-                }
+                let (listener, driver) = spawn_interface_listener(driver).await?;
+                let handle: ManagedTaskHandle =
+                    spawn_admin_interface_task(listener, admin_api, stop_tx.subscribe())?;
+                InterfaceResult::Ok((driver, handle))
             }
         };
 
@@ -288,8 +296,10 @@ where
             .await?;
 
             // Now that tasks are spawned, register them with the TaskManager
-            for (port, handle) in handles {
-                ports.push(port);
+            for (driver, handle) in handles {
+                if let InterfaceDriver::Websocket { port, .. } = driver {
+                    ports.push(port);
+                }
                 self.manage_task(ManagedTaskAdd::new(
                     handle,
                     Box::new(|result| {
@@ -308,19 +318,54 @@ where
         Ok(())
     }
 
+    /// Issue a new admin token which never expires, and write it to a file
+    /// only readable by the current user, so that local tools run by that user
+    /// can authenticate with admin interfaces which require a token.
+    fn write_admin_token(&self, tokens: &InterfaceTokens) -> ConductorResult<()> {
+        let issued = tokens.issue(TokenScope::Admin, None);
+        let path = std::path::PathBuf::from(self.root_env_dir.clone()).join(ADMIN_TOKEN_FILE);
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        // The mode only applies when the file is created,
+        // so make sure a token file from before is not readable by others.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::io::Write::write_all(&mut file, issued.token.0.as_bytes())?;
+        info!(path = ?path, "Wrote admin interface token");
+        Ok(())
+    }
+
+    /// Spawn an app interface task and persist its config in the state.
+    /// Returns the driver the interface was bound with,
+    /// i.e. with the port filled in if the OS chose it.
     pub(super) async fn add_app_interface_via_handle(
         &mut self,
-        port: u16,
+        driver: InterfaceDriver,
+        require_token: bool,
         handle: ConductorHandle,
-    ) -> ConductorResult<u16> {
-        let interface_id: AppInterfaceId = format!("interface-{}", port).into();
-        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+    ) -> ConductorResult<InterfaceDriver> {
+        let (listener, driver) = spawn_interface_listener(driver).await.map_err(Box::new)?;
+        let interface_id: AppInterfaceId = match &driver {
+            InterfaceDriver::Websocket { port, .. } => format!("interface-{}", port),
+            InterfaceDriver::UnixSocket { path } => format!("interface-{}", path.display()),
+        }
+        .into();
+        let app_api =
+            RealAppInterfaceApi::new(handle, interface_id.clone()).require_token(require_token);
         // This receiver is thrown away because we can produce infinite new
         // receivers from the Sender
         let (signal_tx, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
         let stop_rx = self.managed_task_stop_broadcaster.subscribe();
-        let (port, task) = spawn_app_interface_task(port, app_api, signal_tx.clone(), stop_rx)
-            .await
+        let task = spawn_app_interface_task(listener, app_api, signal_tx.clone(), stop_rx)
             .map_err(Box::new)?;
         // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
         self.manage_task(ManagedTaskAdd::dont_handle(task)).await?;
@@ -331,31 +376,25 @@ where
         }
 
         self.app_interfaces.insert(interface_id.clone(), interface);
-        let config = AppInterfaceConfig::websocket(port);
+        let config = AppInterfaceConfig::new(driver.clone()).require_token(require_token);
         self.update_state(|mut state| {
             state.app_interfaces.insert(interface_id, config);
             Ok(state)
         })
         .await?;
-        Ok(port)
+        Ok(driver)
     }
 
     /// Start all app interfaces currently in state.
     /// This should only be run at conductor initialization.
-    #[allow(irrefutable_let_patterns)]
     pub(super) async fn startup_app_interfaces_via_handle(
         &mut self,
         handle: ConductorHandle,
     ) -> ConductorResult<()> {
         for i in self.get_state().await?.app_interfaces.values() {
             tracing::debug!("Starting up app interface: {:?}", i);
-            let port = if let InterfaceDriver::Websocket { port } = i.driver {
-                port
-            } else {
-                unreachable!()
-            };
             let _ = self
-                .add_app_interface_via_handle(port, handle.clone())
+                .add_app_interface_via_handle(i.driver.clone(), i.require_token, handle.clone())
                 .await?;
         }
         Ok(())
@@ -558,6 +597,9 @@ where
             if is_active || is_inactive {
                 Err(ConductorError::AppAlreadyInstalled(app.installed_app_id))
             } else {
+                if !app.bridges.is_empty() {
+                    state.app_bridges.insert(app.installed_app_id, app.bridges);
+                }
                 Ok(state)
             }
         })
@@ -610,6 +652,112 @@ where
             .collect())
     }
 
+    /// The Cells of an installed app which are not part of any other app,
+    /// and so can be removed when the app is uninstalled.
+    pub(super) async fn cells_only_in_app(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Vec<CellId>> {
+        let state = self.get_state().await?;
+        let cells = state
+            .active_apps
+            .get(installed_app_id)
+            .or_else(|| state.inactive_apps.get(installed_app_id))
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        let in_other_apps: HashSet<&CellId> = state
+            .active_apps
+            .iter()
+            .chain(state.inactive_apps.iter())
+            .filter(|(id, _)| *id != installed_app_id)
+            .flat_map(|(_, cells)| cells)
+            .map(|c| c.as_id())
+            .collect();
+        Ok(cells
+            .iter()
+            .map(|c| c.as_id())
+            .filter(|cell_id| !in_other_apps.contains(cell_id))
+            .cloned()
+            .collect())
+    }
+
+    /// Remove an app from the database, whether it is active or inactive.
+    pub(super) async fn remove_app_from_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state
+                .active_apps
+                .remove(&installed_app_id)
+                .or_else(|| state.inactive_apps.remove(&installed_app_id))
+                .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+            state.app_bridges.remove(&installed_app_id);
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Get the Cell with this nick in an active app
+    pub(super) async fn get_active_app_cell(
+        &self,
+        installed_app_id: &InstalledAppId,
+        cell_nick: &CellNick,
+    ) -> ConductorResult<CellId> {
+        self.get_state()
+            .await?
+            .active_apps
+            .get(installed_app_id)
+            .ok_or_else(|| ConductorError::AppNotActive(installed_app_id.clone()))?
+            .iter()
+            .find(|c| c.as_nick() == cell_nick)
+            .map(|c| c.as_id().clone())
+            .ok_or_else(|| {
+                ConductorError::CellNickMissing(installed_app_id.clone(), cell_nick.clone())
+            })
+    }
+
+    /// Point the Cell with this nick in an active app at a new CellId
+    pub(super) async fn replace_app_cell_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        cell: InstalledCell,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            let installed_cell = state
+                .active_apps
+                .get_mut(&installed_app_id)
+                .ok_or_else(|| ConductorError::AppNotActive(installed_app_id.clone()))?
+                .iter_mut()
+                .find(|c| c.as_nick() == cell.as_nick())
+                .ok_or_else(|| {
+                    ConductorError::CellNickMissing(
+                        installed_app_id.clone(),
+                        cell.as_nick().clone(),
+                    )
+                })?;
+            *installed_cell = cell;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Delete the LMDB environments of these Cells.
+    /// The Cells must already be removed from the cell map.
+    pub(super) async fn remove_cell_envs(&self, cell_ids: Vec<CellId>) -> ConductorResult<()> {
+        let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
+        for cell_id in cell_ids {
+            let env = EnvironmentWrite::new(
+                &root_env_dir,
+                EnvironmentKind::Cell(cell_id),
+                self.keystore.clone(),
+            )?;
+            env.remove().await?;
+        }
+        Ok(())
+    }
+
     /// Add fully constructed cells to the cell map in the Conductor
     pub(super) fn add_cells(&mut self, cells: Vec<(Cell, InitialQueueTriggers)>) {
         for (cell, trigger) in cells {
@@ -671,10 +819,12 @@ where
         Ok((dnas, defs))
     }
 
-    /// Remove cells from the cell map in the Conductor
+    /// Stop the workflows of cells and remove them from the cell map in the Conductor
     pub(super) fn remove_cells(&mut self, cell_ids: Vec<CellId>) {
         for cell_id in cell_ids {
-            self.cells.remove(&cell_id);
+            if let Some(item) = self.cells.remove(&cell_id) {
+                item.cell.stop_workflows();
+            }
         }
     }
 
@@ -751,11 +901,22 @@ where
         Ok(active_apps.keys().cloned().collect())
     }
 
-    pub(super) async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
+    pub(super) async fn dump_cell_state(
+        &self,
+        cell_id: &CellId,
+        options: DumpStateOptions,
+    ) -> ConductorApiResult<CellStateDump> {
+        let cell = self.cell_by_id(cell_id)?;
+        Ok(cell.dump_state(options)?)
+    }
+
+    pub(super) fn get_validation_receipts(
+        &self,
+        cell_id: &CellId,
+        dht_op_hash: &DhtOpHash,
+    ) -> ConductorApiResult<Vec<SignedValidationReceipt>> {
         let cell = self.cell_by_id(cell_id)?;
-        let arc = cell.env();
-        let source_chain = SourceChainBuf::new(arc.clone().into())?;
-        Ok(source_chain.dump_as_json().await?)
+        Ok(cell.get_validation_receipts(dht_op_hash)?)
     }
 
     pub(super) fn p2p_env(&self) -> EnvironmentWrite {
@@ -811,6 +972,12 @@ where
         holochain_p2p: holochain_p2p::HolochainP2pRef,
     ) -> ConductorResult<Self> {
         let db: SingleStore = env.get_db(&db::CONDUCTOR_STATE)?;
+        let state_db = KvStore::new(db);
+        let state = {
+            let guard = env.guard();
+            let reader = guard.reader()?;
+            state_db.get(&reader, &UnitDbKey)?.unwrap_or_default()
+        };
         let (task_tx, task_manager_run_handle) = spawn_task_manager();
         let task_manager_run_handle = Some(task_manager_run_handle);
         let (stop_tx, _) = tokio::sync::broadcast::channel::<()>(1);
@@ -818,7 +985,8 @@ where
             env,
             wasm_env,
             p2p_env,
-            state_db: KvStore::new(db),
+            state_db,
+            state_cache: parking_lot::RwLock::new(state),
             cells: HashMap::new(),
             shutting_down: false,
             app_interfaces: HashMap::new(),
@@ -839,18 +1007,29 @@ where
         Ok(self.state_db.get(&reader, &UnitDbKey)?.unwrap_or_default())
     }
 
+    /// Can a zome call in one Cell call into another Cell.
+    /// Checked against the in-memory state so no database read is needed.
+    pub(super) fn is_bridge_allowed(&self, from_cell: &CellId, to_cell: &CellId) -> bool {
+        self.state_cache
+            .read()
+            .is_bridge_allowed(from_cell, to_cell)
+    }
+
     async fn update_state<F: Send>(&self, f: F) -> ConductorResult<ConductorState>
     where
         F: FnOnce(ConductorState) -> ConductorResult<ConductorState>,
     {
         self.check_running()?;
         let guard = self.env.guard();
+        // Hold the cache for the whole update so it changes in the same order as the database
+        let mut state_cache = self.state_cache.write();
         let new_state = guard.with_commit(|txn| {
             let state: ConductorState = self.state_db.get(txn, &UnitDbKey)?.unwrap_or_default();
             let new_state = f(state)?;
             self.state_db.put(txn, &UnitDbKey, &new_state)?;
             Result::<_, ConductorError>::Ok(new_state)
         })?;
+        *state_cache = new_state.clone();
         Ok(new_state)
     }
 
@@ -1020,6 +1199,8 @@ mod builder {
                 conductor: RwLock::new(conductor),
                 keystore,
                 holochain_p2p,
+                interface_tokens: Default::default(),
+                wasm_limits: conductor_config.wasm_limits.clone(),
             });
 
             handle.add_dnas().await?;
@@ -2,6 +2,7 @@ use super::Conductor;
 use super::ConductorState;
 use super::*;
 use crate::conductor::dna_store::MockDnaStore;
+use crate::conductor::handle::ConductorHandleT;
 use holochain_lmdb::test_utils::test_environments;
 use holochain_types::test_utils::fake_cell_id;
 use matches::assert_matches;
@@ -73,6 +74,7 @@ async fn app_ids_are_unique() {
     let app = InstalledApp {
         installed_app_id: "id".to_string(),
         cell_data: vec![installed_cell],
+        bridges: AppBridges::new(),
     };
 
     conductor.add_inactive_app_to_db(app.clone()).await.unwrap();
@@ -96,6 +98,93 @@ async fn app_ids_are_unique() {
     );
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn app_bridges_restrict_calls_between_cells() {
+    let environments = test_environments();
+    let dna_store = MockDnaStore::new();
+    let holochain_p2p = holochain_p2p::stub_network().await;
+    let mut conductor = Conductor::new(
+        environments.conductor(),
+        environments.wasm(),
+        environments.p2p(),
+        dna_store,
+        environments.keystore().clone(),
+        environments.tempdir().path().to_path_buf().into(),
+        holochain_p2p,
+    )
+    .await
+    .unwrap();
+
+    let cell_ids: Vec<_> = (1..=3).map(fake_cell_id).collect();
+    let outside_cell_id = fake_cell_id(4);
+    let cell_data = cell_ids
+        .iter()
+        .zip(["a", "b", "c"].iter())
+        .map(|(cell_id, nick)| InstalledCell::new(cell_id.clone(), nick.to_string()))
+        .collect();
+    let mut bridges = AppBridges::new();
+    bridges.insert("a".to_string(), vec!["b".to_string()]);
+    let app = InstalledApp {
+        installed_app_id: "id".to_string(),
+        cell_data,
+        bridges: bridges.clone(),
+    };
+
+    conductor.add_inactive_app_to_db(app).await.unwrap();
+    conductor
+        .activate_app_in_db("id".to_string())
+        .await
+        .unwrap();
+
+    let state = conductor.get_state().await.unwrap();
+    assert_eq!(
+        state.get_app_info(&"id".to_string()).unwrap().bridges,
+        bridges
+    );
+    // "a" may only call "b"
+    assert!(state.is_bridge_allowed(&cell_ids[0], &cell_ids[1]));
+    assert!(!state.is_bridge_allowed(&cell_ids[0], &cell_ids[2]));
+    assert!(!state.is_bridge_allowed(&cell_ids[0], &outside_cell_id));
+    // "b" didn't declare any bridges
+    assert!(state.is_bridge_allowed(&cell_ids[1], &cell_ids[2]));
+    assert!(state.is_bridge_allowed(&cell_ids[1], &outside_cell_id));
+    // Calls are checked against the in-memory copy of the state
+    assert!(conductor.is_bridge_allowed(&cell_ids[0], &cell_ids[1]));
+    assert!(!conductor.is_bridge_allowed(&cell_ids[0], &cell_ids[2]));
+
+    conductor
+        .remove_app_from_db("id".to_string())
+        .await
+        .unwrap();
+    let state = conductor.get_state().await.unwrap();
+    assert!(state.app_bridges.is_empty());
+    assert!(state.is_bridge_allowed(&cell_ids[0], &cell_ids[2]));
+    assert!(conductor.is_bridge_allowed(&cell_ids[0], &cell_ids[2]));
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn install_app_rejects_unknown_bridge_nicks() {
+    let envs = test_environments();
+    let conductor = ConductorBuilder::new().test(&envs).await.unwrap();
+    let cell_data = vec![(InstalledCell::new(fake_cell_id(1), "a".to_string()), None)];
+
+    let mut bridges = AppBridges::new();
+    bridges.insert("a".to_string(), vec!["typo".to_string()]);
+    let result = conductor
+        .clone()
+        .install_app("id".to_string(), cell_data.clone(), bridges)
+        .await;
+    assert_matches!(result, Err(ConductorError::CellNickMissing(_, nick)) if nick == "typo");
+
+    let mut bridges = AppBridges::new();
+    bridges.insert("typo".to_string(), vec!["a".to_string()]);
+    let result = conductor
+        .clone()
+        .install_app("id".to_string(), cell_data, bridges)
+        .await;
+    assert_matches!(result, Err(ConductorError::CellNickMissing(_, nick)) if nick == "typo");
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn can_set_fake_state() {
     let envs = test_environments();
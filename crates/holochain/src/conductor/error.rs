@@ -2,8 +2,10 @@ use super::interface::error::InterfaceError;
 use super::{entry_def_store::error::EntryDefStoreError, state::AppInterfaceId};
 use crate::conductor::cell::error::CellError;
 use crate::core::workflow::error::WorkflowError;
+use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::ConductorConfigError;
 use holochain_lmdb::error::DatabaseError;
+use holochain_types::app::CellNick;
 use holochain_types::app::InstalledAppId;
 use holochain_zome_types::cell::CellId;
 use thiserror::Error;
@@ -85,6 +87,15 @@ pub enum ConductorError {
     #[error("Tried to deactivate an app that was not active: {0}")]
     AppNotActive(InstalledAppId),
 
+    #[error("The app {0} has no cell with the nick {1}")]
+    CellNickMissing(InstalledAppId, CellNick),
+
+    #[error("Tried to migrate to a DNA that is not registered: {0}")]
+    DnaMissing(DnaHash),
+
+    #[error("Tried to migrate a cell to the DNA it is already using: {0}")]
+    MigrateToSameDna(DnaHash),
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
@@ -41,27 +41,43 @@
 //! types for testing. If we did not have a way of hiding this type genericity,
 //! code which interacted with the Conductor would also have to be highly generic.
 
+use super::api::error::ConductorApiError;
 use super::api::error::ConductorApiResult;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::InterfaceDriver;
+use super::config::WasmLimitsConfig;
 use super::dna_store::DnaStore;
 use super::entry_def_store::EntryDefBufferKey;
+use super::error::ConductorError;
 use super::error::ConductorResult;
 use super::error::CreateAppError;
+use super::interface::auth::InterfaceTokens;
 use super::interface::SignalBroadcaster;
 use super::manager::TaskManagerRunHandle;
 use super::p2p_store::get_agent_info_signed;
 use super::p2p_store::put_agent_info_signed;
 use super::p2p_store::query_agent_info_signed;
 use super::Cell;
+use super::CellError;
 use super::Conductor;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use crate::core::workflow::ZomeCallResult;
 use derive_more::From;
 use futures::future::FutureExt;
+use holo_hash::DhtOpHash;
+use holochain_conductor_api::auth::IssueTokenPayload;
+use holochain_conductor_api::auth::IssuedToken;
+use holochain_conductor_api::auth::TokenScope;
+use holochain_conductor_api::state_dump::CellStateDump;
+use holochain_conductor_api::state_dump::DumpStateOptions;
 use holochain_p2p::event::HolochainP2pEvent::*;
+use holochain_p2p::HolochainP2pSender;
 use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfo;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::dht_arc::DhtArc;
+use std::convert::TryFrom;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::*;
@@ -100,8 +116,14 @@ pub trait ConductorHandleT: Send + Sync {
     /// Should only be run once at Conductor initialization.
     async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()>;
 
-    /// Add an app interface
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16>;
+    /// Add an app interface.
+    /// Returns the driver the interface was bound with,
+    /// i.e. with the port filled in if the OS chose it.
+    async fn add_app_interface(
+        self: Arc<Self>,
+        driver: InterfaceDriver,
+        require_token: bool,
+    ) -> ConductorResult<InterfaceDriver>;
 
     /// Install a [Dna] in this Conductor
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()>;
@@ -115,6 +137,14 @@ pub trait ConductorHandleT: Send + Sync {
     /// Get a [EntryDef] from the [EntryDefBuffer]
     async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
 
+    /// Get the storage arc an agent is advertising for a [Dna],
+    /// if we hold its agent info
+    async fn get_storage_arc(
+        &self,
+        dna_hash: &DnaHash,
+        agent: &AgentPubKey,
+    ) -> ConductorResult<Option<DhtArc>>;
+
     /// Add the [DnaFile]s from the wasm and dna_def databases into memory
     async fn add_dnas(&self) -> ConductorResult<()>;
 
@@ -135,6 +165,15 @@ pub trait ConductorHandleT: Send + Sync {
         workspace_lock: CallZomeWorkspaceLock,
     ) -> ConductorApiResult<ZomeCallResult>;
 
+    /// Invoke a zome function on a Cell from a zome call in another Cell.
+    /// The call runs in the other Cell's own workspace, if the apps
+    /// the calling Cell belongs to allow it to call that Cell.
+    async fn bridge_call_zome(
+        &self,
+        from_cell: &CellId,
+        invocation: ZomeCall,
+    ) -> ConductorApiResult<ZomeCallResult>;
+
     /// Cue the autonomic system to perform some action early (experimental)
     async fn autonomic_cue(&self, cue: AutonomicCue, cell_id: &CellId) -> ConductorApiResult<()>;
 
@@ -157,6 +196,19 @@ pub trait ConductorHandleT: Send + Sync {
     /// Request access to this conductor's networking handle
     fn holochain_p2p(&self) -> &holochain_p2p::HolochainP2pRef;
 
+    /// Request access to the tokens issued for this conductor's interfaces
+    fn interface_tokens(&self) -> &InterfaceTokens;
+
+    /// The limits on the resources zome calls and callbacks can use
+    fn wasm_limits(&self) -> &WasmLimitsConfig;
+
+    /// Issue a token for authenticating connections to interfaces.
+    /// All the apps in an app scope must be installed.
+    async fn issue_interface_token(
+        &self,
+        payload: IssueTokenPayload,
+    ) -> ConductorResult<IssuedToken>;
+
     /// Install Cells into ConductorState based on installation info, and run
     /// genesis on all new source chains
     #[allow(clippy::ptr_arg)]
@@ -164,6 +216,7 @@ pub trait ConductorHandleT: Send + Sync {
         self: Arc<Self>,
         installed_app_id: InstalledAppId,
         cell_data_with_proofs: Vec<(InstalledCell, Option<MembraneProof>)>,
+        bridges: AppBridges,
     ) -> ConductorResult<()>;
 
     /// Setup the cells from the database
@@ -178,6 +231,23 @@ pub trait ConductorHandleT: Send + Sync {
     #[allow(clippy::ptr_arg)]
     async fn deactivate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()>;
 
+    /// Uninstall an app, deleting the data of any of its Cells
+    /// which are not part of another app
+    #[allow(clippy::ptr_arg)]
+    async fn uninstall_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()>;
+
+    /// Migrate the agent of a Cell in an active app to a new version of its DNA.
+    /// A new chain is created and opened, the app uses the new Cell
+    /// under the same CellNick, and then the old chain is closed.
+    /// Returns the new CellId.
+    async fn migrate_agent(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cell_nick: CellNick,
+        new_dna_hash: DnaHash,
+        membrane_proof: Option<MembraneProof>,
+    ) -> ConductorResult<CellId>;
+
     /// List Cell Ids
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>>;
 
@@ -186,7 +256,19 @@ pub trait ConductorHandleT: Send + Sync {
 
     /// Dump the cells state
     #[allow(clippy::ptr_arg)]
-    async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String>;
+    async fn dump_cell_state(
+        &self,
+        cell_id: &CellId,
+        options: DumpStateOptions,
+    ) -> ConductorApiResult<CellStateDump>;
+
+    /// List the validation receipts a cell has received for an op it authored
+    #[allow(clippy::ptr_arg)]
+    async fn get_validation_receipts(
+        &self,
+        cell_id: &CellId,
+        dht_op_hash: &DhtOpHash,
+    ) -> ConductorApiResult<Vec<SignedValidationReceipt>>;
 
     /// Access the broadcast Sender which will send a Signal across every
     /// attached app interface
@@ -245,6 +327,8 @@ pub struct ConductorHandleImpl<DS: DnaStore + 'static> {
     pub(crate) conductor: RwLock<Conductor<DS>>,
     pub(crate) keystore: KeystoreSender,
     pub(crate) holochain_p2p: holochain_p2p::HolochainP2pRef,
+    pub(crate) interface_tokens: InterfaceTokens,
+    pub(crate) wasm_limits: WasmLimitsConfig,
 }
 
 #[async_trait::async_trait]
@@ -271,9 +355,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .await
     }
 
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16> {
+    async fn add_app_interface(
+        self: Arc<Self>,
+        driver: InterfaceDriver,
+        require_token: bool,
+    ) -> ConductorResult<InterfaceDriver> {
         let mut lock = self.conductor.write().await;
-        lock.add_app_interface_via_handle(port, self.clone()).await
+        lock.add_app_interface_via_handle(driver, require_token, self.clone())
+            .await
     }
 
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()> {
@@ -321,6 +410,21 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.dna_store().get_entry_def(key)
     }
 
+    async fn get_storage_arc(
+        &self,
+        dna_hash: &DnaHash,
+        agent: &AgentPubKey,
+    ) -> ConductorResult<Option<DhtArc>> {
+        let env = { self.conductor.read().await.p2p_env() };
+        let space = holochain_p2p::space_holo_to_kit(dna_hash.clone());
+        let agent = holochain_p2p::agent_holo_to_kit(agent.clone());
+        let info = get_agent_info_signed(env, Arc::new(space), Arc::new(agent))?;
+        Ok(match info {
+            Some(info) => Some(AgentInfo::try_from(&info)?.storage_arc()),
+            None => None,
+        })
+    }
+
     #[instrument(skip(self))]
     /// Warning: returning an error from this function kills the network for the conductor.
     async fn dispatch_holochain_p2p_event(
@@ -391,6 +495,25 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(cell.call_zome(call, Some(workspace_lock)).await?)
     }
 
+    async fn bridge_call_zome(
+        &self,
+        from_cell: &CellId,
+        call: ZomeCall,
+    ) -> ConductorApiResult<ZomeCallResult> {
+        let allowed = self
+            .conductor
+            .read()
+            .await
+            .is_bridge_allowed(from_cell, &call.cell_id);
+        if !allowed {
+            return Err(ConductorApiError::BridgeNotAllowed {
+                from_cell: from_cell.clone(),
+                to_cell: call.cell_id,
+            });
+        }
+        self.call_zome(call).await
+    }
+
     async fn autonomic_cue(&self, cue: AutonomicCue, cell_id: &CellId) -> ConductorApiResult<()> {
         let cell = self.cell_by_id(cell_id).await?;
         let _ = cell.handle_autonomic_process(cue.into()).await;
@@ -420,11 +543,54 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         &self.holochain_p2p
     }
 
+    fn interface_tokens(&self) -> &InterfaceTokens {
+        &self.interface_tokens
+    }
+
+    fn wasm_limits(&self) -> &WasmLimitsConfig {
+        &self.wasm_limits
+    }
+
+    async fn issue_interface_token(
+        &self,
+        payload: IssueTokenPayload,
+    ) -> ConductorResult<IssuedToken> {
+        let IssueTokenPayload {
+            scope,
+            expires_in_seconds,
+        } = payload;
+        if let TokenScope::App(installed_app_ids) = &scope {
+            let state = self.conductor.read().await.get_state().await?;
+            for installed_app_id in installed_app_ids {
+                if state.get_app_info(installed_app_id).is_none() {
+                    return Err(ConductorError::AppNotInstalled(installed_app_id.clone()));
+                }
+            }
+        }
+        Ok(self.interface_tokens.issue(
+            scope,
+            expires_in_seconds.map(std::time::Duration::from_secs),
+        ))
+    }
+
     async fn install_app(
         self: Arc<Self>,
         installed_app_id: InstalledAppId,
         cell_data: Vec<(InstalledCell, Option<MembraneProof>)>,
+        bridges: AppBridges,
     ) -> ConductorResult<()> {
+        // A bridge naming a Cell the app doesn't have would deny every call
+        for nick in bridges
+            .iter()
+            .flat_map(|(from, to)| std::iter::once(from).chain(to))
+        {
+            if !cell_data.iter().any(|(cell, _)| cell.as_nick() == nick) {
+                return Err(ConductorError::CellNickMissing(
+                    installed_app_id,
+                    nick.clone(),
+                ));
+            }
+        }
         self.conductor
             .read()
             .await
@@ -441,6 +607,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         let app = InstalledApp {
             installed_app_id,
             cell_data,
+            bridges,
         };
 
         // Update the db
@@ -501,6 +668,113 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(())
     }
 
+    async fn uninstall_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()> {
+        // Deactivate the app and stop its cells before anything is deleted,
+        // so nothing is left running against a removed environment.
+        let cell_ids = {
+            let mut conductor = self.conductor.write().await;
+            let cell_ids = conductor.cells_only_in_app(&installed_app_id).await?;
+            match conductor
+                .deactivate_app_in_db(installed_app_id.clone())
+                .await
+            {
+                Ok(_) | Err(ConductorError::AppNotActive(_)) => (),
+                Err(e) => return Err(e),
+            }
+            conductor.remove_cells(cell_ids.clone());
+            cell_ids
+        };
+        for cell_id in &cell_ids {
+            self.holochain_p2p
+                .leave(cell_id.dna_hash().clone(), cell_id.agent_pubkey().clone())
+                .await
+                .map_err(CellError::from)?;
+        }
+        let mut conductor = self.conductor.write().await;
+        conductor.remove_cell_envs(cell_ids).await?;
+        conductor.remove_app_from_db(installed_app_id).await
+    }
+
+    async fn migrate_agent(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cell_nick: CellNick,
+        new_dna_hash: DnaHash,
+        membrane_proof: Option<MembraneProof>,
+    ) -> ConductorResult<CellId> {
+        let old_cell_id = self
+            .conductor
+            .read()
+            .await
+            .get_active_app_cell(&installed_app_id, &cell_nick)
+            .await?;
+        if old_cell_id.dna_hash() == &new_dna_hash {
+            return Err(ConductorError::MigrateToSameDna(new_dna_hash));
+        }
+        if self.get_dna(&new_dna_hash).await.is_none() {
+            return Err(ConductorError::DnaMissing(new_dna_hash));
+        }
+        let new_cell_id = CellId::new(new_dna_hash.clone(), old_cell_id.agent_pubkey().clone());
+
+        // Check the old chain can be closed before anything is changed
+        let old_cell = self.conductor.read().await.cell_by_id(&old_cell_id)?;
+        old_cell.check_migrate_agent(MigrateAgent::Close).await?;
+
+        // Create the new chain and point the app at it
+        let new_cell_existed = self.conductor.read().await.cell_by_id(&new_cell_id).is_ok();
+        self.conductor
+            .read()
+            .await
+            .genesis_cells(vec![(new_cell_id.clone(), membrane_proof)], self.clone())
+            .await?;
+        self.conductor
+            .write()
+            .await
+            .replace_app_cell_in_db(
+                installed_app_id.clone(),
+                InstalledCell::new(new_cell_id.clone(), cell_nick.clone()),
+            )
+            .await?;
+
+        // Open the new chain
+        let opened = async {
+            if let Some(error) = self.clone().setup_cells().await?.into_iter().next() {
+                return Err(error.into());
+            }
+            let new_cell = self.conductor.read().await.cell_by_id(&new_cell_id)?;
+            new_cell
+                .migrate_agent(MigrateAgent::Open, old_cell_id.dna_hash().clone())
+                .await?;
+            Ok::<(), ConductorError>(())
+        }
+        .await;
+
+        // If it can't be opened, point the app back at the old chain
+        // and get rid of the new one so the migration can be retried
+        if let Err(error) = opened {
+            let mut conductor = self.conductor.write().await;
+            conductor
+                .replace_app_cell_in_db(
+                    installed_app_id,
+                    InstalledCell::new(old_cell_id, cell_nick),
+                )
+                .await?;
+            if !new_cell_existed {
+                conductor.remove_cells(vec![new_cell_id.clone()]);
+                conductor.remove_cell_envs(vec![new_cell_id]).await?;
+            }
+            return Err(error);
+        }
+
+        // Close the old chain last so it's only closed once the new one is open.
+        // The old Cell keeps running until the conductor restarts
+        // so the CloseChain header can be published.
+        old_cell
+            .migrate_agent(MigrateAgent::Close, new_dna_hash)
+            .await?;
+        Ok(new_cell_id)
+    }
+
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>> {
         self.conductor.read().await.list_cell_ids().await
     }
@@ -509,8 +783,27 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.list_active_apps().await
     }
 
-    async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
-        self.conductor.read().await.dump_cell_state(cell_id).await
+    async fn dump_cell_state(
+        &self,
+        cell_id: &CellId,
+        options: DumpStateOptions,
+    ) -> ConductorApiResult<CellStateDump> {
+        self.conductor
+            .read()
+            .await
+            .dump_cell_state(cell_id, options)
+            .await
+    }
+
+    async fn get_validation_receipts(
+        &self,
+        cell_id: &CellId,
+        dht_op_hash: &DhtOpHash,
+    ) -> ConductorApiResult<Vec<SignedValidationReceipt>> {
+        self.conductor
+            .read()
+            .await
+            .get_validation_receipts(cell_id, dht_op_hash)
     }
 
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
@@ -6,12 +6,20 @@
 //! implementation can be found in the `websocket` module here.
 
 use crate::conductor::api::*;
+use auth::ConnectionAuth;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_conductor_api::signal_subscription::SignalSubscription;
+use holochain_types::prelude::*;
 use holochain_types::signal::Signal;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryInto;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
+pub mod auth;
 #[allow(missing_docs)]
 pub mod error;
 pub mod websocket;
@@ -98,4 +106,109 @@ impl SignalBroadcaster {
     }
 }
 
+/// The signal subscriptions made by a single client connection to an App interface.
+///
+/// Clones share the same subscriptions, so the API handling the connection's
+/// requests can update them while the task sending signals to the client applies them.
+/// A new connection is subscribed to all signals, but only receives signals
+/// which its [ConnectionAuth] allows.
+#[derive(Clone, Default)]
+pub struct ConnectionSignalFilter {
+    subscriptions: Arc<parking_lot::RwLock<HashMap<InstalledAppId, AppSignalFilter>>>,
+    auth: ConnectionAuth,
+}
+
+/// The filters for one App, along with the Cells they apply to
+struct AppSignalFilter {
+    cells: HashSet<CellId>,
+    filters: SignalFilterSet,
+}
+
+impl ConnectionSignalFilter {
+    /// Create the filter for a connection with this authentication state
+    pub fn new(auth: ConnectionAuth) -> Self {
+        Self {
+            subscriptions: Default::default(),
+            auth,
+        }
+    }
+
+    /// Set the subscription for an App, replacing any previous one.
+    /// The subscription only applies to signals from the App's Cells.
+    pub fn subscribe(&self, subscription: SignalSubscription, cells: HashSet<CellId>) {
+        let (installed_app_id, filters) = subscription.into_inner();
+        self.subscriptions
+            .write()
+            .insert(installed_app_id, AppSignalFilter { cells, filters });
+    }
+
+    /// Should this signal be sent over the connection?
+    ///
+    /// Nothing is sent to a connection which hasn't authenticated when it is required to.
+    /// System signals are otherwise always sent. An App signal is sent if the connection
+    /// may access the emitting Cell and every App that includes the Cell allows it.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        match signal {
+            Signal::App(cell_id, zome_name, app_signal) => {
+                self.auth.allows_cell(cell_id)
+                    && self
+                        .subscriptions
+                        .read()
+                        .values()
+                        .filter(|app| app.cells.contains(cell_id))
+                        .all(|app| app.filters.allows(cell_id, zome_name, app_signal))
+            }
+            Signal::System(_) => self.auth.is_authenticated(),
+        }
+    }
+}
+
 pub use holochain_conductor_api::config::InterfaceDriver;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use holochain_conductor_api::signal_subscription::SignalFilter;
+    use holochain_types::test_utils::fake_cell_id;
+
+    #[test]
+    fn connection_signal_filter() {
+        let filter = ConnectionSignalFilter::default();
+        let zome_name: ZomeName = "zome".into();
+        let app_cell = fake_cell_id(1);
+        let other_cell = fake_cell_id(2);
+        let signal = |cell_id: &CellId| {
+            Signal::App(
+                cell_id.clone(),
+                zome_name.clone(),
+                AppSignal::new(ExternIO::encode(()).unwrap()),
+            )
+        };
+
+        // Everything is allowed before subscribing
+        assert!(filter.allows(&signal(&app_cell)));
+        assert!(filter.allows(&test_signal("system")));
+
+        let mut filters = HashMap::new();
+        filters.insert(
+            app_cell.clone(),
+            SignalFilter::empty().zomes(vec!["other zome".into()]),
+        );
+        filter.subscribe(
+            SignalSubscription::new("app".into(), SignalFilterSet::Include(filters)),
+            vec![app_cell.clone()].into_iter().collect(),
+        );
+
+        assert!(!filter.allows(&signal(&app_cell)));
+        // Signals from cells outside the app are not affected
+        assert!(filter.allows(&signal(&other_cell)));
+        assert!(filter.allows(&test_signal("system")));
+
+        // Subscribing again replaces the previous subscription
+        filter.subscribe(
+            SignalSubscription::new("app".into(), SignalFilterSet::allow_all()),
+            vec![app_cell.clone()].into_iter().collect(),
+        );
+        assert!(filter.allows(&signal(&app_cell)));
+    }
+}
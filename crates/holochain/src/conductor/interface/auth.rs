@@ -0,0 +1,324 @@
+//! Token authentication for connections to Admin and App interfaces.
+//!
+//! The conductor keeps the tokens it has issued in an [InterfaceTokens] store.
+//! Each connection to an interface has its own [ConnectionAuth], which records
+//! the token the connection authenticated with. The token is looked up again
+//! for every request and signal, so revoking or expiring a token immediately
+//! cuts off every connection that used it.
+
+use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::api::error::ConductorApiResult;
+use holochain_conductor_api::auth::InterfaceToken;
+use holochain_conductor_api::auth::IssuedToken;
+use holochain_conductor_api::auth::TokenScope;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// The number of random bytes in a token
+const TOKEN_BYTES: usize = 32;
+
+/// A token issued by the conductor
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenGrant {
+    /// What the token gives access to
+    pub scope: TokenScope,
+    /// When the token stops being valid, if ever
+    pub expires_at: Option<Timestamp>,
+}
+
+impl TokenGrant {
+    /// Has the token expired at this time?
+    pub fn is_expired(&self, now: &Timestamp) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= *now)
+            .unwrap_or(false)
+    }
+}
+
+/// All the tokens issued by a conductor.
+///
+/// Tokens are only held in memory, so they don't survive a restart.
+/// Clones share the same tokens.
+#[derive(Clone, Default)]
+pub struct InterfaceTokens(Arc<parking_lot::RwLock<HashMap<InterfaceToken, TokenGrant>>>);
+
+impl InterfaceTokens {
+    /// Issue a new random token
+    pub fn issue(&self, scope: TokenScope, expires_in: Option<std::time::Duration>) -> IssuedToken {
+        let bytes: [u8; TOKEN_BYTES] = rand::random();
+        let token = InterfaceToken(base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD));
+        // A duration too large to represent never expires
+        let expires_at =
+            expires_in.and_then(|expires_in| (holochain_types::timestamp::now() + expires_in).ok());
+        self.0
+            .write()
+            .insert(token.clone(), TokenGrant { scope, expires_at });
+        IssuedToken { token, expires_at }
+    }
+
+    /// Revoke a token. Revoking an unknown token does nothing.
+    pub fn revoke(&self, token: &InterfaceToken) {
+        self.0.write().remove(token);
+    }
+
+    /// Get the grant for a token if it is still valid.
+    /// Expired tokens are removed.
+    pub fn get(&self, token: &InterfaceToken) -> Option<TokenGrant> {
+        let now = holochain_types::timestamp::now();
+        let grant = self.0.read().get(token).cloned()?;
+        if grant.is_expired(&now) {
+            self.0.write().remove(token);
+            None
+        } else {
+            Some(grant)
+        }
+    }
+}
+
+/// The authentication state of a single connection to an interface.
+///
+/// Clones share the same state, so the API handling the connection's requests
+/// can authenticate it while the task sending signals to the client checks it.
+/// If a token is not required every request is allowed.
+#[derive(Clone, Default)]
+pub struct ConnectionAuth {
+    tokens: InterfaceTokens,
+    required: bool,
+    authenticated: Arc<parking_lot::RwLock<Option<Authenticated>>>,
+}
+
+/// A token a connection has authenticated with,
+/// along with the Cells an app token gives access to
+struct Authenticated {
+    token: InterfaceToken,
+    cells: HashSet<CellId>,
+}
+
+impl ConnectionAuth {
+    /// Create the authentication state for an interface
+    pub fn new(tokens: InterfaceTokens, required: bool) -> Self {
+        Self {
+            tokens,
+            required,
+            authenticated: Default::default(),
+        }
+    }
+
+    /// Fresh, unauthenticated state for a new connection to the same interface
+    pub fn for_connection(&self) -> Self {
+        Self::new(self.tokens.clone(), self.required)
+    }
+
+    /// Authenticate the connection with an admin token
+    pub fn authenticate_admin(&self, token: InterfaceToken) -> ConductorApiResult<()> {
+        match self.tokens.get(&token) {
+            Some(TokenGrant {
+                scope: TokenScope::Admin,
+                ..
+            }) => {
+                self.set_authenticated(token, HashSet::new());
+                Ok(())
+            }
+            _ => Err(invalid_token()),
+        }
+    }
+
+    /// Check an app token and get the apps it gives access to.
+    /// The connection is not authenticated until
+    /// [ConnectionAuth::authenticate_app] is called.
+    pub fn app_scope(&self, token: &InterfaceToken) -> ConductorApiResult<Vec<InstalledAppId>> {
+        match self.tokens.get(token) {
+            Some(TokenGrant {
+                scope: TokenScope::App(installed_app_ids),
+                ..
+            }) => Ok(installed_app_ids),
+            _ => Err(invalid_token()),
+        }
+    }
+
+    /// Authenticate the connection with an app token,
+    /// allowing access to the Cells of the token's apps
+    pub fn authenticate_app(&self, token: InterfaceToken, cells: HashSet<CellId>) {
+        self.set_authenticated(token, cells);
+    }
+
+    /// Check the connection may make admin requests
+    pub fn check_admin(&self) -> ConductorApiResult<()> {
+        match self.grant()? {
+            None
+            | Some(TokenGrant {
+                scope: TokenScope::Admin,
+                ..
+            }) => Ok(()),
+            Some(_) => Err(ConductorApiError::Unauthorized(
+                "An app token can't be used on an admin interface".to_string(),
+            )),
+        }
+    }
+
+    /// Check the connection may access this app
+    #[allow(clippy::ptr_arg)]
+    pub fn check_app(&self, installed_app_id: &InstalledAppId) -> ConductorApiResult<()> {
+        match self.grant()? {
+            None => Ok(()),
+            Some(TokenGrant {
+                scope: TokenScope::App(installed_app_ids),
+                ..
+            }) if installed_app_ids.contains(installed_app_id) => Ok(()),
+            Some(_) => Err(ConductorApiError::Unauthorized(format!(
+                "The token used to authenticate this connection does not give access to the app {}",
+                installed_app_id
+            ))),
+        }
+    }
+
+    /// Check the connection may access this Cell
+    pub fn check_cell(&self, cell_id: &CellId) -> ConductorApiResult<()> {
+        if self.allows_cell(cell_id) {
+            Ok(())
+        } else {
+            Err(ConductorApiError::Unauthorized(format!(
+                "The token used to authenticate this connection does not give access to the cell {}",
+                cell_id
+            )))
+        }
+    }
+
+    /// Check the connection has authenticated if it is required to
+    pub fn check_authenticated(&self) -> ConductorApiResult<()> {
+        self.grant().map(|_| ())
+    }
+
+    /// Is the connection allowed to receive signals?
+    pub fn is_authenticated(&self) -> bool {
+        self.check_authenticated().is_ok()
+    }
+
+    /// Is the connection allowed to access this Cell?
+    pub fn allows_cell(&self, cell_id: &CellId) -> bool {
+        match self.grant() {
+            Ok(None) => true,
+            Ok(Some(TokenGrant {
+                scope: TokenScope::App(_),
+                ..
+            })) => self
+                .authenticated
+                .read()
+                .as_ref()
+                .map(|authenticated| authenticated.cells.contains(cell_id))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn set_authenticated(&self, token: InterfaceToken, cells: HashSet<CellId>) {
+        *self.authenticated.write() = Some(Authenticated { token, cells });
+    }
+
+    /// The grant this connection authenticated with.
+    /// `None` means no token is required and none was given.
+    fn grant(&self) -> ConductorApiResult<Option<TokenGrant>> {
+        let token = self
+            .authenticated
+            .read()
+            .as_ref()
+            .map(|authenticated| authenticated.token.clone());
+        match token {
+            Some(token) => self.tokens.get(&token).map(Some).ok_or_else(|| {
+                ConductorApiError::Unauthorized(
+                    "The token used to authenticate this connection has expired or been revoked"
+                        .to_string(),
+                )
+            }),
+            None if self.required => Err(ConductorApiError::Unauthorized(
+                "This interface requires the connection to authenticate with a token".to_string(),
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+fn invalid_token() -> ConductorApiError {
+    ConductorApiError::Unauthorized("Invalid, expired or revoked token".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use holochain_types::test_utils::fake_cell_id;
+    use std::time::Duration;
+
+    #[test]
+    fn admin_token() {
+        let tokens = InterfaceTokens::default();
+        let auth = ConnectionAuth::new(tokens.clone(), true);
+        assert!(auth.check_admin().is_err());
+
+        let app_token = tokens.issue(TokenScope::App(vec!["app".into()]), None);
+        assert!(auth.authenticate_admin(app_token.token).is_err());
+        assert!(auth.check_admin().is_err());
+
+        let admin_token = tokens.issue(TokenScope::Admin, None);
+        auth.authenticate_admin(admin_token.token.clone()).unwrap();
+        auth.check_admin().unwrap();
+
+        // Other connections are not affected
+        assert!(auth.for_connection().check_admin().is_err());
+
+        tokens.revoke(&admin_token.token);
+        assert!(auth.check_admin().is_err());
+    }
+
+    #[test]
+    fn tokens_are_not_logged() {
+        let tokens = InterfaceTokens::default();
+        let issued = tokens.issue(TokenScope::Admin, None);
+        assert!(!format!("{:?}", issued).contains(&issued.token.0));
+        assert!(!format!("{}", issued.token).contains(&issued.token.0));
+    }
+
+    #[test]
+    fn app_token_scope() {
+        let tokens = InterfaceTokens::default();
+        let auth = ConnectionAuth::new(tokens.clone(), true);
+        let app_cell = fake_cell_id(1);
+        let other_cell = fake_cell_id(2);
+        assert!(!auth.allows_cell(&app_cell));
+        assert!(!auth.is_authenticated());
+
+        let issued = tokens.issue(TokenScope::App(vec!["app".into()]), None);
+        assert_eq!(
+            auth.app_scope(&issued.token).unwrap(),
+            vec!["app".to_string()]
+        );
+        auth.authenticate_app(issued.token, vec![app_cell.clone()].into_iter().collect());
+
+        assert!(auth.is_authenticated());
+        assert!(auth.allows_cell(&app_cell));
+        assert!(!auth.allows_cell(&other_cell));
+        auth.check_app(&"app".into()).unwrap();
+        assert!(auth.check_app(&"other app".into()).is_err());
+        assert!(auth.check_admin().is_err());
+    }
+
+    #[test]
+    fn expired_tokens_are_rejected() {
+        let tokens = InterfaceTokens::default();
+        let auth = ConnectionAuth::new(tokens.clone(), true);
+        let issued = tokens.issue(TokenScope::Admin, Some(Duration::from_secs(0)));
+        assert!(issued.expires_at.is_some());
+        assert!(auth.authenticate_admin(issued.token.clone()).is_err());
+        assert_eq!(tokens.get(&issued.token), None);
+    }
+
+    #[test]
+    fn not_required() {
+        let auth = ConnectionAuth::default();
+        auth.check_admin().unwrap();
+        auth.check_app(&"app".into()).unwrap();
+        assert!(auth.allows_cell(&fake_cell_id(1)));
+        assert!(auth.is_authenticated());
+    }
+}
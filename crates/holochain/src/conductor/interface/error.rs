@@ -29,6 +29,8 @@ pub enum InterfaceError {
     IoTodo(#[from] std::io::Error),
     #[error("Failed to find free port")]
     PortError,
+    #[error("Only one of the TLS certificate and key files exists, missing: {0:?}")]
+    TlsFileMissing(std::path::PathBuf),
 }
 
 impl From<String> for InterfaceError {
@@ -1,5 +1,6 @@
 //! Module for establishing Websocket-based Interfaces,
-//! i.e. those configured with `InterfaceDriver::Websocket`
+//! i.e. those configured with `InterfaceDriver::Websocket`,
+//! or `InterfaceDriver::UnixSocket` which speaks websockets over a unix domain socket
 
 use super::error::InterfaceError;
 use super::error::InterfaceResult;
@@ -7,15 +8,25 @@ use crate::conductor::conductor::StopReceiver;
 use crate::conductor::interface::*;
 use crate::conductor::manager::ManagedTaskHandle;
 use crate::conductor::manager::ManagedTaskResult;
+use holochain_conductor_api::config::InterfaceTlsConfig;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::signal::Signal;
 use holochain_websocket::websocket_bind;
+#[cfg(unix)]
+use holochain_websocket::websocket_bind_unix;
+use holochain_websocket::TlsIdentity;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketMessage;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
 use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -32,21 +43,120 @@ use url2::url2;
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
 const MAX_CONNECTIONS: usize = 400;
 
-/// Create a WebsocketListener to be used in interfaces
-pub async fn spawn_websocket_listener(port: u16) -> InterfaceResult<WebsocketListener> {
+/// Create a WebsocketListener to be used in interfaces.
+/// It listens on `127.0.0.1` unless another bind address is given.
+/// If a TLS config is given the listener only accepts "wss" connections.
+pub async fn spawn_websocket_listener(
+    port: u16,
+    bind_address: Option<IpAddr>,
+    tls: Option<&InterfaceTlsConfig>,
+) -> InterfaceResult<WebsocketListener> {
     trace!("Initializing Admin interface");
-    let listener = websocket_bind(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
+    let bind_address = bind_address.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let addr = SocketAddr::new(bind_address, port);
+    let listener = match tls {
+        Some(tls) => {
+            let config = WebsocketConfig::default()
+                .scheme("wss")
+                .tls_identity(load_or_generate_tls_identity(tls, bind_address)?);
+            websocket_bind(url2!("wss://{}", addr), Arc::new(config)).await?
+        }
+        None => {
+            websocket_bind(url2!("ws://{}", addr), Arc::new(WebsocketConfig::default())).await?
+        }
+    };
     trace!("LISTENING AT: {}", listener.local_addr());
     Ok(listener)
 }
 
+/// Read the certificate and key of a TLS interface,
+/// or generate a self-signed certificate and save it if neither file exists yet.
+/// The generated certificate is for the local host, the bind address
+/// and any other hosts in the config.
+fn load_or_generate_tls_identity(
+    tls: &InterfaceTlsConfig,
+    bind_address: IpAddr,
+) -> InterfaceResult<TlsIdentity> {
+    let InterfaceTlsConfig {
+        cert_path,
+        key_path,
+        hosts,
+    } = tls;
+    match (cert_path.exists(), key_path.exists()) {
+        (true, true) => Ok(TlsIdentity::from_pem_files(cert_path, key_path)?),
+        (false, false) => {
+            let mut names = vec![
+                "localhost".to_string(),
+                Ipv4Addr::LOCALHOST.to_string(),
+                Ipv6Addr::LOCALHOST.to_string(),
+            ];
+            if !bind_address.is_unspecified() {
+                names.push(bind_address.to_string());
+            }
+            for host in hosts {
+                if !names.contains(host) {
+                    names.push(host.clone());
+                }
+            }
+            let identity = TlsIdentity::self_signed(names)?;
+            identity.write_pem_files(cert_path, key_path)?;
+            info!(cert_path = ?cert_path, "Generated a self-signed interface certificate");
+            Ok(identity)
+        }
+        (true, false) => Err(InterfaceError::TlsFileMissing(key_path.clone())),
+        (false, true) => Err(InterfaceError::TlsFileMissing(cert_path.clone())),
+    }
+}
+
+/// Create a WebsocketListener on a unix domain socket to be used in interfaces
+#[cfg(unix)]
+pub async fn spawn_unix_socket_listener(path: &Path) -> InterfaceResult<WebsocketListener> {
+    let listener = websocket_bind_unix(path, Arc::new(WebsocketConfig::default())).await?;
+    trace!("LISTENING AT: {}", listener.local_addr());
+    Ok(listener)
+}
+
+/// Create a WebsocketListener for any interface driver.
+/// Returns the listener along with the driver it was actually bound with,
+/// i.e. with the port filled in if the OS chose it.
+pub async fn spawn_interface_listener(
+    driver: InterfaceDriver,
+) -> InterfaceResult<(WebsocketListener, InterfaceDriver)> {
+    match driver {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address,
+            tls,
+        } => {
+            let listener = spawn_websocket_listener(port, bind_address, tls.as_ref()).await?;
+            let port = listener
+                .local_addr()
+                .port()
+                .ok_or(InterfaceError::PortError)?;
+            Ok((
+                listener,
+                InterfaceDriver::Websocket {
+                    port,
+                    bind_address,
+                    tls,
+                },
+            ))
+        }
+        #[cfg(unix)]
+        InterfaceDriver::UnixSocket { path } => {
+            let listener = spawn_unix_socket_listener(&path).await?;
+            Ok((listener, InterfaceDriver::UnixSocket { path }))
+        }
+        #[cfg(not(unix))]
+        InterfaceDriver::UnixSocket { .. } => Err(InterfaceError::Other(
+            "Unix socket interfaces are only supported on unix platforms".to_string(),
+        )),
+    }
+}
+
 /// Create an Admin Interface, which only receives AdminRequest messages
 /// from the external client
-pub fn spawn_admin_interface_task<A: InterfaceApi>(
+pub fn spawn_admin_interface_task<A: InterfaceApi + AdminInterfaceApi>(
     mut listener: WebsocketListener,
     api: A,
     mut stop_rx: StopReceiver,
@@ -72,7 +182,7 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
                             };
                             send_sockets.push(tx_to_iface.clone());
                             listener_handles.push(tokio::task::spawn(recv_incoming_admin_msgs(
-                                api.clone(),
+                                api.for_connection(),
                                 rx_from_iface,
                                 tx_to_iface,
                                 num_connections.clone(),
@@ -112,34 +222,28 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task<A: InterfaceApi>(
-    port: u16,
+/// from Cells via a broadcast channel.
+/// Each connection gets its own copy of the api so it can manage its own
+/// signal subscriptions.
+pub fn spawn_app_interface_task<A: InterfaceApi + AppInterfaceApi>(
+    mut listener: WebsocketListener,
     api: A,
     signal_broadcaster: broadcast::Sender<Signal>,
     mut stop_rx: StopReceiver,
-) -> InterfaceResult<(u16, ManagedTaskHandle)> {
+) -> InterfaceResult<ManagedTaskHandle> {
     trace!("Initializing App interface");
-    let mut listener = websocket_bind(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", listener.local_addr());
-    let port = listener
-        .local_addr()
-        .port()
-        .ok_or(InterfaceError::PortError)?;
-    let task = tokio::task::spawn(async move {
+    Ok(tokio::task::spawn(async move {
         let mut listener_handles = Vec::new();
 
         let mut handle_connection =
             |tx_to_iface: WebsocketSender, rx_from_iface: WebsocketReceiver| {
                 let rx_from_cell = signal_broadcaster.subscribe();
+                let (api, signal_filter) = api.for_connection();
                 listener_handles.push(tokio::task::spawn(recv_incoming_msgs_and_outgoing_signals(
-                    api.clone(),
+                    api,
                     rx_from_iface,
                     rx_from_cell,
+                    signal_filter,
                     tx_to_iface,
                 )));
             };
@@ -167,8 +271,7 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
 
         handle_shutdown(listener_handles).await;
         ManagedTaskResult::Ok(())
-    });
-    Ok((port, task))
+    }))
 }
 
 async fn handle_shutdown(listener_handles: Vec<JoinHandle<InterfaceResult<()>>>) {
@@ -224,11 +327,12 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
 
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
-/// App interface.
+/// App interface. Only the signals the client has subscribed to are sent.
 async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
     api: A,
     mut rx_from_iface: WebsocketReceiver,
     mut rx_from_cell: broadcast::Receiver<Signal>,
+    signal_filter: ConnectionSignalFilter,
     mut tx_to_iface: WebsocketSender,
 ) -> InterfaceResult<()> {
     trace!("CONNECTION: {}", rx_from_iface.remote_addr());
@@ -241,10 +345,13 @@ async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
             // tx and rx together in a new spawned task
             signal = rx_from_cell.next() => {
                 if let Some(signal) = signal {
+                    let signal = signal.map_err(InterfaceError::SignalReceive)?;
+                    if !signal_filter.allows(&signal) {
+                        trace!(msg = "Signal filtered out by subscription", ?signal);
+                        continue;
+                    }
                     trace!(msg = "Sending signal!", ?signal);
-                    let bytes = SerializedBytes::try_from(
-                        signal.map_err(InterfaceError::SignalReceive)?,
-                    )?;
+                    let bytes = SerializedBytes::try_from(signal)?;
                     tx_to_iface.signal(bytes).await?;
                 } else {
                     debug!("Closing interface: signal stream empty");
@@ -293,6 +400,7 @@ pub mod test_utils {
     use crate::conductor::ConductorHandle;
     use holochain_lmdb::test_utils::test_environments;
     use holochain_serialized_bytes::prelude::*;
+    use holochain_types::app::AppBridges;
     use holochain_types::app::InstalledCell;
     use std::sync::Arc;
     use tempdir::TempDir;
@@ -311,7 +419,7 @@ pub mod test_utils {
 
         conductor_handle
             .clone()
-            .install_app("test app".to_string(), cell_data)
+            .install_app("test app".to_string(), cell_data, AppBridges::new())
             .await
             .unwrap();
 
@@ -342,8 +450,10 @@ pub mod test {
     use crate::conductor::api::AdminRequest;
     use crate::conductor::api::AdminResponse;
     use crate::conductor::api::RealAdminInterfaceApi;
+    use crate::conductor::cell::error::CellError;
     use crate::conductor::conductor::ConductorBuilder;
     use crate::conductor::dna_store::MockDnaStore;
+    use crate::conductor::error::ConductorError;
     use crate::conductor::p2p_store::AgentKv;
     use crate::conductor::p2p_store::AgentKvKey;
     use crate::conductor::state::ConductorState;
@@ -354,11 +464,14 @@ pub mod test {
     use ::fixt::prelude::*;
     use fallible_iterator::FallibleIterator;
     use futures::future::FutureExt;
+    use holo_hash::HeaderHash;
+    use holochain_conductor_api::state_dump::DumpStateOptions;
     use holochain_lmdb::buffer::KvStoreT;
     use holochain_lmdb::fresh_reader_test;
     use holochain_lmdb::test_utils::test_environments;
     use holochain_serialized_bytes::prelude::*;
     use holochain_state::source_chain::SourceChainBuf;
+    use holochain_types::app::AppBridges;
     use holochain_types::app::InstallAppDnaPayload;
     use holochain_types::app::InstallAppPayload;
     use holochain_types::app::InstalledCell;
@@ -412,7 +525,7 @@ pub mod test {
 
         conductor_handle
             .clone()
-            .install_app("test app".to_string(), cell_data)
+            .install_app("test app".to_string(), cell_data, AppBridges::new())
             .await
             .unwrap();
 
@@ -668,13 +781,151 @@ pub mod test {
         shutdown.await.unwrap();
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn uninstall_app() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![("zomey".into(), TestWasm::Foo.into())],
+        );
+        let cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_1()));
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store.expect_get().returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let env_path = conductor_handle
+            .get_cell_env(&cell_id)
+            .await
+            .unwrap()
+            .path()
+            .clone();
+        assert!(env_path.is_dir());
+
+        let uninstall = || {
+            let msg = AdminRequest::UninstallApp {
+                installed_app_id: "test app".to_string(),
+            };
+            msg.try_into().unwrap()
+        };
+        let respond = |bytes: SerializedBytes| {
+            let response: AdminResponse = bytes.try_into().unwrap();
+            assert_matches!(response, AdminResponse::AppUninstalled);
+            async { Ok(()) }.boxed()
+        };
+        let msg = WebsocketMessage::Request(uninstall(), Box::new(respond));
+        handle_incoming_message(msg, RealAdminInterfaceApi::new(conductor_handle.clone()))
+            .await
+            .unwrap();
+
+        // The app and its cell are gone
+        let state = conductor_handle.get_state_from_handle().await.unwrap();
+        assert_eq!(state.active_apps.get("test app"), None);
+        assert_eq!(state.inactive_apps.get("test app"), None);
+        assert!(conductor_handle.list_cell_ids().await.unwrap().is_empty());
+        assert!(!env_path.exists());
+
+        // Uninstalling again is an error
+        let respond = |bytes: SerializedBytes| {
+            let response: AdminResponse = bytes.try_into().unwrap();
+            assert_matches!(response, AdminResponse::Error(_));
+            async { Ok(()) }.boxed()
+        };
+        let msg = WebsocketMessage::Request(uninstall(), Box::new(respond));
+        handle_incoming_message(msg, RealAdminInterfaceApi::new(conductor_handle.clone()))
+            .await
+            .unwrap();
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn migrate_agent_keeps_old_cell_when_open_fails() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let old_dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![("zomey".into(), TestWasm::MigrateAgentPass.into())],
+        );
+        // The new DNA's migrate_agent callback won't open its chain
+        let new_dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![("zomey".into(), TestWasm::MigrateAgentFail.into())],
+        );
+        let old_cell_id = CellId::from((old_dna.dna_hash().clone(), fake_agent_pubkey_1()));
+        let new_dna_hash = new_dna.dna_hash().clone();
+        let dna_map = vec![old_dna, new_dna]
+            .into_iter()
+            .map(|dna| (dna.dna_hash().clone(), dna))
+            .collect::<HashMap<_, _>>();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .returning(move |hash| dna_map.get(&hash).cloned());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(old_cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let state = conductor_handle.get_state_from_handle().await.unwrap();
+        let old_cells = state.active_apps.get("test app").cloned().unwrap();
+
+        let result = conductor_handle
+            .clone()
+            .migrate_agent(
+                "test app".to_string(),
+                old_cells[0].as_nick().clone(),
+                new_dna_hash,
+                None,
+            )
+            .await;
+        assert_matches!(
+            result,
+            Err(ConductorError::InternalCellError(
+                CellError::MigrateAgentFailed(_)
+            ))
+        );
+
+        // The app still uses the old cell and the new cell is gone
+        let state = conductor_handle.get_state_from_handle().await.unwrap();
+        assert_eq!(state.active_apps.get("test app"), Some(&old_cells));
+        assert_eq!(
+            conductor_handle.list_cell_ids().await.unwrap(),
+            vec![old_cell_id]
+        );
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn attach_app_interface() {
         observability::test_run().ok();
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            require_token: false,
+            bind_address: None,
+            tls: None,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -718,19 +969,47 @@ pub mod test {
         let cell_env = conductor_handle.get_cell_env(&cell_id).await.unwrap();
 
         // Get state
-        let expected = {
+        let expected: Vec<HeaderHash> = {
             let source_chain = SourceChainBuf::new(cell_env.clone().into()).unwrap();
-            source_chain.dump_as_json().await.unwrap()
+            let mut headers: Vec<_> = source_chain
+                .iter_back()
+                .map(|h| Ok(h.header_address().clone()))
+                .collect()
+                .unwrap();
+            headers.reverse();
+            headers
         };
 
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
         let msg = AdminRequest::DumpState {
-            cell_id: Box::new(cell_id),
+            cell_id: Box::new(cell_id.clone()),
+            options: DumpStateOptions {
+                include_entries: true,
+            },
         };
         let msg = msg.try_into().unwrap();
         let respond = move |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
-            assert_matches!(response, AdminResponse::StateDumped(s) if s == expected);
+            let dump = unwrap_to::unwrap_to!(response => AdminResponse::StateDumped).clone();
+            assert_eq!(dump.cell_id, cell_id);
+            let headers: Vec<_> = dump
+                .source_chain
+                .iter()
+                .map(|e| e.header_address.clone())
+                .collect();
+            assert_eq!(headers, expected);
+            for element in &dump.source_chain {
+                assert_eq!(
+                    element.header.entry_data().is_some(),
+                    element.entry.is_some()
+                );
+            }
+            let chain_sequence = dump
+                .databases
+                .iter()
+                .find(|db| db.db == "ChainSequence")
+                .expect("ChainSequence should be in the dump");
+            assert_eq!(chain_sequence.entries, expected.len() as u64);
             async { Ok(()) }.boxed()
         };
         let respond = Box::new(respond);
@@ -747,6 +1026,8 @@ pub mod test {
                 uuid: uuid.to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
                 zomes: zomes.clone().into_iter().map(Into::into).collect(),
+                default_required_validations: Default::default(),
+                entry_type_properties: Default::default(),
             },
             zomes.into_iter().map(Into::into),
         )
@@ -25,6 +25,9 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+    /// The Cells each Cell of an app may call, for apps which declared them
+    #[serde(default)]
+    pub app_bridges: HashMap<InstalledAppId, AppBridges>,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -58,9 +61,32 @@ impl ConductorState {
             .map(|cell_data| InstalledApp {
                 installed_app_id: installed_app_id.clone(),
                 cell_data: cell_data.clone(),
+                bridges: self
+                    .app_bridges
+                    .get(installed_app_id)
+                    .cloned()
+                    .unwrap_or_default(),
             })
     }
 
+    /// Can a zome call in one Cell call into another Cell.
+    /// Every active app containing the calling Cell that declares
+    /// bridges for it must list the other Cell.
+    pub fn is_bridge_allowed(&self, from_cell: &CellId, to_cell: &CellId) -> bool {
+        self.active_apps.iter().all(|(installed_app_id, cells)| {
+            let allowed = cells
+                .iter()
+                .find(|c| c.as_id() == from_cell)
+                .and_then(|c| self.app_bridges.get(installed_app_id)?.get(c.as_nick()));
+            match allowed {
+                Some(allowed) => cells
+                    .iter()
+                    .any(|c| c.as_id() == to_cell && allowed.contains(c.as_nick())),
+                None => true,
+            }
+        })
+    }
+
     /// Returns the interface configuration with the given ID if present
     pub fn interface_by_id(&self, id: &AppInterfaceId) -> Option<AppInterfaceConfig> {
         self.app_interfaces.get(id).cloned()
@@ -71,9 +97,7 @@ impl ConductorState {
 /// GUIs, browser based web UIs, local native UIs, other local applications and scripts.
 /// We currently have:
 /// * websockets
-///
-/// We will also soon develop
-/// * Unix domain sockets
+/// * websockets over Unix domain sockets
 ///
 /// The cells (referenced by ID) that are to be made available via that interface should be listed.
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -84,14 +108,36 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// Whether connections must authenticate with an app token
+    #[serde(default)]
+    pub require_token: bool,
 }
 
 impl AppInterfaceConfig {
+    /// Create config for an interface with this driver
+    pub fn new(driver: InterfaceDriver) -> Self {
+        Self {
+            signal_subscriptions: HashMap::new(),
+            driver,
+            require_token: false,
+        }
+    }
+
     /// Create config for a websocket interface
     pub fn websocket(port: u16) -> Self {
+        Self::new(InterfaceDriver::Websocket {
+            port,
+            bind_address: None,
+            tls: None,
+        })
+    }
+
+    /// Require connections to authenticate with an app token
+    pub fn require_token(self, require_token: bool) -> Self {
         Self {
-            signal_subscriptions: HashMap::new(),
-            driver: InterfaceDriver::Websocket { port },
+            require_token,
+            ..self
         }
     }
 }
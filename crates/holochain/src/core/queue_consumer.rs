@@ -19,6 +19,9 @@
 //! |                 **integration, common to both paths**                 |
 //! | DhtOpIntegr.   | IntegrationLimbo | IntegratedDhtOps | Publish        |
 //! | Publish        | AuthoredDhtOps   | *n/a*            | *n/a*          |
+//! |                        **scheduled functions**                        |
+//! | CallZome       | *n/a*            | ScheduledFns     | Schedule       |
+//! | Schedule       | ScheduledFns     | ScheduledFns     | *n/a*          |
 //!
 //! († Auth'd + IntQ is short for: AuthoredDhtOps + IntegrationLimbo)
 //!
@@ -45,11 +48,13 @@ use app_validation_consumer::*;
 mod produce_dht_ops_consumer;
 use produce_dht_ops_consumer::*;
 mod publish_dht_ops_consumer;
+mod schedule_consumer;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::manager::ManagedTaskAdd;
 use holochain_p2p::HolochainP2pCell;
 use holochain_state::workspace::WorkspaceError;
 use publish_dht_ops_consumer::*;
+use schedule_consumer::*;
 
 /// Spawns several long-running tasks which are responsible for processing work
 /// which shows up on various databases.
@@ -64,8 +69,12 @@ pub async fn spawn_queue_consumer_tasks(
     stop: sync::broadcast::Sender<()>,
 ) -> (QueueTriggers, InitialQueueTriggers) {
     // Publish
-    let (tx_publish, handle) =
-        spawn_publish_dht_ops_consumer(env.clone(), stop.subscribe(), cell_network.clone());
+    let (tx_publish, handle) = spawn_publish_dht_ops_consumer(
+        env.clone(),
+        stop.subscribe(),
+        conductor_api.clone(),
+        cell_network.clone(),
+    );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
         .await
@@ -100,7 +109,7 @@ pub async fn spawn_queue_consumer_tasks(
         stop.subscribe(),
         tx_app.clone(),
         cell_network,
-        conductor_api,
+        conductor_api.clone(),
     );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
@@ -118,9 +127,24 @@ pub async fn spawn_queue_consumer_tasks(
         .await
         .expect("Failed to manage workflow handle");
 
+    // Schedule
+    let (tx_schedule, handle) =
+        spawn_schedule_consumer(env.clone(), stop.subscribe(), conductor_api);
+    task_sender
+        .send(ManagedTaskAdd::dont_handle(handle))
+        .await
+        .expect("Failed to manage workflow handle");
+
     (
-        QueueTriggers::new(tx_sys.clone(), tx_produce.clone()),
-        InitialQueueTriggers::new(tx_sys, tx_produce, tx_publish, tx_app, tx_integration),
+        QueueTriggers::new(tx_sys.clone(), tx_produce.clone(), tx_schedule.clone()),
+        InitialQueueTriggers::new(
+            tx_sys,
+            tx_produce,
+            tx_publish,
+            tx_app,
+            tx_integration,
+            tx_schedule,
+        ),
     )
 }
 
@@ -131,6 +155,9 @@ pub struct QueueTriggers {
     pub sys_validation: TriggerSender,
     /// Notify the ProduceDhtOps workflow to run, i.e. after InvokeCallZome
     pub produce_dht_ops: TriggerSender,
+    /// Notify the Schedule workflow to run, i.e. after InvokeCallZome
+    /// has scheduled a function
+    pub schedule: TriggerSender,
 }
 
 /// The triggers to run once at the start of a cell
@@ -142,14 +169,20 @@ pub struct InitialQueueTriggers {
     publish_dht_ops: TriggerSender,
     app_validation: TriggerSender,
     integrate_dht_ops: TriggerSender,
+    schedule: TriggerSender,
 }
 
 impl QueueTriggers {
     /// Create a new queue trigger
-    pub fn new(sys_validation: TriggerSender, produce_dht_ops: TriggerSender) -> Self {
+    pub fn new(
+        sys_validation: TriggerSender,
+        produce_dht_ops: TriggerSender,
+        schedule: TriggerSender,
+    ) -> Self {
         Self {
             sys_validation,
             produce_dht_ops,
+            schedule,
         }
     }
 }
@@ -161,6 +194,7 @@ impl InitialQueueTriggers {
         publish_dht_ops: TriggerSender,
         app_validation: TriggerSender,
         integrate_dht_ops: TriggerSender,
+        schedule: TriggerSender,
     ) -> Self {
         Self {
             sys_validation,
@@ -168,6 +202,7 @@ impl InitialQueueTriggers {
             publish_dht_ops,
             app_validation,
             integrate_dht_ops,
+            schedule,
         }
     }
 
@@ -178,6 +213,7 @@ impl InitialQueueTriggers {
         self.publish_dht_ops.trigger();
         self.integrate_dht_ops.trigger();
         self.produce_dht_ops.trigger();
+        self.schedule.trigger();
     }
 }
 /// The means of nudging a queue consumer to tell it to look for more work
@@ -6,23 +6,40 @@ use crate::conductor::manager::ManagedTaskResult;
 use crate::core::workflow::publish_dht_ops_workflow::publish_dht_ops_workflow;
 use crate::core::workflow::publish_dht_ops_workflow::PublishDhtOpsWorkspace;
 use holochain_lmdb::env::EnvironmentWrite;
+use holochain_types::prelude::*;
 
 use tokio::task::JoinHandle;
 use tracing::*;
 
 /// Spawn the QueueConsumer for Publish workflow
-#[instrument(skip(env, stop, cell_network))]
+///
+/// As well as running when triggered, this consumer wakes itself
+/// up when the next queued op is due to be published again.
+#[instrument(skip(env, stop, conductor_api, cell_network))]
 pub fn spawn_publish_dht_ops_consumer(
     env: EnvironmentWrite,
     mut stop: sync::broadcast::Receiver<()>,
+    conductor_api: impl CellConductorApiT + 'static,
     mut cell_network: HolochainP2pCell,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
     let (tx, mut rx) = TriggerSender::new();
-    let mut trigger_self = tx.clone();
     let handle = tokio::spawn(async move {
+        let mut next_publish: Option<Timestamp> = None;
         loop {
-            // Wait for next job
-            if let Job::Shutdown = next_job_or_exit(&mut rx, &mut stop).await {
+            // Wait for next job or for the next op to be due
+            let job = match next_publish {
+                Some(next_publish) => {
+                    let wait = next_publish
+                        .checked_difference_signed(&holochain_types::timestamp::now())
+                        .and_then(|d| d.to_std().ok())
+                        .unwrap_or_default();
+                    tokio::time::timeout(wait, next_job_or_exit(&mut rx, &mut stop))
+                        .await
+                        .unwrap_or(Job::Run)
+                }
+                None => next_job_or_exit(&mut rx, &mut stop).await,
+            };
+            if let Job::Shutdown = job {
                 tracing::warn!(
                     "Cell is shutting down: stopping publish_dht_ops_workflow queue consumer."
                 );
@@ -32,13 +49,14 @@ pub fn spawn_publish_dht_ops_consumer(
             // Run the workflow
             let workspace = PublishDhtOpsWorkspace::new(env.clone().into())
                 .expect("Could not create Workspace");
-            if let WorkComplete::Incomplete =
-                publish_dht_ops_workflow(workspace, env.clone().into(), &mut cell_network)
-                    .await
-                    .expect("Error running Workflow")
-            {
-                trigger_self.trigger()
-            };
+            next_publish = publish_dht_ops_workflow(
+                workspace,
+                env.clone().into(),
+                &mut cell_network,
+                &conductor_api,
+            )
+            .await
+            .expect("Error running Workflow");
         }
         Ok(())
     });
@@ -0,0 +1,56 @@
+//! The workflow and queue consumer for scheduled zome functions
+
+use super::*;
+
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::schedule_workflow::schedule_workflow;
+use crate::core::workflow::schedule_workflow::ScheduleWorkspace;
+use holochain_lmdb::env::EnvironmentWrite;
+use holochain_types::prelude::*;
+
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// Spawn the QueueConsumer for the Schedule workflow.
+///
+/// As well as running when triggered, this consumer wakes itself
+/// up when the next scheduled function is due.
+#[instrument(skip(env, stop, conductor_api))]
+pub fn spawn_schedule_consumer(
+    env: EnvironmentWrite,
+    mut stop: sync::broadcast::Receiver<()>,
+    conductor_api: impl CellConductorApiT + 'static,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let handle = tokio::spawn(async move {
+        let mut next_run: Option<Timestamp> = None;
+        loop {
+            // Wait for next job or for the next function to be due
+            let job = match next_run {
+                Some(next_run) => {
+                    let wait = next_run
+                        .checked_difference_signed(&holochain_types::timestamp::now())
+                        .and_then(|d| d.to_std().ok())
+                        .unwrap_or_default();
+                    tokio::time::timeout(wait, next_job_or_exit(&mut rx, &mut stop))
+                        .await
+                        .unwrap_or(Job::Run)
+                }
+                None => next_job_or_exit(&mut rx, &mut stop).await,
+            };
+            if let Job::Shutdown = job {
+                tracing::warn!("Cell is shutting down: stopping schedule_workflow queue consumer.");
+                break;
+            }
+
+            // Run the workflow
+            let workspace =
+                ScheduleWorkspace::new(env.clone().into()).expect("Could not create Workspace");
+            next_run = schedule_workflow(workspace, env.clone().into(), conductor_api.clone())
+                .await
+                .expect("Error running Workflow");
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
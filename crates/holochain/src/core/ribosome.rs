@@ -32,7 +32,6 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use error::RibosomeResult;
 use guest_callback::entry_defs::EntryDefsHostAccess;
 use guest_callback::init::InitHostAccess;
@@ -256,19 +255,26 @@ impl ZomeCallInvocation {
     /// - the live cap grant needs to include the invocation's provenance AND zome/function name
     #[allow(clippy::extra_unused_lifetimes)]
     pub fn is_authorized<'a>(&self, host_access: &ZomeCallHostAccess) -> RibosomeResult<bool> {
+        Ok(self.authorizing_grant(host_access)?.is_some())
+    }
+
+    /// the live cap grant that authorizes this zome call, if any
+    /// @see is_authorized
+    pub fn authorizing_grant(
+        &self,
+        host_access: &ZomeCallHostAccess,
+    ) -> RibosomeResult<Option<CapGrant>> {
         let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
         let check_agent = self.provenance.clone();
         let check_secret = self.cap;
 
         tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-            let maybe_grant: Option<CapGrant> = host_access
+            Ok(host_access
                 .workspace
                 .read()
                 .await
                 .source_chain
-                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?;
-
-            Ok(maybe_grant.is_some())
+                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?)
         })
     }
 }
@@ -366,7 +372,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
     }
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ZomeCallHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
@@ -377,6 +383,30 @@ pub struct ZomeCallHostAccess {
     // "resource" to give access to, but rather it's a bit of data that makes sense in
     // the context of zome calls, but not every CallContext
     pub cell_id: CellId,
+    /// The capability that authorized the call.
+    /// This is set by the ribosome once the call has been authorized.
+    pub cap_info: Option<CapInfo>,
+}
+
+impl ZomeCallHostAccess {
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        keystore: KeystoreSender,
+        network: HolochainP2pCell,
+        signal_tx: SignalBroadcaster,
+        call_zome_handle: CellConductorReadHandle,
+        cell_id: CellId,
+    ) -> Self {
+        Self {
+            workspace,
+            keystore,
+            network,
+            signal_tx,
+            call_zome_handle,
+            cell_id,
+            cap_info: None,
+        }
+    }
 }
 
 impl From<ZomeCallHostAccess> for HostAccess {
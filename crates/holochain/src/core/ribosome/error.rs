@@ -37,6 +37,10 @@ pub enum RibosomeError {
     #[error("Attempted to call a zome function that doesn't exist: Zome: {0} Fn {1}")]
     ZomeFnNotExists(ZomeName, FunctionName),
 
+    /// A zome function or callback went over one of the conductor's wasm limits
+    #[error("Zome {0} function {1} went over its wasm limits: {2}")]
+    WasmLimitExceeded(ZomeName, FunctionName, WasmLimitExceeded),
+
     /// a problem with entry defs
     #[error("An error with entry defs: {0}")]
     EntryDefs(ZomeName, String),
@@ -105,6 +109,25 @@ pub enum RibosomeError {
     SecurePrimitive(#[from] holochain_zome_types::SecurePrimitiveError),
 }
 
+/// The wasm limit that a call went over
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WasmLimitExceeded {
+    /// The call ran more instructions than it was allowed
+    #[error("the call ran out of fuel after {0} instructions")]
+    Fuel(u64),
+
+    /// The wasm needed more memory than it was allowed
+    #[error("the wasm needed more than the {max} bytes of memory it is allowed")]
+    Memory {
+        /// The most memory allowed
+        max: u64,
+    },
+
+    /// The call didn't return in time
+    #[error("the call didn't return within {0:?}")]
+    Timeout(std::time::Duration),
+}
+
 impl From<xsalsa20poly1305::aead::Error> for RibosomeError {
     fn from(error: xsalsa20poly1305::aead::Error) -> Self {
         Self::Aead(error.to_string())
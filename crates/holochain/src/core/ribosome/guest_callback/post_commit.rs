@@ -36,7 +36,11 @@ impl From<PostCommitHostAccess> for HostAccess {
 
 impl From<&PostCommitHostAccess> for HostFnAccess {
     fn from(_: &PostCommitHostAccess) -> Self {
-        Self::all()
+        let mut access = Self::all();
+        // The commit has already been flushed so
+        // anything written here would be lost.
+        access.write_workspace = Permission::Deny;
+        access
     }
 }
 
@@ -97,6 +101,7 @@ mod test {
     use crate::fixt::PostCommitInvocationFixturator;
     use ::fixt::prelude::*;
     use holochain_types::dna::zome::HostFnAccess;
+    use holochain_types::dna::zome::Permission;
     use holochain_zome_types::post_commit::PostCommitCallbackResult;
     use holochain_zome_types::ExternIO;
 
@@ -148,10 +153,9 @@ mod test {
         let post_commit_host_access = PostCommitHostAccessFixturator::new(::fixt::Unpredictable)
             .next()
             .unwrap();
-        assert_eq!(
-            HostFnAccess::from(&post_commit_host_access),
-            HostFnAccess::all()
-        );
+        let mut access = HostFnAccess::all();
+        access.write_workspace = Permission::Deny;
+        assert_eq!(HostFnAccess::from(&post_commit_host_access), access);
     }
 
     #[test]
@@ -54,14 +54,14 @@ host_fn_api_impls! {
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List all the live local capability claims, optionally filtered by tag.
+    fn capability_claims (Option<String>) -> Vec<zt::capability::CapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the live local capability grants, optionally filtered by tag.
+    fn capability_grants (Option<String>) -> Vec<zt::capability::LiveCapGrant>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapInfo;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -94,8 +94,8 @@ host_fn_api_impls! {
     // Header hash of the CreateLink element.
     fn delete_link (holo_hash::HeaderHash) -> holo_hash::HeaderHash;
 
-    // @todo
-    fn entry_type_properties (()) -> ();
+    // Get the properties the dna declares for one of the current zome's entry types.
+    fn entry_type_properties (zt::entry_def::EntryDefId) -> Option<SerializedBytes>;
 
     // Header hash of the newly committed element.
     // Emit a Signal::App to subscribers on the interface
@@ -115,6 +115,9 @@ host_fn_api_impls! {
     // Get links by entry hash from the cascade.
     fn get_links (zt::link::GetLinksInput) -> zt::link::Links;
 
+    // Get a page of links by entry hash from the cascade.
+    fn get_links_page (zt::link::GetLinksPageInput) -> zt::link::LinkPage;
+
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
 
     // Hash an entry on the host.
@@ -135,11 +138,11 @@ host_fn_api_impls! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called by the host later.
+    fn schedule (zt::schedule::Schedule) -> ();
 
-    // @todo
-    fn show_env (()) -> ();
+    // Information about the environment the current zome call is running in.
+    fn show_env (()) -> zt::env_info::EnvInfo;
 
     // Attempt to have the keystore sign some data
     // The pubkey in the input needs to be found in the keystore for this to work
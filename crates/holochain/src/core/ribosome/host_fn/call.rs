@@ -23,6 +23,10 @@ pub fn call(
 
     let zome_name = call.zome_name.clone();
 
+    // The caller is always this cell's agent, whatever the guest claims,
+    // so capability checks in the called cell can be trusted
+    let provenance = conductor_handle.cell_id().agent_pubkey().clone();
+
     // Create the invocation for this call
     let invocation = ZomeCall {
         cell_id,
@@ -30,10 +34,10 @@ pub fn call(
         cap: call.cap,
         fn_name: call.fn_name,
         payload: call.payload,
-        provenance: call.provenance,
+        provenance,
     };
 
-    // Make the call using this workspace
+    // Make the call, sharing this workspace if it is to this cell
     Ok(tokio_safe_block_on::tokio_safe_block_forever_on(async move {
         conductor_handle
             .call_zome(invocation, workspace)
@@ -52,6 +56,7 @@ pub mod wasm_test {
     use hdk3::prelude::CellId;
     use holo_hash::HeaderHash;
     use holochain_serialized_bytes::SerializedBytes;
+    use holochain_types::app::AppBridges;
     use holochain_types::app::InstalledCell;
     use holochain_types::dna::DnaDef;
     use holochain_types::dna::DnaFile;
@@ -63,7 +68,7 @@ pub mod wasm_test {
 
     use crate::conductor::{api::ZomeCall, ConductorHandle};
     use crate::test_utils::conductor_setup::ConductorTestData;
-    use crate::test_utils::install_app;
+    use crate::test_utils::install_app_with_bridges;
     use crate::test_utils::new_zome_call;
     use holochain_state::element_buf::ElementBuf;
 
@@ -202,10 +207,54 @@ pub mod wasm_test {
         conductor_test.shutdown_conductor().await;
     }
 
+    /// A bridge call is refused when the caller's app
+    /// doesn't list the called cell in its bridges.
+    #[tokio::test(threaded_scheduler)]
+    async fn bridge_call_not_allowed_by_app() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::Create];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+        let handle = conductor_test.handle();
+        let alice_call_data = conductor_test.alice_call_data();
+        let alice_cell_id = &alice_call_data.cell_id;
+
+        // Bob's cell may not call any other cell
+        let mut bridges = AppBridges::new();
+        bridges.insert("bob_handle".to_string(), vec![]);
+        let zomes = vec![TestWasm::WhoAmI];
+        let bob_cell_id = install_new_app_with_bridges("bobs_dna", zomes, bridges, &handle).await;
+
+        let invocation = new_zome_call(
+            &bob_cell_id,
+            "call_create_entry",
+            alice_cell_id.clone(),
+            TestWasm::WhoAmI,
+        )
+        .unwrap();
+        let result = handle.call_zome(invocation).await;
+        assert!(
+            format!("{:?}", result).contains("does not allow it to call"),
+            "{:?}",
+            result
+        );
+
+        conductor_test.shutdown_conductor().await;
+    }
+
     async fn install_new_app(
         dna_name: &str,
         zomes: Vec<TestWasm>,
         handle: &ConductorHandle,
+    ) -> CellId {
+        install_new_app_with_bridges(dna_name, zomes, AppBridges::new(), handle).await
+    }
+
+    async fn install_new_app_with_bridges(
+        dna_name: &str,
+        zomes: Vec<TestWasm>,
+        bridges: AppBridges,
+        handle: &ConductorHandle,
     ) -> CellId {
         let dna_file = DnaFile::new(
             DnaDef {
@@ -213,6 +262,8 @@ pub mod wasm_test {
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
                 zomes: zomes.clone().into_iter().map(Into::into).collect(),
+                default_required_validations: Default::default(),
+                entry_type_properties: Default::default(),
             },
             zomes.into_iter().map(Into::into),
         )
@@ -222,7 +273,14 @@ pub mod wasm_test {
         let bob_cell_id = CellId::new(dna_file.dna_hash().to_owned(), bob_agent_id.clone());
         let bob_installed_cell = InstalledCell::new(bob_cell_id.clone(), "bob_handle".into());
         let cell_data = vec![(bob_installed_cell, None)];
-        install_app("bob_app", cell_data, vec![dna_file], handle.clone()).await;
+        install_app_with_bridges(
+            "bob_app",
+            cell_data,
+            vec![dna_file],
+            bridges,
+            handle.clone(),
+        )
+        .await;
         bob_cell_id
     }
 }
@@ -60,6 +60,8 @@ pub mod wasm_test {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::WhoAmI.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         };
         let dna_file = DnaFile::new(dna_def, vec![TestWasm::WhoAmI.into()])
             .await
@@ -1,13 +1,77 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
 /// lists all the local claims filtered by tag
+/// this is only the current claims as per local CRUD
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: Option<String>,
+) -> Result<Vec<CapClaim>, WasmError> {
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .live_cap_claims(input.as_deref())
+    })
+    .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use crate::core::workflow::call_zome_workflow::CallZomeWorkspace;
+    use ::fixt::prelude::*;
+    use hdk3::prelude::*;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_capability_claims_test<'a>() {
+        observability::test_run().ok();
+        // test workspace boilerplate
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        let secret: CapSecret =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "cap_secret", ());
+        let claim = CapClaim::new("foo".into(), fake_agent_pubkey_2(), secret);
+        let _: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "accept_cap_claim",
+            claim
+        );
+
+        let claims: Vec<CapClaim> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_claims",
+            Some(String::from("foo"))
+        );
+        assert_eq!(claims, vec![claim]);
+
+        let claims: Vec<CapClaim> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_claims",
+            Some(String::from("bar"))
+        );
+        assert!(claims.is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
 use std::sync::Arc;
 
@@ -7,10 +8,19 @@ use std::sync::Arc;
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: Option<String>,
+) -> Result<Vec<LiveCapGrant>, WasmError> {
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .live_cap_grants(input.as_deref())
+    })
+    .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
 }
 
 #[cfg(test)]
@@ -89,6 +99,87 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret,);
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_capability_grants_test<'a>() {
+        observability::test_run().ok();
+        // test workspace boilerplate
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        let grants: Vec<LiveCapGrant> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_grants",
+            Option::<String>::None
+        );
+        assert!(grants.is_empty());
+
+        let secret: CapSecret =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "cap_secret", ());
+        let original_header: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "transferable_cap_grant",
+            secret
+        );
+        let grants: Vec<LiveCapGrant> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_grants",
+            Option::<String>::None
+        );
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, original_header);
+
+        // the tag filters the grants
+        let grants: Vec<LiveCapGrant> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_grants",
+            Some(String::from("not a tag"))
+        );
+        assert!(grants.is_empty());
+
+        // an updated grant replaces the original
+        let new_header: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "roll_cap_grant",
+            original_header
+        );
+        let grants: Vec<LiveCapGrant> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_grants",
+            Option::<String>::None
+        );
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, new_header);
+
+        // a deleted grant is no longer live
+        let _: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "delete_cap_grant",
+            new_header
+        );
+        let grants: Vec<LiveCapGrant> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "list_cap_grants",
+            Option::<String>::None
+        );
+        assert!(grants.is_empty());
+    }
+
     // TODO: [ B-03669 ] can move this to an integration test (may need to switch to using a RealDnaStore)
     #[tokio::test(threaded_scheduler)]
     async fn ribosome_authorized_call() -> anyhow::Result<()> {
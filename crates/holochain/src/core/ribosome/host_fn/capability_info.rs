@@ -1,5 +1,8 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
@@ -7,8 +10,51 @@ use holochain_wasmer_host::prelude::WasmError;
 /// also return who is originated the call (pubkey)
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+) -> Result<CapInfo, WasmError> {
+    match &call_context.host_access {
+        HostAccess::ZomeCall(ZomeCallHostAccess {
+            cap_info: Some(cap_info),
+            ..
+        }) => Ok(cap_info.clone()),
+        // callbacks are called by the host so there is no capability to report
+        _ => Err(WasmError::Host(
+            "capability_info is only available to authorized zome calls".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use crate::core::workflow::call_zome_workflow::CallZomeWorkspace;
+    use ::fixt::prelude::*;
+    use hdk3::prelude::*;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_capability_info_test<'a>() {
+        observability::test_run().ok();
+        // test workspace boilerplate
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        let cap_info: CapInfo =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "cap_info", ());
+
+        // the test ribosome simulates the author calling their own chain
+        assert_eq!(cap_info.grant, CapGrant::ChainAuthor(cap_info.provenance.clone()));
+        assert_eq!(cap_info.provenance, fake_agent_pubkey_1());
+    }
 }
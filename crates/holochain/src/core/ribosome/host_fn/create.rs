@@ -273,6 +273,8 @@ pub mod wasm_test {
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
                 zomes: vec![TestWasm::MultipleCalls.into()].into(),
+                default_required_validations: Default::default(),
+                entry_type_properties: Default::default(),
             },
             vec![TestWasm::MultipleCalls.into()],
         )
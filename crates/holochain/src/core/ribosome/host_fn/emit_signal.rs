@@ -11,7 +11,8 @@ pub fn emit_signal(
     input: AppSignal,
 ) -> Result<(), WasmError> {
     let cell_id = call_context.host_access().cell_id().clone();
-    let signal = Signal::App(cell_id, input);
+    let zome_name = call_context.zome().zome_name().clone();
+    let signal = Signal::App(cell_id, zome_name, input);
     call_context.host_access().signal_tx().send(signal).map_err(|interface_error| WasmError::Host(interface_error.to_string()))?;
     Ok(())
 }
@@ -1,12 +1,51 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
 
+/// The properties the dna declares for an entry type of the calling zome.
+/// Entry types without declared properties return `None`.
 pub fn entry_type_properties(
-    _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: EntryDefId,
+) -> Result<Option<SerializedBytes>, WasmError> {
+    Ok(ribosome
+        .dna_def()
+        .get_entry_type_properties(call_context.zome.zome_name(), &input)
+        .cloned())
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::prelude::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_entry_type_properties_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let properties: Option<SerializedBytes> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::ZomeInfo,
+            "entry_type_properties",
+            EntryDefId::from("post")
+        );
+        // The test dna doesn't declare any entry type properties
+        assert_eq!(properties, None);
+    }
 }
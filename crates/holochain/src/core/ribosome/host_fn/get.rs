@@ -33,3 +33,77 @@ pub fn get<'a>(
 
 // we are relying on the create tests to show the commit/get round trip
 // @see commit_entry.rs
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::workflow::CallZomeWorkspace;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use hdk3::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_get_follow_redirects_test() {
+        observability::test_run().ok();
+
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        // simple replica of the internal type for the TestWasm::Crud entry
+        #[derive(Clone, Copy, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+        struct CounTree(u32);
+
+        let check = |element: Option<Element>, count, header_hash: &HeaderHash| {
+            let element = element.expect("no element");
+            assert_eq!(element.header_address(), header_hash);
+            assert_eq!(
+                element.entry().to_app_option::<CounTree>().unwrap(),
+                Some(CounTree(count))
+            );
+        };
+
+        let zero_hash: EntryHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "entry_hash", CounTree(0));
+
+        let zero: HeaderHash = crate::call_test_ribosome!(host_access, TestWasm::Crud, "new", ());
+        check(
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "newest_entry", zero_hash),
+            0,
+            &zero,
+        );
+        check(
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "newest_header", zero),
+            0,
+            &zero,
+        );
+
+        // Branch the original then update the newest branch
+        let _one_a: HeaderHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "inc", zero);
+        let one_b: HeaderHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "inc", zero);
+        let two: HeaderHash = crate::call_test_ribosome!(host_access, TestWasm::Crud, "inc", one_b);
+
+        check(
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "newest_entry", zero_hash),
+            2,
+            &two,
+        );
+        check(
+            crate::call_test_ribosome!(host_access, TestWasm::Crud, "newest_header", zero),
+            2,
+            &two,
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_p2p::actor::GetLinksOptions;
+use holochain_state::metadata::LinkMetaKey;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn get_links_page<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetLinksPageInput,
+) -> Result<LinkPage, WasmError> {
+    let GetLinksPageInput {
+        base_address,
+        tag_prefix,
+        query,
+    } = input;
+
+    // Get zome id
+    let zome_id = ribosome
+        .zome_to_id(&call_context.zome)
+        .expect("Failed to get ID for current zome.");
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        // Create the key
+        let key = match tag_prefix.as_ref() {
+            Some(tag_prefix) => LinkMetaKey::BaseZomeTag(&base_address, zome_id, tag_prefix),
+            None => LinkMetaKey::BaseZome(&base_address, zome_id),
+        };
+        let options = GetLinksOptions {
+            page: query,
+            ..Default::default()
+        };
+
+        // Get the page of links from the dht
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .cascade(network)
+            .dht_get_links_page(&key, options)
+            .await
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holo_hash::HeaderHash;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::link::LinkPage;
+    use holochain_zome_types::link::LinkPageQuery;
+    use std::num::NonZeroU32;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_get_links_page() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        // commits fail validation if we don't do genesis
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+
+        let mut created = Vec::new();
+        for _ in 0..5 {
+            let hash: HeaderHash =
+                crate::call_test_ribosome!(host_access, TestWasm::Link, "create_link", ());
+            created.push(hash);
+        }
+        let _: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Link,
+            "delete_link",
+            created[1].clone()
+        );
+
+        // Page through two links at a time
+        let mut query = LinkPageQuery::new().limit(NonZeroU32::new(2).unwrap());
+        let mut pages = Vec::new();
+        loop {
+            let page: LinkPage = crate::call_test_ribosome!(
+                host_access,
+                TestWasm::Link,
+                "get_links_page",
+                query.clone()
+            );
+            pages.push(
+                page.links
+                    .into_iter()
+                    .map(|link| link.create_link_hash)
+                    .collect::<Vec<_>>(),
+            );
+            match page.next {
+                Some(next) => query = query.after(next),
+                None => break,
+            }
+        }
+
+        // The deleted link is skipped but still counts towards its page
+        assert_eq!(
+            pages,
+            vec![
+                vec![created[0].clone()],
+                created[2..4].to_vec(),
+                created[4..].to_vec(),
+            ]
+        );
+    }
+}
@@ -1,12 +1,110 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_state::schedule_db::ScheduledFn;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
 
+/// Schedule a function in the calling zome to be called by the host later.
+/// The schedule is written to the workspace so it is only persisted if the
+/// zome call that made it succeeds.
 pub fn schedule(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: Schedule,
 ) -> Result<(), WasmError> {
-    unimplemented!()
+    let Schedule {
+        fn_name,
+        delay,
+        interval,
+    } = input;
+    // A zero interval would have the scheduler call the function in a tight loop.
+    if interval == Some(core::time::Duration::from_secs(0)) {
+        return Err(WasmError::Host(
+            "A recurring schedule must have an interval greater than zero".to_string(),
+        ));
+    }
+    let next_run = (holochain_types::timestamp::now() + delay)
+        .map_err(|timestamp_error| WasmError::Host(timestamp_error.to_string()))?;
+    let scheduled_fn = ScheduledFn::new(
+        call_context.zome.zome_name().clone(),
+        fn_name,
+        next_run,
+        interval,
+    );
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .scheduled
+            .schedule(scheduled_fn)
+            .map_err(|database_error| WasmError::Host(database_error.to_string()))
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::workflow::CallZomeWorkspace;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::sync::Arc;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn schedule_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        let _: () = crate::call_test_ribosome!(host_access, TestWasm::Schedule, "schedule", ());
+
+        let workspace = workspace_lock.read().await;
+        let scheduled = workspace.scheduled.all().unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].zome_name, TestWasm::Schedule.into());
+        assert_eq!(scheduled[0].fn_name, "scheduled_fn".into());
+        assert_eq!(
+            scheduled[0].interval,
+            Some(core::time::Duration::from_millis(10))
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn schedule_zero_interval_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let ribosome =
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![TestWasm::Schedule]))
+                .next()
+                .unwrap();
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.zome = TestWasm::Schedule.into();
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+        call_context.host_access = host_access.into();
+
+        let input = Schedule::recurring(
+            "scheduled_fn".into(),
+            core::time::Duration::from_millis(0),
+            core::time::Duration::from_millis(0),
+        );
+        assert!(super::schedule(Arc::new(ribosome), Arc::new(call_context), input).is_err());
+
+        // Nothing was scheduled.
+        let workspace = workspace_lock.read().await;
+        assert!(workspace.scheduled.all().unwrap().is_empty());
+    }
 }
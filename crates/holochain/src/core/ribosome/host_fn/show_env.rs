@@ -1,12 +1,61 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holo_hash::HasHash;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
 
 pub fn show_env(
-    _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+) -> Result<EnvInfo, WasmError> {
+    let workspace = call_context.host_access.workspace().clone();
+    let agent_pubkey = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        let lock = workspace.read().await;
+        lock.source_chain.agent_pubkey()
+    })
+    .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))?;
+    let dna_def = ribosome.dna_def();
+    Ok(EnvInfo {
+        dna_hash: dna_def.as_hash().clone(),
+        zome_name: call_context.zome.zome_name().clone(),
+        agent_pubkey,
+        conductor_version: env!("CARGO_PKG_VERSION").to_string(),
+        network: NetworkInfo {
+            uuid: dna_def.uuid.clone(),
+            default_required_validations: dna_def.default_required_validations,
+        },
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::prelude::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_show_env_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let agent_pubkey = workspace.source_chain.agent_pubkey().unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let env_info: EnvInfo =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "show_env", ());
+        assert_eq!(env_info.zome_name, ZomeName::from(TestWasm::ZomeInfo));
+        assert_eq!(env_info.agent_pubkey, agent_pubkey);
+        assert_eq!(env_info.conductor_version, env!("CARGO_PKG_VERSION"));
+    }
 }
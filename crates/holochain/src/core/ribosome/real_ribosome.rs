@@ -8,8 +8,11 @@ use super::host_fn::get_agent_activity::get_agent_activity;
 use super::host_fn::HostFnApi;
 use super::HostAccess;
 use super::ZomeCallHostAccess;
+use crate::conductor::config::WasmCallLimits;
+use crate::conductor::config::WasmLimitsConfig;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::error::WasmLimitExceeded;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
@@ -38,10 +41,12 @@ use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
+use crate::core::ribosome::host_fn::entry_type_properties::entry_type_properties;
 use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
+use crate::core::ribosome::host_fn::get_links_page::get_links_page;
 use crate::core::ribosome::host_fn::hash_entry::hash_entry;
 use crate::core::ribosome::host_fn::property::property;
 use crate::core::ribosome::host_fn::query::query;
@@ -68,11 +73,54 @@ use fallible_iterator::FallibleIterator;
 use holochain_types::prelude::*;
 
 use holochain_wasmer_host::prelude::*;
+use once_cell::sync::Lazy;
+use parity_wasm::elements::MemoryType;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::Duration;
+use wasmer_middleware_common::metering::get_points_used;
+use wasmer_middleware_common::metering::set_points_used;
+use wasmer_middleware_common::metering::Metering;
+use wasmer_runtime_core::codegen::MiddlewareChain;
+use wasmer_runtime_core::codegen::StreamingCompiler;
+use wasmer_singlepass_backend::ModuleCodeGenerator as SinglePassMCG;
 
 /// Path to the wasm cache path
 const WASM_CACHE_PATH_ENV: &str = "HC_WASM_CACHE_PATH";
 
+/// The size in bytes of a page of wasm linear memory
+const WASM_PAGE_SIZE: u64 = 65_536;
+
+/// Modules compiled with memory and fuel limits, keyed by the wasm and its limits
+static LIMITED_MODULES: Lazy<Mutex<HashMap<(Vec<u8>, Option<u64>, Option<u32>), Module>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Set the maximum size of the wasm's memory so the wasm itself
+/// can't grow it past `max_pages`.
+/// Returns None if the wasm starts with more memory than that.
+fn cap_memory(wasm: &[u8], max_pages: u32) -> RibosomeResult<Option<Vec<u8>>> {
+    let mut module: parity_wasm::elements::Module =
+        parity_wasm::deserialize_buffer(wasm).map_err(|e| WasmError::Compile(e.to_string()))?;
+    if let Some(memory_section) = module.memory_section_mut() {
+        for memory in memory_section.entries_mut() {
+            let initial = memory.limits().initial();
+            if initial > max_pages {
+                return Ok(None);
+            }
+            let maximum = memory
+                .limits()
+                .maximum()
+                .map_or(max_pages, |maximum| maximum.min(max_pages));
+            *memory = MemoryType::new(initial, Some(maximum));
+        }
+    }
+    Ok(Some(
+        parity_wasm::serialize(module).map_err(|e| WasmError::Compile(e.to_string()))?,
+    ))
+}
+
 /// The only RealRibosome is a Wasm ribosome.
 /// note that this is cloned on every invocation so keep clones cheap!
 #[derive(Clone, Debug)]
@@ -82,12 +130,23 @@ pub struct RealRibosome {
     //      - is already in the wasm cache, and only include the DnaDef portion
     //      - here in the ribosome.
     pub dna_file: DnaFile,
+    /// Limits on the resources each call into the wasm can use
+    pub limits: WasmLimitsConfig,
 }
 
 impl RealRibosome {
     /// Create a new instance
     pub fn new(dna_file: DnaFile) -> Self {
-        Self { dna_file }
+        Self {
+            dna_file,
+            limits: WasmLimitsConfig::default(),
+        }
+    }
+
+    /// Limit the resources each call into the wasm can use
+    pub fn with_limits(mut self, limits: WasmLimitsConfig) -> Self {
+        self.limits = limits;
+        self
     }
 
     pub fn dna_file(&self) -> &DnaFile {
@@ -127,8 +186,155 @@ impl RealRibosome {
         )?)
     }
 
+    /// Compile the zome's wasm with the memory and fuel limits built in.
+    /// Limited modules are compiled by the metering singlepass backend
+    /// so they are cached separately from unlimited modules.
+    /// Returns None if the wasm starts with more memory than it is allowed.
+    fn limited_module(
+        &self,
+        zome_name: &ZomeName,
+        max_fuel: Option<u64>,
+        max_pages: Option<u32>,
+    ) -> RibosomeResult<Option<Module>> {
+        let key = (
+            self.wasm_cache_key(zome_name)?.to_vec(),
+            max_fuel,
+            max_pages,
+        );
+        if let Some(module) = LIMITED_MODULES.lock().get(&key) {
+            return Ok(Some(module.clone()));
+        }
+
+        let wasm: Arc<Box<[u8]>> = self.dna_file.get_wasm_for_zome(zome_name)?.code();
+        let wasm = match max_pages {
+            Some(max_pages) => match cap_memory(&wasm, max_pages)? {
+                Some(wasm) => wasm,
+                None => return Ok(None),
+            },
+            None => wasm.to_vec(),
+        };
+        let compiler: StreamingCompiler<SinglePassMCG, _, _, _, _> =
+            StreamingCompiler::new(move || {
+                let mut chain = MiddlewareChain::new();
+                if let Some(max_fuel) = max_fuel {
+                    chain.push(Metering::new(max_fuel));
+                }
+                chain
+            });
+        let module = wasmer_runtime_core::compile_with(&wasm, &compiler)
+            .map_err(|e| WasmError::Compile(e.to_string()))?;
+        LIMITED_MODULES.lock().insert(key, module.clone());
+        Ok(Some(module))
+    }
+
+    /// Call a function in a wasm zome within the limits for this kind of call.
+    fn call_wasm(
+        &self,
+        call_context: CallContext,
+        to_call: &FunctionName,
+        input: ExternIO,
+    ) -> RibosomeResult<ExternIO> {
+        let limits: WasmCallLimits = match call_context.host_access {
+            HostAccess::ZomeCall(_) => self.limits.zome_call.clone(),
+            _ => self.limits.callback.clone(),
+        };
+        let timeout = match limits.timeout_ms {
+            Some(timeout_ms) => Duration::from_millis(timeout_ms),
+            None => return self.call_wasm_metered(call_context, to_call, input, limits),
+        };
+
+        // Fuel doesn't run down while the call is blocked in a host function.
+        // A running call can't be interrupted so it gets a blocking thread
+        // which is left to finish on its own if the call times out.
+        let zome_name = call_context.zome.zome_name().clone();
+        let ribosome = self.clone();
+        let fn_name = to_call.clone();
+        let result = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+            tokio::time::timeout(
+                timeout,
+                tokio::task::spawn_blocking(move || {
+                    ribosome.call_wasm_metered(call_context, &fn_name, input, limits)
+                }),
+            )
+            .await
+        });
+        match result {
+            Ok(joined) => joined?,
+            Err(_) => Err(RibosomeError::WasmLimitExceeded(
+                zome_name,
+                to_call.clone(),
+                WasmLimitExceeded::Timeout(timeout),
+            )),
+        }
+    }
+
+    /// Call a function in a wasm zome within its fuel and memory limits.
+    fn call_wasm_metered(
+        &self,
+        call_context: CallContext,
+        to_call: &FunctionName,
+        input: ExternIO,
+        limits: WasmCallLimits,
+    ) -> RibosomeResult<ExternIO> {
+        if limits.max_fuel.is_none() && limits.max_memory_bytes.is_none() {
+            // it is important to fully instantiate this (e.g. don't try to use the module above)
+            // because it builds guards against memory leaks and handles imports correctly
+            let mut instance = self.instance(call_context)?;
+            let result: Result<ExternIO, WasmError> =
+                holochain_wasmer_host::guest::call(&mut instance, to_call.as_ref(), input);
+            return Ok(result?);
+        }
+
+        let zome_name = call_context.zome.zome_name().clone();
+        let limit_exceeded =
+            |limit| RibosomeError::WasmLimitExceeded(zome_name.clone(), to_call.clone(), limit);
+        let max_pages = limits
+            .max_memory_bytes
+            .map(|max| u32::try_from(max / WASM_PAGE_SIZE).unwrap_or(u32::MAX));
+
+        let module = match self.limited_module(&zome_name, limits.max_fuel, max_pages)? {
+            Some(module) => module,
+            None => {
+                return Err(limit_exceeded(WasmLimitExceeded::Memory {
+                    max: limits.max_memory_bytes.unwrap_or_default(),
+                }))
+            }
+        };
+        let imports: ImportObject = Self::imports(self, call_context);
+        let mut instance = module
+            .instantiate(&imports)
+            .map_err(|e| WasmError::Compile(e.to_string()))?;
+        if limits.max_fuel.is_some() {
+            set_points_used(&mut instance, 0);
+        }
+
+        let result: Result<ExternIO, WasmError> =
+            holochain_wasmer_host::guest::call(&mut instance, to_call.as_ref(), input);
+        match result {
+            Ok(output) => Ok(output),
+            Err(error) => {
+                // The metering traps once the call has used all its fuel
+                if let Some(max_fuel) = limits.max_fuel {
+                    if get_points_used(&instance) >= max_fuel {
+                        return Err(limit_exceeded(WasmLimitExceeded::Fuel(max_fuel)));
+                    }
+                }
+                // The guest fails when it can't grow its memory any further
+                if let (Some(max), Some(max_pages)) = (limits.max_memory_bytes, max_pages) {
+                    if instance.context().memory(0).size().0 >= max_pages {
+                        return Err(limit_exceeded(WasmLimitExceeded::Memory { max }));
+                    }
+                }
+                Err(error.into())
+            }
+        }
+    }
+
     fn imports(&self, call_context: CallContext) -> ImportObject {
-        let host_fn_access = (&call_context.host_access()).into();
+        // A zome only gets the host functions that both the call context
+        // allows and the zome has declared that it needs
+        let host_fn_access = HostFnAccess::from(&call_context.host_access())
+            .intersection(call_context.zome.zome_def().host_fn_access());
 
         // it is important that RealRibosome and ZomeCallInvocation are cheap to clone here
         let self_arc = std::sync::Arc::new((*self).clone());
@@ -238,9 +444,17 @@ impl RealRibosome {
         {
             ns.insert("__zome_info", func!(invoke_host_function!(zome_info)));
             ns.insert("__property", func!(invoke_host_function!(property)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(entry_type_properties)),
+            );
         } else {
             ns.insert("__zome_info", func!(invoke_host_function!(unreachable)));
             ns.insert("__property", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(unreachable)),
+            );
         }
 
         if let HostFnAccess {
@@ -299,6 +513,10 @@ impl RealRibosome {
             ns.insert("__get", func!(invoke_host_function!(get)));
             ns.insert("__get_details", func!(invoke_host_function!(get_details)));
             ns.insert("__get_links", func!(invoke_host_function!(get_links)));
+            ns.insert(
+                "__get_links_page",
+                func!(invoke_host_function!(get_links_page)),
+            );
             ns.insert(
                 "__get_link_details",
                 func!(invoke_host_function!(get_link_details)),
@@ -312,6 +530,10 @@ impl RealRibosome {
             ns.insert("__get", func!(invoke_host_function!(unreachable)));
             ns.insert("__get_details", func!(invoke_host_function!(unreachable)));
             ns.insert("__get_links", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__get_links_page",
+                func!(invoke_host_function!(unreachable)),
+            );
             ns.insert(
                 "__get_link_details",
                 func!(invoke_host_function!(unreachable)),
@@ -415,20 +637,11 @@ impl RibosomeT for RealRibosome {
 
                 if module.info().exports.contains_key(to_call.as_ref()) {
                     // there is a callback to_call and it is implemented in the wasm
-                    // it is important to fully instantiate this (e.g. don't try to use the module above)
-                    // because it builds guards against memory leaks and handles imports correctly
-                    let mut instance = self.instance(call_context)?;
-
-                    let result: Result<ExternIO, WasmError> = holochain_wasmer_host::guest::call(
-                        &mut instance,
-                        to_call.as_ref(),
-                        // be aware of this clone!
-                        // the whole invocation is cloned!
-                        // @todo - is this a problem for large payloads like entries?
-                        invocation.to_owned().host_input()?,
-                    );
-
-                    Ok(Some(result?))
+                    // be aware of this clone!
+                    // the whole invocation is cloned!
+                    // @todo - is this a problem for large payloads like entries?
+                    let input = invocation.to_owned().host_input()?;
+                    Ok(Some(self.call_wasm(call_context, to_call, input)?))
                 } else {
                     // the func doesn't exist
                     // the callback is not implemented
@@ -459,25 +672,32 @@ impl RibosomeT for RealRibosome {
         host_access: ZomeCallHostAccess,
         invocation: ZomeCallInvocation,
     ) -> RibosomeResult<ZomeCallResponse> {
-        Ok(if invocation.is_authorized(&host_access)? {
-            // make a copy of these for the error handling below
-            let zome_name = invocation.zome.zome_name().clone();
-            let fn_name = invocation.fn_name.clone();
-
-            let guest_output: ExternIO =
-                match self.call_iterator(host_access.into(), invocation).next()? {
-                    Some(result) => result.1,
-                    None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+        Ok(match invocation.authorizing_grant(&host_access)? {
+            Some(grant) => {
+                // make a copy of these for the error handling below
+                let zome_name = invocation.zome.zome_name().clone();
+                let fn_name = invocation.fn_name.clone();
+
+                // let the zome function know how it was authorized
+                let host_access = ZomeCallHostAccess {
+                    cap_info: Some(CapInfo::new(grant, invocation.provenance.clone())),
+                    ..host_access
                 };
 
-            ZomeCallResponse::Ok(guest_output)
-        } else {
-            ZomeCallResponse::Unauthorized(
+                let guest_output: ExternIO =
+                    match self.call_iterator(host_access.into(), invocation).next()? {
+                        Some(result) => result.1,
+                        None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+                    };
+
+                ZomeCallResponse::Ok(guest_output)
+            }
+            None => ZomeCallResponse::Unauthorized(
                 invocation.cell_id.clone(),
                 invocation.zome.zome_name().clone(),
                 invocation.fn_name.clone(),
                 invocation.provenance.clone(),
-            )
+            ),
         })
     }
 
@@ -572,4 +792,141 @@ pub mod wasm_test {
 
         assert_eq!("foobar", bar_result.as_str());
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn wasm_limits_test() {
+        use crate::conductor::api::CellConductorApi;
+        use crate::conductor::config::WasmLimitsConfig;
+        use crate::conductor::handle::MockConductorHandleT;
+        use crate::core::ribosome::error::RibosomeError;
+        use crate::core::ribosome::error::WasmLimitExceeded;
+        use crate::core::ribosome::RibosomeT;
+        use crate::core::ribosome::ZomeCallHostAccess;
+        use crate::fixt::curve::Zomes;
+        use crate::fixt::AgentPubKeyFixturator;
+        use crate::fixt::NamedInvocation;
+        use crate::fixt::RealRibosomeFixturator;
+        use crate::fixt::ZomeCallInvocationFixturator;
+        use matches::assert_matches;
+        use std::sync::Arc;
+
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess, Predictable);
+        host_access.workspace = workspace_lock;
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::HdkExtern.into()]))
+            .next()
+            .unwrap();
+        let cell_id = CellId::new(
+            ribosome.dna_def().as_hash().clone(),
+            fixt!(AgentPubKey, Predictable),
+        );
+        let call_with =
+            |host_access: ZomeCallHostAccess, limits: WasmLimitsConfig, fn_name: &str| {
+                let invocation = ZomeCallInvocationFixturator::new(NamedInvocation(
+                    cell_id.clone(),
+                    TestWasm::HdkExtern.into(),
+                    fn_name.into(),
+                    ExternIO::encode(()).unwrap(),
+                ))
+                .next()
+                .unwrap();
+                ribosome
+                    .clone()
+                    .with_limits(limits)
+                    .call_zome_function(host_access, invocation)
+            };
+        let call = |limits: WasmLimitsConfig, fn_name: &str| {
+            call_with(host_access.clone(), limits, fn_name)
+        };
+
+        // A call that never returns is stopped when it runs out of fuel
+        let mut limits = WasmLimitsConfig::default();
+        limits.zome_call.max_fuel = Some(10_000_000);
+        assert_matches!(
+            call(limits.clone(), "infinite_loop"),
+            Err(RibosomeError::WasmLimitExceeded(
+                _,
+                _,
+                WasmLimitExceeded::Fuel(10_000_000)
+            ))
+        );
+        // and the same fuel is plenty for a call that does return
+        assert_matches!(call(limits, "foo"), Ok(ZomeCallResponse::Ok(_)));
+
+        // A wasm can't grow its memory past the limit
+        let mut limits = WasmLimitsConfig::default();
+        limits.zome_call.max_memory_bytes = Some(32 * 1024 * 1024);
+        assert_matches!(
+            call(limits.clone(), "grow_memory"),
+            Err(RibosomeError::WasmLimitExceeded(
+                _,
+                _,
+                WasmLimitExceeded::Memory { .. }
+            ))
+        );
+        assert_matches!(call(limits, "foo"), Ok(ZomeCallResponse::Ok(_)));
+
+        // Every wasm has at least one page of memory
+        let mut limits = WasmLimitsConfig::default();
+        limits.zome_call.max_memory_bytes = Some(1);
+        assert_matches!(
+            call(limits, "foo"),
+            Err(RibosomeError::WasmLimitExceeded(
+                _,
+                _,
+                WasmLimitExceeded::Memory { max: 1 }
+            ))
+        );
+
+        // The call returns in plenty of time
+        let mut limits = WasmLimitsConfig::default();
+        limits.zome_call.timeout_ms = Some(60_000);
+        assert_matches!(call(limits, "foo"), Ok(ZomeCallResponse::Ok(_)));
+
+        // A call that is blocked in a host function uses no fuel,
+        // so only the timeout stops it
+        let (release, blocked) = std::sync::mpsc::channel::<()>();
+        let blocked = parking_lot::Mutex::new(blocked);
+        let mut conductor_handle = MockConductorHandleT::new();
+        conductor_handle
+            .expect_call_zome_with_workspace()
+            .returning(move |_, _| {
+                blocked.lock().recv().ok();
+                Ok(Ok(ZomeCallResponse::Ok(ExternIO::encode(()).unwrap())))
+            });
+        let mut blocked_host_access = host_access.clone();
+        blocked_host_access.call_zome_handle = Arc::new(CellConductorApi::new(
+            Arc::new(conductor_handle),
+            cell_id.clone(),
+        ));
+        let mut limits = WasmLimitsConfig::default();
+        limits.zome_call.max_fuel = Some(10_000_000);
+        limits.zome_call.timeout_ms = Some(100);
+        assert_matches!(
+            call_with(blocked_host_access, limits, "call_foo"),
+            Err(RibosomeError::WasmLimitExceeded(
+                _,
+                _,
+                WasmLimitExceeded::Timeout(_)
+            ))
+        );
+        // let the abandoned call finish
+        release.send(()).unwrap();
+
+        // Callback limits don't apply to zome calls
+        let mut limits = WasmLimitsConfig::default();
+        limits.callback.max_memory_bytes = Some(1);
+        limits.callback.max_fuel = Some(1);
+        limits.callback.timeout_ms = Some(0);
+        assert_matches!(call(limits, "foo"), Ok(ZomeCallResponse::Ok(_)));
+    }
 }
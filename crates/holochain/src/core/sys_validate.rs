@@ -456,7 +456,7 @@ impl IncomingDhtOpSender {
     ) -> SysValidationResult<()> {
         if let Some(op) = make_op(element) {
             let ops = vec![op];
-            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None)
+            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None, false)
                 .await
                 .map_err(Box::new)?;
         }
@@ -277,6 +277,8 @@ async fn check_app_entry_type_test() {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::EntryDefs.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::EntryDefs.into()],
     )
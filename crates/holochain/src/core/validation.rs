@@ -3,12 +3,18 @@ use std::convert::TryFrom;
 
 use derivative::Derivative;
 use holo_hash::DhtOpHash;
+use holochain_p2p::HolochainP2pCell;
+use holochain_p2p::HolochainP2pCellT;
+use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::dht_op::DhtOp;
+use holochain_types::validation_receipt::ValidationReceipt;
+use holochain_types::validation_receipt::ValidationResult;
 
 use super::workflow::error::WorkflowResult;
 use super::SourceChainError;
 use super::SysValidationError;
 use super::ValidationOutcome;
+use crate::conductor::api::CellConductorApiT;
 
 /// Exit early with either an outcome or an error
 pub enum OutcomeOrError<T, E> {
@@ -83,3 +89,32 @@ impl OutcomeOrError<ValidationOutcome, SysValidationError> {
         Err(SourceChainError::InvalidCommit(ValidationOutcome::try_from(self)?.to_string()).into())
     }
 }
+
+/// Sign a validation receipt for this op and send it back to the author.
+/// Failing to send is only logged because the author will
+/// keep publishing the op until it has enough receipts.
+pub async fn send_validation_receipt(
+    dht_op_hash: DhtOpHash,
+    op: &DhtOp,
+    validation_result: ValidationResult,
+    conductor_api: &impl CellConductorApiT,
+    mut network: HolochainP2pCell,
+) {
+    let author = op.header().author().clone();
+    let send = async move {
+        let receipt = ValidationReceipt {
+            dht_op_hash,
+            validation_result,
+            validator: conductor_api.cell_id().agent_pubkey().clone(),
+        }
+        .sign(conductor_api.keystore())
+        .await?;
+        network
+            .send_validation_receipt(author, SerializedBytes::try_from(receipt)?)
+            .await?;
+        WorkflowResult::Ok(())
+    };
+    if let Err(e) = send.await {
+        tracing::warn!(msg = "Failed to send validation receipt", ?e);
+    }
+}
@@ -29,8 +29,10 @@ pub mod genesis_workflow;
 pub mod incoming_dht_ops_workflow;
 pub mod initialize_zomes_workflow;
 pub mod integrate_dht_ops_workflow;
+pub mod migrate_agent_workflow;
 pub mod produce_dht_ops_workflow;
 pub mod publish_dht_ops_workflow;
+pub mod schedule_workflow;
 pub mod sys_validation_workflow;
 
 // TODO: either remove wildcards or add wildcards for all above child modules
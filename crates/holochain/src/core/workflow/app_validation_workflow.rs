@@ -33,6 +33,7 @@ use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::Invocation;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomesToInvoke;
+use crate::core::validation::send_validation_receipt;
 use crate::core::validation::DhtOpOrder;
 use crate::core::validation::OrderedOp;
 use error::AppValidationResult;
@@ -69,6 +70,10 @@ mod error;
 mod types;
 pub mod validation_package;
 
+/// The number of times an op can fail to find its dependencies
+/// during app validation before it is abandoned.
+pub const MAX_APP_VALIDATION_TRIES: u32 = 100;
+
 #[instrument(skip(workspace, writer, trigger_integration, conductor_api, network))]
 pub async fn app_validation_workflow(
     mut workspace: AppValidationWorkspace,
@@ -155,26 +160,40 @@ async fn app_validation_workflow_inner(
                 // Get the outcome or return the error
                 .or_else(|outcome_or_err| outcome_or_err.try_into())?;
 
-                match outcome {
-                    Outcome::Accepted => {
-                        let iv = IntegrationLimboValue {
-                            validation_status: ValidationStatus::Valid,
-                            op: vlv.op,
-                        };
-                        workspace.put_int_limbo(hash, iv, op)?;
+                let validation_status = match outcome {
+                    Outcome::Accepted => ValidationStatus::Valid,
+                    Outcome::AwaitingDeps(deps) if vlv.num_tries >= MAX_APP_VALIDATION_TRIES => {
+                        // Give up on this op but remember what it was
+                        // missing so it can be retried if they turn up
+                        debug!(?hash, ?deps, "abandoning op");
+                        workspace
+                            .meta_vault
+                            .register_abandoned_op(hash.clone(), deps)?;
+                        ValidationStatus::Abandoned
                     }
                     Outcome::AwaitingDeps(deps) => {
                         vlv.status = ValidationLimboStatus::AwaitingAppDeps(deps);
                         workspace.put_val_limbo(hash, vlv)?;
+                        continue;
                     }
-                    Outcome::Rejected(_) => {
-                        let iv = IntegrationLimboValue {
-                            op: vlv.op,
-                            validation_status: ValidationStatus::Rejected,
-                        };
-                        workspace.put_int_limbo(hash, iv, op)?;
-                    }
+                    Outcome::Rejected(_) => ValidationStatus::Rejected,
+                };
+                // Let the author know the outcome
+                if vlv.send_receipt {
+                    send_validation_receipt(
+                        hash.clone(),
+                        &op,
+                        validation_status.into(),
+                        &conductor_api,
+                        network.clone(),
+                    )
+                    .await;
                 }
+                let iv = IntegrationLimboValue {
+                    validation_status,
+                    op: vlv.op,
+                };
+                workspace.put_int_limbo(hash, iv, op)?;
             }
             _ => unreachable!("Should not contain any other status"),
         }
@@ -217,7 +236,7 @@ async fn validate_op(
     };
 
     // Create the ribosome
-    let ribosome = RealRibosome::new(dna_file);
+    let ribosome = RealRibosome::new(dna_file).with_limits(conductor_api.wasm_limits().clone());
 
     // Get the validation package
     let validation_package = get_validation_package(
@@ -1034,6 +1053,8 @@ impl Workspace for AppValidationWorkspace {
         self.integration_limbo.flush_to_txn_ref(writer)?;
         self.element_pending.flush_to_txn_ref(writer)?;
         self.meta_pending.flush_to_txn_ref(writer)?;
+        // Only abandoned ops are registered in the vault here
+        self.meta_vault.flush_to_txn_ref(writer)?;
 
         // Flush for cascade
         self.element_cache.flush_to_txn_ref(writer)?;
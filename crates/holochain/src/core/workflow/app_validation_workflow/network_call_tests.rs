@@ -230,6 +230,7 @@ async fn get_agent_activity_test() {
             status,
             highest_observed,
             agent: alice_agent_id.clone(),
+            warrants: Vec::new(),
         }
     };
 
@@ -272,6 +273,7 @@ async fn get_agent_activity_test() {
             rejected_activity,
             status: activity.status,
             highest_observed: activity.highest_observed,
+            warrants: activity.warrants,
         };
         activity
     };
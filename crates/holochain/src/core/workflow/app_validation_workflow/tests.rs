@@ -42,6 +42,8 @@ async fn app_validation_workflow_test() {
                 TestWasm::Create.into(),
             ]
             .into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![
             TestWasm::Validate.into(),
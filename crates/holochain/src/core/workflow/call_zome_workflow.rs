@@ -7,6 +7,9 @@ use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitResult;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
@@ -21,6 +24,7 @@ use holochain_p2p::HolochainP2pCell;
 use holochain_state::element_buf::ElementBuf;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
+use holochain_state::schedule_db::ScheduleStore;
 use holochain_state::source_chain::SourceChain;
 use holochain_state::source_chain::SourceChainError;
 use holochain_state::workspace::Workspace;
@@ -54,7 +58,8 @@ pub struct CallZomeWorkflowArgs<Ribosome: RibosomeT + Send, C: CellConductorApiT
     keystore,
     writer,
     args,
-    trigger_produce_dht_ops
+    trigger_produce_dht_ops,
+    trigger_schedule
 ))]
 pub async fn call_zome_workflow<
     'env,
@@ -67,9 +72,17 @@ pub async fn call_zome_workflow<
     writer: OneshotWriter,
     args: CallZomeWorkflowArgs<Ribosome, C>,
     mut trigger_produce_dht_ops: TriggerSender,
+    mut trigger_schedule: TriggerSender,
 ) -> WorkflowResult<ZomeCallResult> {
     let should_write = args.is_root_zome_call;
-    let result = call_zome_workflow_inner(workspace_lock.clone(), network, keystore, args).await?;
+    let zome = args.invocation.zome.clone();
+    let (ribosome, result, committed) = call_zome_workflow_inner(
+        workspace_lock.clone(),
+        network.clone(),
+        keystore.clone(),
+        args,
+    )
+    .await?;
 
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
@@ -81,10 +94,46 @@ pub async fn call_zome_workflow<
     }
 
     trigger_produce_dht_ops.trigger();
+    trigger_schedule.trigger();
+
+    // Now the headers are durably on the chain let the zome know about them.
+    // Only the root call writes, so it also runs post_commit for
+    // anything committed by nested calls.
+    if should_write && !committed.is_empty() {
+        if let Ok(ZomeCallResponse::Ok(_)) = &result {
+            let access = PostCommitHostAccess::new(workspace_lock, keystore, network);
+            let invocation = PostCommitInvocation::new(zome, committed.into());
+            run_post_commit(ribosome, access, invocation).await?;
+        }
+    }
 
     Ok(result)
 }
 
+/// Run the post_commit callback for headers that have already been flushed.
+/// The commit has happened so a failing callback can't undo it,
+/// it is only logged.
+/// The callback isn't allowed to write to the workspace, as nothing
+/// is flushed after it runs.
+async fn run_post_commit<Ribosome: RibosomeT + Send + 'static>(
+    ribosome: Ribosome,
+    access: PostCommitHostAccess,
+    invocation: PostCommitInvocation,
+) -> WorkflowResult<()> {
+    let result =
+        tokio::task::spawn_blocking(move || ribosome.run_post_commit(access, invocation)).await?;
+    match result {
+        Ok(PostCommitResult::Success) => {}
+        Ok(PostCommitResult::Fail(headers, reason)) => {
+            tracing::warn!(?headers, %reason, "post_commit callback failed");
+        }
+        Err(e) => {
+            tracing::error!(?e, "post_commit callback returned an error");
+        }
+    }
+    Ok(())
+}
+
 async fn call_zome_workflow_inner<
     'env,
     Ribosome: RibosomeT + Send + 'static,
@@ -94,7 +143,7 @@ async fn call_zome_workflow_inner<
     network: HolochainP2pCell,
     keystore: KeystoreSender,
     args: CallZomeWorkflowArgs<Ribosome, C>,
-) -> WorkflowResult<ZomeCallResult> {
+) -> WorkflowResult<(Ribosome, ZomeCallResult, Vec<HeaderHash>)> {
     let CallZomeWorkflowArgs {
         ribosome,
         invocation,
@@ -157,6 +206,11 @@ async fn call_zome_workflow_inner<
         to_app_validate
     };
 
+    let committed = to_app_validate
+        .iter()
+        .map(|element| element.header_address().clone())
+        .collect();
+
     {
         for chain_element in to_app_validate {
             let outcome = match chain_element.header() {
@@ -257,7 +311,7 @@ async fn call_zome_workflow_inner<
         }
     }
 
-    Ok(result)
+    Ok((ribosome, result, committed))
 }
 
 pub struct CallZomeWorkspace {
@@ -269,6 +323,7 @@ pub struct CallZomeWorkspace {
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    pub scheduled: ScheduleStore,
 }
 
 impl<'a> CallZomeWorkspace {
@@ -280,7 +335,8 @@ impl<'a> CallZomeWorkspace {
         let element_rejected = ElementBuf::rejected(env.clone())?;
         let meta_rejected = MetadataBuf::rejected(env.clone())?;
         let element_cache = ElementBuf::cache(env.clone())?;
-        let meta_cache = MetadataBuf::cache(env)?;
+        let meta_cache = MetadataBuf::cache(env.clone())?;
+        let scheduled = ScheduleStore::new(env)?;
 
         Ok(CallZomeWorkspace {
             source_chain,
@@ -291,6 +347,7 @@ impl<'a> CallZomeWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            scheduled,
         })
     }
 
@@ -331,6 +388,7 @@ impl Workspace for CallZomeWorkspace {
         self.meta_authored.flush_to_txn_ref(writer)?;
         self.element_cache.flush_to_txn_ref(writer)?;
         self.meta_cache.flush_to_txn_ref(writer)?;
+        self.scheduled.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -340,6 +398,7 @@ pub mod tests {
     use super::*;
     use crate::conductor::api::CellConductorApi;
     use crate::conductor::handle::MockConductorHandleT;
+    use crate::core::ribosome::Invocation;
     use crate::core::ribosome::MockRibosomeT;
     use crate::core::workflow::error::WorkflowError;
     use crate::core::workflow::genesis_workflow::tests::fake_genesis;
@@ -353,6 +412,7 @@ pub mod tests {
     use holochain_wasm_test_utils::TestWasm;
     use holochain_zome_types::cell::CellId;
     use holochain_zome_types::entry::Entry;
+    use holochain_zome_types::header::builder;
     use holochain_zome_types::ExternIO;
     use matches::assert_matches;
     use observability;
@@ -379,7 +439,9 @@ pub mod tests {
             conductor_api,
             is_root_zome_call: true,
         };
-        call_zome_workflow_inner(workspace.into(), network, keystore, args).await
+        call_zome_workflow_inner(workspace.into(), network, keystore, args)
+            .await
+            .map(|(_, result, _)| result)
     }
 
     // 1.  Check if there is a Capability token secret in the parameters.
@@ -553,4 +615,135 @@ pub mod tests {
             .unwrap();
         // TODO: Check the workspace has changes
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn post_commit_skipped_without_commits() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        let mut ribosome = MockRibosomeT::new();
+        let dna_def = fixt!(DnaFile).dna().clone();
+        ribosome.expect_dna_def().return_const(dna_def);
+        ribosome
+            .expect_call_zome_function()
+            .returning(|_, _| Ok(ZomeCallResponse::Ok(ExternIO::encode(()).unwrap())));
+        // Nothing was committed so there's nothing to tell the zome about
+        ribosome.expect_run_post_commit().never();
+        let invocation =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                holochain_types::fixt::CellIdFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap(),
+                TestWasm::Foo.into(),
+                "fun_times".into(),
+                ExternIO::encode(Payload { a: 1 }).unwrap(),
+            ))
+            .next()
+            .unwrap();
+        let cell_id = CellId::new(ribosome.dna_def().as_hash().clone(), fixt!(AgentPubKey));
+        let conductor_api = Arc::new(MockConductorHandleT::new());
+        let conductor_api = CellConductorApi::new(conductor_api, cell_id);
+        let args = CallZomeWorkflowArgs {
+            invocation,
+            ribosome,
+            signal_tx: SignalBroadcaster::noop(),
+            conductor_api,
+            is_root_zome_call: true,
+        };
+        let (trigger_produce_dht_ops, _rx) = TriggerSender::new();
+        let (trigger_schedule, _rx) = TriggerSender::new();
+        let result = call_zome_workflow(
+            workspace.into(),
+            fixt!(HolochainP2pCell),
+            fixt!(KeystoreSender),
+            env.clone().into(),
+            args,
+            trigger_produce_dht_ops,
+            trigger_schedule,
+        )
+        .await
+        .unwrap();
+        assert_matches!(result, Ok(ZomeCallResponse::Ok(_)));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn post_commit_receives_committed_headers() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        fake_genesis(&mut workspace.source_chain).await.unwrap();
+        let mut ribosome = MockRibosomeT::new();
+        let dna_def = fixt!(DnaFile).dna().clone();
+        ribosome.expect_dna_def().return_const(dna_def);
+        // The zome call commits a header
+        ribosome
+            .expect_call_zome_function()
+            .returning(|host_access, _| {
+                tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+                    let mut workspace = host_access.workspace.write().await;
+                    workspace
+                        .source_chain
+                        .put(builder::InitZomesComplete {}, None)
+                        .await
+                })
+                .unwrap();
+                Ok(ZomeCallResponse::Ok(ExternIO::encode(()).unwrap()))
+            });
+        let post_commits = Arc::new(std::sync::Mutex::new(Vec::new()));
+        ribosome.expect_run_post_commit().times(1).returning({
+            let post_commits = post_commits.clone();
+            move |_, invocation| {
+                post_commits.lock().unwrap().push(invocation);
+                Ok(PostCommitResult::Success)
+            }
+        });
+        let invocation =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                holochain_types::fixt::CellIdFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap(),
+                TestWasm::Foo.into(),
+                "fun_times".into(),
+                ExternIO::encode(Payload { a: 1 }).unwrap(),
+            ))
+            .next()
+            .unwrap();
+        let cell_id = CellId::new(ribosome.dna_def().as_hash().clone(), fixt!(AgentPubKey));
+        let conductor_api = Arc::new(MockConductorHandleT::new());
+        let conductor_api = CellConductorApi::new(conductor_api, cell_id);
+        let args = CallZomeWorkflowArgs {
+            invocation,
+            ribosome,
+            signal_tx: SignalBroadcaster::noop(),
+            conductor_api,
+            is_root_zome_call: true,
+        };
+        let (trigger_produce_dht_ops, _rx) = TriggerSender::new();
+        let (trigger_schedule, _rx) = TriggerSender::new();
+        let result = call_zome_workflow(
+            workspace.into(),
+            fixt!(HolochainP2pCell),
+            fixt!(KeystoreSender),
+            env.clone().into(),
+            args,
+            trigger_produce_dht_ops,
+            trigger_schedule,
+        )
+        .await
+        .unwrap();
+        assert_matches!(result, Ok(ZomeCallResponse::Ok(_)));
+
+        // post_commit ran after the header was written, and was given its hash
+        let chain_head = SourceChain::new(env.clone().into())
+            .unwrap()
+            .chain_head()
+            .unwrap()
+            .clone();
+        let post_commits = post_commits.lock().unwrap();
+        assert_eq!(post_commits.len(), 1);
+        assert_eq!(
+            post_commits[0].clone().host_input().unwrap(),
+            ExternIO::encode(HeaderHashes::from(vec![chain_head])).unwrap()
+        );
+    }
 }
@@ -21,6 +21,8 @@ async fn direct_validation_test() {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::Update.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::Update.into()],
     )
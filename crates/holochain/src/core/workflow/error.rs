@@ -4,11 +4,13 @@
 use super::app_validation_workflow::AppValidationError;
 use super::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
 use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::conductor::CellError;
 use crate::core::queue_consumer::QueueTriggerClosedError;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::SysValidationError;
 use holochain_cascade::error::CascadeError;
+use holochain_keystore::KeystoreError;
 use holochain_lmdb::error::DatabaseError;
 use holochain_p2p::HolochainP2pError;
 use holochain_state::source_chain::SourceChainError;
@@ -68,6 +70,12 @@ pub enum WorkflowError {
 
     #[error(transparent)]
     SysValidationError(#[from] SysValidationError),
+
+    #[error(transparent)]
+    KeystoreError(#[from] KeystoreError),
+
+    #[error(transparent)]
+    EntryDefStoreError(#[from] EntryDefStoreError),
 }
 
 /// Internal type to handle running workflows
@@ -8,6 +8,9 @@ use crate::core::validation::DhtOpOrder;
 use crate::core::validation::OrderedOp;
 use error::WorkflowResult;
 use fallible_iterator::FallibleIterator;
+use holo_hash::hash_type::AnyDht;
+use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
 use holo_hash::DhtOpHash;
 use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
@@ -31,6 +34,8 @@ use holochain_zome_types::ValidationStatus;
 use produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertResult;
 use produce_dht_ops_workflow::dht_op_light::light_to_op;
 use std::collections::BinaryHeap;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::convert::TryInto;
 use tracing::*;
 
@@ -49,6 +54,23 @@ pub async fn integrate_dht_ops_workflow(
 ) -> WorkflowResult<WorkComplete> {
     // one of many possible ways to access the env
     let env = workspace.elements.headers().env().clone();
+
+    // Links integrated before links were indexed by time can't be paged
+    workspace.meta.backfill_link_times()?;
+    // Updates integrated before their redirects were indexed can't be followed
+    let elements = &workspace.elements;
+    workspace.meta.backfill_update_redirects(|| {
+        fresh_reader!(env, |r| elements
+            .headers()
+            .inner()
+            .iter_fail(&r)?
+            .filter_map(|header| Ok(match header.into_content() {
+                SignedHeader(Header::Update(update), _) => Some(update),
+                _ => None,
+            }))
+            .collect())
+    })?;
+
     // Pull ops out of queue
     // TODO: PERF: Combine this collect with the sort when ElementBuf gets
     // aren't async
@@ -116,6 +138,11 @@ pub async fn integrate_dht_ops_workflow(
         }
     }
 
+    // New data may be what some abandoned ops were missing
+    if total_integrated > 0 {
+        retry_abandoned_ops(&mut workspace)?;
+    }
+
     let result = if sorted_ops.is_empty() {
         // There were no ops deferred, meaning we exhausted the queue
         WorkComplete::Complete
@@ -156,25 +183,47 @@ async fn integrate_single_dht_op(
     workspace: &mut IntegrateDhtOpsWorkspace,
 ) -> WorkflowResult<Outcome> {
     if op_dependencies_held(&op, workspace).await? {
+        let activity_author = match &op {
+            DhtOp::RegisterAgentActivity(_, h) => Some(h.author().clone()),
+            _ => None,
+        };
         match iv.validation_status {
-            ValidationStatus::Valid => Ok(integrate_data_and_meta(
-                iv,
-                op,
-                &mut workspace.elements,
-                &mut workspace.meta,
-            )?),
+            ValidationStatus::Valid => {
+                let outcome =
+                    integrate_data_and_meta(iv, op, &mut workspace.elements, &mut workspace.meta)?;
+                if let Some(author) = activity_author {
+                    warrant_chain_fork(&author, workspace)?;
+                }
+                Ok(outcome)
+            }
             ValidationStatus::Rejected => {
                 update_activity_status(&op, &mut workspace.meta)?;
                 update_validation_status(&op, &mut workspace.meta)?;
-                Ok(integrate_data(iv, op, &mut workspace.element_rejected)?)
+                warrant_invalid_header(&op, &mut workspace.meta)?;
+                let outcome = integrate_data(iv, op, &mut workspace.element_rejected)?;
+                if let Some(author) = activity_author {
+                    warrant_chain_fork(&author, workspace)?;
+                }
+                Ok(outcome)
             }
             ValidationStatus::Abandoned => {
-                // Throwing away abandoned ops
-                // TODO: keep abandoned ops but remove the entries
-                // and put them in a AbandonedPrefix db
+                // Keep the header of abandoned ops but drop the entry
+                match &op {
+                    DhtOp::StoreElement(_, h, _) => {
+                        workspace.meta.register_abandoned_element_header(h)?
+                    }
+                    DhtOp::StoreEntry(_, h, _) => {
+                        workspace.meta.register_abandoned_header(h.clone())?
+                    }
+                    _ => {}
+                }
+                let (signature, header, _) = op.into_inner();
+                let when_authored = header.timestamp();
+                put_data(signature, header, None, &mut workspace.element_abandoned)?;
                 let integrated = IntegratedDhtOpsValue {
                     validation_status: iv.validation_status,
                     op: iv.op,
+                    when_authored,
                     when_integrated: timestamp::now(),
                 };
                 Ok(Outcome::Integrated(integrated))
@@ -192,11 +241,13 @@ fn integrate_data_and_meta<P: PrefixType>(
     element_store: &mut ElementBuf<P>,
     meta_store: &mut MetadataBuf<P>,
 ) -> DhtOpConvertResult<Outcome> {
+    let when_authored = op.header().timestamp();
     integrate_single_data(op, element_store)?;
     integrate_single_metadata(iv.op.clone(), element_store, meta_store)?;
     let integrated = IntegratedDhtOpsValue {
         validation_status: iv.validation_status,
         op: iv.op,
+        when_authored,
         when_integrated: timestamp::now(),
     };
     debug!("integrating");
@@ -209,10 +260,12 @@ fn integrate_data<P: PrefixType>(
     op: DhtOp,
     element_store: &mut ElementBuf<P>,
 ) -> DhtOpConvertResult<Outcome> {
+    let when_authored = op.header().timestamp();
     integrate_single_data(op, element_store)?;
     let integrated = IntegratedDhtOpsValue {
         validation_status: iv.validation_status,
         op: iv.op,
+        when_authored,
         when_integrated: timestamp::now(),
     };
     debug!("integrating");
@@ -236,6 +289,116 @@ fn update_activity_status(
     Ok(())
 }
 
+/// The header of any rejected op is kept as a warrant against its author.
+fn warrant_invalid_header(
+    op: &DhtOp,
+    meta_integrated: &mut impl MetadataBufT,
+) -> WorkflowResult<()> {
+    let warrant = Warrant::InvalidHeader(SignedHeader(op.header(), op.signature().clone()));
+    meta_integrated.register_warrant(warrant)?;
+    Ok(())
+}
+
+/// If the author's chain is now forked then store a warrant
+/// with both of the forking headers as evidence.
+fn warrant_chain_fork(
+    author: &AgentPubKey,
+    workspace: &mut IntegrateDhtOpsWorkspace,
+) -> WorkflowResult<()> {
+    if let Some(ChainStatus::Forked(fork)) = workspace.meta.get_activity_status(author)? {
+        let first = get_signed_header(&fork.first_header, workspace)?;
+        let second = get_signed_header(&fork.second_header, workspace)?;
+        match (first, second) {
+            (Some(first), Some(second)) => {
+                workspace
+                    .meta
+                    .register_warrant(Warrant::chain_fork(first, second))?;
+            }
+            _ => warn!(?fork, "Missing the headers for a forked chain"),
+        }
+    }
+    Ok(())
+}
+
+/// Get a header that has been integrated as valid or rejected
+fn get_signed_header(
+    hash: &HeaderHash,
+    workspace: &IntegrateDhtOpsWorkspace,
+) -> DatabaseResult<Option<SignedHeader>> {
+    let shh = match workspace.elements.get_header(hash)? {
+        Some(shh) => Some(shh),
+        None => workspace.element_rejected.get_header(hash)?,
+    };
+    Ok(shh.map(|shh| shh.into_inner().0))
+}
+
+/// Retry any abandoned ops where all the dependencies they were
+/// missing are now integrated.
+/// The entries of abandoned ops are not kept so they can't be validated
+/// again from our own stores. Instead the op is forgotten so it will be
+/// validated again the next time it is published or gossiped to us.
+fn retry_abandoned_ops(workspace: &mut IntegrateDhtOpsWorkspace) -> WorkflowResult<()> {
+    let mut retry = Vec::new();
+    let mut still_abandoned = HashSet::new();
+    for (op_hash, missing_dependencies) in workspace.meta.get_abandoned_ops()? {
+        // The op might still be waiting in the integration limbo
+        let op = match workspace.integrated_dht_ops.get(&op_hash)? {
+            Some(integrated) => integrated.op,
+            None => continue,
+        };
+        let mut all_held = true;
+        for hash in missing_dependencies.iter() {
+            if !dependency_integrated(hash, workspace)? {
+                all_held = false;
+                break;
+            }
+        }
+        if all_held {
+            retry.push((op_hash, op));
+        } else {
+            still_abandoned.insert(op.header_hash().clone());
+        }
+    }
+    for (op_hash, op) in retry {
+        debug!(?op_hash, "retrying abandoned op");
+        workspace.meta.deregister_abandoned_op(&op_hash)?;
+        workspace.integrated_dht_ops.delete(op_hash)?;
+        let header_hash = op.header_hash().clone();
+        match op {
+            DhtOpLight::StoreElement(..) => {
+                workspace
+                    .meta
+                    .deregister_abandoned_element_header(header_hash.clone())?;
+            }
+            DhtOpLight::StoreEntry(..) => {
+                let header = workspace
+                    .element_abandoned
+                    .get_header(&header_hash)?
+                    .and_then(|h| NewEntryHeader::try_from(h.header().clone()).ok());
+                if let Some(header) = header {
+                    workspace.meta.deregister_abandoned_header(header)?;
+                }
+            }
+            _ => {}
+        }
+        // Other ops for this header might still be abandoned
+        if !still_abandoned.contains(&header_hash) {
+            workspace.element_abandoned.delete(header_hash, None);
+        }
+    }
+    Ok(())
+}
+
+fn dependency_integrated(
+    hash: &AnyDhtHash,
+    workspace: &IntegrateDhtOpsWorkspace,
+) -> DatabaseResult<bool> {
+    match *hash.hash_type() {
+        AnyDht::Entry => workspace.elements.contains_entry(&hash.clone().into()),
+        AnyDht::Header => workspace.elements.contains_header(&hash.clone().into()),
+    }
+}
+
 /// Rejected headers still need to be stored in the metadata vault so
 /// they can be served for a get details call.
 fn update_validation_status(
@@ -466,6 +629,8 @@ pub struct IntegrateDhtOpsWorkspace {
     pub meta_pending: MetadataBuf<PendingPrefix>,
     pub element_rejected: ElementBuf<RejectedPrefix>,
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
+    /// Headers of abandoned ops
+    pub element_abandoned: ElementBuf<AbandonedPrefix>,
     /// Ops to disintegrate
     pub to_disintegrate_pending: Vec<DhtOpLight>,
     /// READ ONLY
@@ -489,6 +654,7 @@ impl Workspace for IntegrateDhtOpsWorkspace {
         self.meta_pending.flush_to_txn_ref(writer)?;
         self.element_rejected.flush_to_txn_ref(writer)?;
         self.meta_rejected.flush_to_txn_ref(writer)?;
+        self.element_abandoned.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -511,7 +677,9 @@ impl IntegrateDhtOpsWorkspace {
         let meta_pending = MetadataBuf::pending(env.clone())?;
 
         let element_rejected = ElementBuf::rejected(env.clone())?;
-        let meta_rejected = MetadataBuf::rejected(env)?;
+        let meta_rejected = MetadataBuf::rejected(env.clone())?;
+
+        let element_abandoned = ElementBuf::abandoned(env)?;
 
         Ok(Self {
             integration_limbo,
@@ -522,6 +690,7 @@ impl IntegrateDhtOpsWorkspace {
             meta_pending,
             element_rejected,
             meta_rejected,
+            element_abandoned,
             validation_limbo,
             to_disintegrate_pending: Vec::new(),
         })
@@ -539,9 +708,8 @@ impl IntegrateDhtOpsWorkspace {
         Ok(self.integrated_dht_ops.contains(&hash)? || self.integration_limbo.contains(&hash)?)
     }
 
-    /// Create a cascade through the integrated and rejected stores
-    // TODO: Might need to add abandoned here but will need some
-    // thought as abandoned entries are not stored.
+    /// Create a cascade through the integrated and rejected stores.
+    /// Abandoned data is not included because abandoned entries are not stored.
     pub fn cascade(&self) -> Cascade<'_> {
         let integrated_data = DbPair {
             element: &self.elements,
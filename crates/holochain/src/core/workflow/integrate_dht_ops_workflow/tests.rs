@@ -195,6 +195,7 @@ impl Db {
                     let value = IntegratedDhtOpsValue {
                         validation_status: ValidationStatus::Valid,
                         op: op.to_light(),
+                        when_authored: op.header().timestamp(),
                         when_integrated: timestamp::now().into(),
                     };
                     let mut r = workspace
@@ -835,6 +836,171 @@ async fn test_ops_state() {
     }
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn abandoned_ops_keep_header_and_are_retried() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let a = TestData::new().await;
+
+    // An abandoned store element that was missing a header
+    let header: Header = a.original_header.clone().into();
+    let header_hash = HeaderHash::with_data_sync(&header);
+    let op = DhtOp::StoreElement(
+        a.signature.clone(),
+        header,
+        Some(a.original_entry.clone().into()),
+    );
+    let op_hash = DhtOpHashed::from_content_sync(op.clone()).into_hash();
+    let missing_header = a.dna_header.clone();
+    let missing_hash = HeaderHash::with_data_sync(&missing_header);
+    {
+        let mut workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        integrate_single_data(op.clone(), &mut workspace.element_pending).unwrap();
+        let val = IntegrationLimboValue {
+            validation_status: ValidationStatus::Abandoned,
+            op: op.to_light(),
+        };
+        workspace
+            .integration_limbo
+            .put(op_hash.clone(), val)
+            .unwrap();
+        workspace
+            .meta
+            .register_abandoned_op(op_hash.clone(), vec![missing_hash.into()])
+            .unwrap();
+        env.guard()
+            .with_commit::<WorkspaceError, _, _>(|writer| {
+                workspace.flush_to_txn(writer)?;
+                Ok(())
+            })
+            .unwrap();
+    }
+    call_workflow(env.clone()).await;
+
+    // Only the header is kept
+    {
+        let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let integrated = workspace.integrated_dht_ops.get(&op_hash).unwrap().unwrap();
+        assert_eq!(integrated.validation_status, ValidationStatus::Abandoned);
+        assert!(workspace
+            .element_abandoned
+            .contains_header(&header_hash)
+            .unwrap());
+        assert!(!workspace
+            .element_abandoned
+            .contains_entry(&a.original_entry_hash)
+            .unwrap());
+        assert!(!workspace.elements.contains_header(&header_hash).unwrap());
+        assert!(workspace
+            .meta
+            .has_abandoned_registered_store_element(&header_hash)
+            .unwrap());
+        assert_eq!(workspace.meta.get_abandoned_ops().unwrap().len(), 1);
+    }
+
+    // The missing header turns up so the abandoned op is forgotten
+    let found_op = DhtOp::RegisterAgentActivity(a.signature.clone(), missing_header);
+    let pre_state = add_op_to_judged(vec![Db::IntQueue(found_op.clone())], &found_op);
+    Db::set(pre_state, env.clone()).await;
+    call_workflow(env.clone()).await;
+    {
+        let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        assert!(!workspace.integrated_dht_ops.contains(&op_hash).unwrap());
+        assert!(!workspace
+            .element_abandoned
+            .contains_header(&header_hash)
+            .unwrap());
+        assert!(!workspace
+            .meta
+            .has_abandoned_registered_store_element(&header_hash)
+            .unwrap());
+        assert!(workspace.meta.get_abandoned_ops().unwrap().is_empty());
+    }
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn abandoned_store_entry_is_registered_on_entry() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let a = TestData::new().await;
+
+    // An abandoned store entry that was missing a header
+    let op = DhtOp::StoreEntry(
+        a.signature.clone(),
+        a.original_header.clone(),
+        a.original_entry.clone().into(),
+    );
+    let op_hash = DhtOpHashed::from_content_sync(op.clone()).into_hash();
+    let header_hash = HeaderHash::with_data_sync(&Header::from(a.original_header.clone()));
+    let missing_header = a.dna_header.clone();
+    let missing_hash = HeaderHash::with_data_sync(&missing_header);
+    {
+        let mut workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        integrate_single_data(op.clone(), &mut workspace.element_pending).unwrap();
+        let val = IntegrationLimboValue {
+            validation_status: ValidationStatus::Abandoned,
+            op: op.to_light(),
+        };
+        workspace
+            .integration_limbo
+            .put(op_hash.clone(), val)
+            .unwrap();
+        workspace
+            .meta
+            .register_abandoned_op(op_hash.clone(), vec![missing_hash.into()])
+            .unwrap();
+        env.guard()
+            .with_commit::<WorkspaceError, _, _>(|writer| {
+                workspace.flush_to_txn(writer)?;
+                Ok(())
+            })
+            .unwrap();
+    }
+    call_workflow(env.clone()).await;
+
+    // The header is registered as abandoned and not as a valid header
+    {
+        let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let env_ref = env.guard();
+        let reader = env_ref.reader().unwrap();
+        let abandoned = workspace
+            .meta
+            .get_abandoned_headers(&reader, a.original_entry_hash.clone())
+            .unwrap()
+            .map(|h| Ok(h.header_hash))
+            .collect::<Vec<_>>()
+            .unwrap();
+        assert_eq!(abandoned, vec![header_hash.clone()]);
+        assert!(workspace
+            .meta
+            .get_all_headers(&reader, a.original_entry_hash.clone())
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_none());
+    }
+
+    // The missing header turns up so the abandoned header is forgotten
+    let found_op = DhtOp::RegisterAgentActivity(a.signature.clone(), missing_header);
+    let pre_state = add_op_to_judged(vec![Db::IntQueue(found_op.clone())], &found_op);
+    Db::set(pre_state, env.clone()).await;
+    call_workflow(env.clone()).await;
+    {
+        let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let env_ref = env.guard();
+        let reader = env_ref.reader().unwrap();
+        assert!(workspace
+            .meta
+            .get_abandoned_headers(&reader, a.original_entry_hash.clone())
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_none());
+    }
+}
+
 /// Call the produce dht ops workflow
 async fn produce_dht_ops<'env>(env: EnvironmentWrite) {
     let (mut qt, _rx) = TriggerSender::new();
@@ -1321,6 +1487,8 @@ mod slow_tests {
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
                 zomes: vec![TestWasm::Create.into()].into(),
+                default_required_validations: Default::default(),
+                entry_type_properties: Default::default(),
             },
             vec![TestWasm::Create.into()],
         )
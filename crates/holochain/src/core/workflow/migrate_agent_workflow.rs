@@ -0,0 +1,251 @@
+//! Migrate Agent Workflow: Run the `migrate_agent` callback and, if every zome
+//! passes, commit the header that links a source chain to the other side of
+//! the migration:
+//! - Close: the old chain gets a CloseChain header naming the new DNA
+//! - Open: the new chain gets an OpenChain header naming the old DNA
+
+use super::error::WorkflowResult;
+use super::CallZomeWorkspace;
+use super::CallZomeWorkspaceLock;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holochain_state::workspace::Workspace;
+use holochain_types::prelude::*;
+use holochain_zome_types::header::builder;
+use tracing::*;
+
+#[derive(Constructor, Debug)]
+pub struct MigrateAgentWorkflowArgs<Ribosome: RibosomeT> {
+    pub dna_def: DnaDef,
+    /// Which end of the migration this chain is
+    pub migrate_agent: MigrateAgent,
+    /// The DNA on the other end of the migration
+    pub other_dna_hash: DnaHash,
+    pub ribosome: Ribosome,
+}
+
+pub type MigrateAgentWorkspace = CallZomeWorkspace;
+
+#[instrument(skip(workspace, writer))]
+pub async fn migrate_agent_workflow<'env, Ribosome: RibosomeT>(
+    workspace: MigrateAgentWorkspace,
+    writer: OneshotWriter,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult> {
+    let workspace_lock = CallZomeWorkspaceLock::new(workspace);
+    let result = migrate_agent_workflow_inner(workspace_lock.clone(), args).await?;
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // Only commit the header if all the zomes allowed the migration
+    if let MigrateAgentResult::Pass = result {
+        let mut guard = workspace_lock.write().await;
+        let workspace: &mut CallZomeWorkspace = &mut guard;
+        writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+    }
+    Ok(result)
+}
+
+/// Run the `migrate_agent` callback without committing anything,
+/// so a migration can be checked before any chain is changed.
+pub async fn run_migrate_agent_callback<Ribosome: RibosomeT>(
+    workspace: MigrateAgentWorkspace,
+    dna_def: DnaDef,
+    migrate_agent: MigrateAgent,
+    ribosome: &Ribosome,
+) -> WorkflowResult<MigrateAgentResult> {
+    let host_access = MigrateAgentHostAccess::new(CallZomeWorkspaceLock::new(workspace));
+    let invocation = MigrateAgentInvocation::new(dna_def, migrate_agent);
+    Ok(ribosome.run_migrate_agent(host_access, invocation)?)
+}
+
+async fn migrate_agent_workflow_inner<Ribosome: RibosomeT>(
+    workspace: CallZomeWorkspaceLock,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult> {
+    let MigrateAgentWorkflowArgs {
+        dna_def,
+        migrate_agent,
+        other_dna_hash,
+        ribosome,
+    } = args;
+
+    // An earlier attempt at this migration may have closed the chain
+    // and then failed, in which case it's already done.
+    if let MigrateAgent::Close = migrate_agent {
+        let workspace = workspace.read().await;
+        let head = workspace.source_chain.chain_head()?;
+        if let Some(head) = workspace.source_chain.get_header(head)? {
+            if let Header::CloseChain(close) = head.header() {
+                if close.new_dna_hash == other_dna_hash {
+                    return Ok(MigrateAgentResult::Pass);
+                }
+            }
+        }
+    }
+
+    // Call the migrate_agent callback
+    let result = {
+        let host_access = MigrateAgentHostAccess::new(workspace.clone());
+        let invocation = MigrateAgentInvocation::new(dna_def, migrate_agent.clone());
+        ribosome.run_migrate_agent(host_access, invocation)?
+    };
+    if let MigrateAgentResult::Fail(..) = result {
+        return Ok(result);
+    }
+
+    // Link this chain to the other end of the migration
+    let mut workspace = workspace.write().await;
+    match migrate_agent {
+        MigrateAgent::Close => {
+            workspace
+                .source_chain
+                .put(
+                    builder::CloseChain {
+                        new_dna_hash: other_dna_hash,
+                    },
+                    None,
+                )
+                .await?;
+        }
+        MigrateAgent::Open => {
+            workspace
+                .source_chain
+                .put(
+                    builder::OpenChain {
+                        prev_dna_hash: other_dna_hash,
+                    },
+                    None,
+                )
+                .await?;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::core::ribosome::MockRibosomeT;
+    use crate::core::workflow::fake_genesis;
+    use crate::fixt::DnaDefFixturator;
+    use ::fixt::prelude::*;
+    use fixt::Unpredictable;
+    use holo_hash::fixt::DnaHashFixturator;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_lmdb::test_utils::TestEnvironment;
+    use holochain_zome_types::Header;
+    use matches::assert_matches;
+
+    async fn run_migrate_agent(
+        migrate_agent: MigrateAgent,
+        result: fn() -> MigrateAgentResult,
+    ) -> (TestEnvironment, CallZomeWorkspaceLock, MigrateAgentResult) {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        fake_genesis(&mut workspace.source_chain).await.unwrap();
+
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_run_migrate_agent()
+            .returning(move |_, _| Ok(result()));
+
+        let args = MigrateAgentWorkflowArgs {
+            dna_def: DnaDefFixturator::new(Unpredictable).next().unwrap(),
+            migrate_agent,
+            other_dna_hash: fixt!(DnaHash),
+            ribosome,
+        };
+        let workspace_lock = CallZomeWorkspaceLock::new(workspace);
+        let result = migrate_agent_workflow_inner(workspace_lock.clone(), args)
+            .await
+            .unwrap();
+        (test_env, workspace_lock, result)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn close_adds_close_chain_header() {
+        let (_te, workspace, result) =
+            run_migrate_agent(MigrateAgent::Close, || MigrateAgentResult::Pass).await;
+        assert_eq!(result, MigrateAgentResult::Pass);
+        assert_matches!(
+            workspace
+                .read()
+                .await
+                .source_chain
+                .get_at_index(3)
+                .unwrap()
+                .unwrap()
+                .header(),
+            Header::CloseChain(_)
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn open_adds_open_chain_header() {
+        let (_te, workspace, result) =
+            run_migrate_agent(MigrateAgent::Open, || MigrateAgentResult::Pass).await;
+        assert_eq!(result, MigrateAgentResult::Pass);
+        let workspace = workspace.read().await;
+        assert_matches!(
+            workspace
+                .source_chain
+                .get_at_index(3)
+                .unwrap()
+                .unwrap()
+                .header(),
+            Header::OpenChain(_)
+        );
+        // Init still needs to run on a migrated chain
+        assert!(!workspace.source_chain.has_initialized());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn failed_callback_adds_no_header() {
+        let (_te, workspace, result) = run_migrate_agent(MigrateAgent::Close, || {
+            MigrateAgentResult::Fail("foo".into(), "no migrating".into())
+        })
+        .await;
+        assert_matches!(result, MigrateAgentResult::Fail(..));
+        assert_eq!(workspace.read().await.source_chain.len(), 3);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn close_is_not_repeated() {
+        let (_te, workspace, _) =
+            run_migrate_agent(MigrateAgent::Close, || MigrateAgentResult::Pass).await;
+        let other_dna_hash = match workspace
+            .read()
+            .await
+            .source_chain
+            .get_at_index(3)
+            .unwrap()
+            .unwrap()
+            .header()
+        {
+            Header::CloseChain(close) => close.new_dna_hash.clone(),
+            h => panic!("Expected a CloseChain header but got {:?}", h),
+        };
+
+        // The callback already passed when the chain was closed
+        let mut ribosome = MockRibosomeT::new();
+        ribosome.expect_run_migrate_agent().never();
+        let args = MigrateAgentWorkflowArgs {
+            dna_def: DnaDefFixturator::new(Unpredictable).next().unwrap(),
+            migrate_agent: MigrateAgent::Close,
+            other_dna_hash,
+            ribosome,
+        };
+        let result = migrate_agent_workflow_inner(workspace.clone(), args)
+            .await
+            .unwrap();
+        assert_eq!(result, MigrateAgentResult::Pass);
+        assert_eq!(workspace.read().await.source_chain.len(), 4);
+    }
+}
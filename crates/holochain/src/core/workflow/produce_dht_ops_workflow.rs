@@ -2,8 +2,11 @@ use super::error::WorkflowResult;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use fallible_iterator::FallibleIterator;
+use holo_hash::DhtOpHash;
 use holochain_lmdb::buffer::KvBufFresh;
 use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::BufferedStore;
 use holochain_lmdb::prelude::EnvironmentRead;
 use holochain_lmdb::prelude::GetDb;
@@ -37,6 +40,22 @@ async fn produce_dht_ops_workflow_inner(
     workspace: &mut ProduceDhtOpsWorkspace,
 ) -> WorkflowResult<WorkComplete> {
     debug!("Starting dht op workflow");
+    let now = holochain_types::timestamp::now();
+
+    // Ops authored before the publish queue existed still need publishing.
+    // The publish workflow drops any that already have enough receipts.
+    if !workspace.publish_queue.is_backfilled()? {
+        let authored = fresh_reader!(workspace.authored_dht_ops.env(), |r| workspace
+            .authored_dht_ops
+            .iter(&r)?
+            .map(|(k, _)| Ok(DhtOpHash::from_raw_39_panicky(k.to_vec())))
+            .collect::<Vec<_>>())?;
+        for hash in authored {
+            workspace.publish_queue.add(hash, now)?;
+        }
+        workspace.publish_queue.mark_backfilled()?;
+    }
+
     let all_ops = workspace.source_chain.get_incomplete_dht_ops().await?;
 
     for (index, ops) in all_ops {
@@ -48,7 +67,9 @@ async fn produce_dht_ops_workflow_inner(
                 receipt_count: 0,
                 last_publish_time: None,
             };
-            workspace.authored_dht_ops.put(hash, value)?;
+            workspace.authored_dht_ops.put(hash.clone(), value)?;
+            // Queue the op to be published straight away
+            workspace.publish_queue.add(hash, now)?;
         }
         // Mark the dht op as complete
         workspace.source_chain.complete_dht_op(index)?;
@@ -60,6 +81,7 @@ async fn produce_dht_ops_workflow_inner(
 pub struct ProduceDhtOpsWorkspace {
     pub source_chain: SourceChain,
     pub authored_dht_ops: AuthoredDhtOpsStore,
+    pub publish_queue: PublishQueue,
 }
 
 impl ProduceDhtOpsWorkspace {
@@ -67,7 +89,8 @@ impl ProduceDhtOpsWorkspace {
         let authored_dht_ops = env.get_db(&*AUTHORED_DHT_OPS)?;
         Ok(Self {
             source_chain: SourceChain::public_only(env.clone())?,
-            authored_dht_ops: KvBufFresh::new(env, authored_dht_ops),
+            authored_dht_ops: KvBufFresh::new(env.clone(), authored_dht_ops),
+            publish_queue: PublishQueue::new(env)?,
         })
     }
 }
@@ -76,6 +99,7 @@ impl Workspace for ProduceDhtOpsWorkspace {
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
         self.source_chain.flush_to_txn_ref(writer)?;
         self.authored_dht_ops.flush_to_txn_ref(writer)?;
+        self.publish_queue.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -95,6 +119,7 @@ mod tests {
     use holochain_lmdb::test_utils::test_cell_env;
     use holochain_types::dht_op::produce_ops_from_element;
     use holochain_types::dht_op::DhtOp;
+    use holochain_types::dht_op::DhtOpLight;
     use holochain_types::fixt::*;
     use holochain_types::EntryHashed;
     use holochain_zome_types::entry_def::EntryVisibility;
@@ -247,6 +272,16 @@ mod tests {
             // Check we got all the hashes
             assert_eq!(authored_results, expected_hashes);
 
+            // Every authored op is queued to be published
+            let queued = workspace
+                .publish_queue
+                .all()
+                .unwrap()
+                .into_iter()
+                .map(|(hash, _)| hash)
+                .collect::<HashSet<_>>();
+            assert_eq!(queued, expected_hashes);
+
             authored_results.len()
         };
 
@@ -278,4 +313,76 @@ mod tests {
             assert_eq!(last_count, authored_count);
         }
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn authored_ops_are_backfilled_into_publish_queue() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let env_ref = env.guard();
+
+        // Ops authored before the publish queue existed
+        let old_hashes = {
+            let mut source_chain = SourceChain::new(env.clone().into()).unwrap();
+            fake_genesis(&mut source_chain).await.unwrap();
+            env_ref
+                .with_commit(|writer| source_chain.flush_to_txn(writer))
+                .unwrap();
+            let mut workspace = ProduceDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            let mut hashes = HashSet::new();
+            for _ in 0..3 {
+                let hash = fixt!(DhtOpHash);
+                let op =
+                    DhtOpLight::RegisterAgentActivity(fixt!(HeaderHash), fixt!(AgentPubKey).into());
+                workspace
+                    .authored_dht_ops
+                    .put(hash.clone(), AuthoredDhtOpsValue::from_light(op))
+                    .unwrap();
+                hashes.insert(hash);
+            }
+            env_ref
+                .with_commit(|writer| workspace.authored_dht_ops.flush_to_txn_ref(writer))
+                .unwrap();
+            hashes
+        };
+
+        // The first run queues every authored op
+        let removed = {
+            let mut workspace = ProduceDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            produce_dht_ops_workflow_inner(&mut workspace)
+                .await
+                .unwrap();
+            let queued = workspace.publish_queue.all().unwrap();
+            let queued_hashes = queued
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect::<HashSet<_>>();
+            assert!(queued_hashes.is_superset(&old_hashes));
+            // Publishing finishes with one of the old ops
+            let (hash, value) = queued
+                .into_iter()
+                .find(|(hash, _)| old_hashes.contains(hash))
+                .unwrap();
+            workspace.publish_queue.remove(&hash, &value).unwrap();
+            env_ref
+                .with_commit(|writer| workspace.flush_to_txn(writer))
+                .unwrap();
+            hash
+        };
+
+        // Later runs don't backfill again
+        {
+            let mut workspace = ProduceDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            produce_dht_ops_workflow_inner(&mut workspace)
+                .await
+                .unwrap();
+            assert!(workspace.publish_queue.is_backfilled().unwrap());
+            assert!(workspace
+                .publish_queue
+                .all()
+                .unwrap()
+                .iter()
+                .all(|(hash, _)| *hash != removed));
+        }
+    }
 }
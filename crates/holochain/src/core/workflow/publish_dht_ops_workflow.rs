@@ -13,9 +13,9 @@
 use super::error::WorkflowResult;
 use super::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
 use super::produce_dht_ops_workflow::dht_op_light::light_to_op;
+use crate::conductor::api::CellConductorApiT;
+use crate::conductor::entry_def_store::get_entry_def_from_ids;
 use crate::core::queue_consumer::OneshotWriter;
-use crate::core::queue_consumer::WorkComplete;
-use fallible_iterator::FallibleIterator;
 use holo_hash::*;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvBufFresh;
@@ -31,32 +31,45 @@ use std::collections::HashMap;
 use std::time;
 use tracing::*;
 
-/// Default redundancy factor for validation receipts
-// TODO: Pull this from the wasm entry def and only use this if it's missing
-// TODO: Put a default in the DnaBundle
-// TODO: build zome_types/entry_def map to get the (AppEntryType map to entry def)
-pub const DEFAULT_RECEIPT_BUNDLE_SIZE: u32 = 5;
-
 /// Don't publish a DhtOp more than once during this interval.
 /// This allows us to trigger the publish workflow as often as we like, without
 /// flooding the network with spurious publishes.
+/// The interval doubles every time the op is published.
 pub const MIN_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(5);
 
+/// The longest we will wait before publishing a DhtOp that
+/// still doesn't have enough validation receipts again.
+pub const MAX_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(60 * 60);
+
+/// Stop publishing a DhtOp once this many validators have
+/// sent back a receipt saying they rejected it.
+pub const REJECTED_RECEIPT_LIMIT: usize = 2;
+
 /// Database buffers required for publishing [DhtOp]s
 pub struct PublishDhtOpsWorkspace {
     /// Database of authored DhtOps, with data about prior publishing
     authored_dht_ops: AuthoredDhtOpsStore,
+    /// Authored DhtOps that still need publishing and when to next publish them
+    publish_queue: PublishQueue,
     /// Element store for looking up data to construct ops
     elements: ElementBuf<AuthoredPrefix>,
+    /// Receipts validators have sent back for our ops
+    validation_receipts: ValidationReceiptsBuf,
+    /// Ops published in this run and when they were published
+    published: Vec<(DhtOpHash, Timestamp)>,
 }
 
-#[instrument(skip(workspace, writer, network))]
+#[instrument(skip(workspace, writer, network, conductor_api))]
+/// Publish the queued ops that are due and
+/// return the next time an op will be due, if any.
 pub async fn publish_dht_ops_workflow(
     mut workspace: PublishDhtOpsWorkspace,
     writer: OneshotWriter,
     network: &mut HolochainP2pCell,
-) -> WorkflowResult<WorkComplete> {
-    let to_publish = publish_dht_ops_workflow_inner(&mut workspace).await?;
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<Option<Timestamp>> {
+    let to_publish = publish_dht_ops_workflow_inner(&mut workspace, conductor_api).await?;
+    let next_publish = workspace.publish_queue.next_publish()?;
 
     // Commit to the network
     for (basis, ops) in to_publish {
@@ -67,64 +80,76 @@ pub async fn publish_dht_ops_workflow(
     // commit the workspace
     writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
 
-    Ok(WorkComplete::Complete)
+    Ok(next_publish)
 }
 
-/// Read the authored for ops with receipt count < R
+/// Read the publish queue for ops that are due to be published.
+/// Ops are removed from the queue once they have R receipts
+/// or have been rejected by the network.
+/// R is the `required_validations` of the op's entry def
+/// or the DNA default if the op has no entry def.
 pub async fn publish_dht_ops_workflow_inner(
     workspace: &mut PublishDhtOpsWorkspace,
+    conductor_api: &impl CellConductorApiT,
 ) -> WorkflowResult<HashMap<AnyDhtHash, Vec<(DhtOpHash, DhtOp)>>> {
-    // TODO: PERF: We need to check all ops every time this runs
-    // instead we could have a queue of ops where count < R and a kv for count > R.
-    // Then if the count for an ops reduces below R move it to the queue.
     let now = timestamp::now();
-    // chrono cannot create const durations
-    let interval =
-        chrono::Duration::from_std(MIN_PUBLISH_INTERVAL).expect("const interval must be positive");
+
+    // Ops to publish by basis
+    let mut to_publish = HashMap::new();
+
+    let due = workspace.publish_queue.due(&now)?;
+    if due.is_empty() {
+        return Ok(to_publish);
+    }
 
     // one of many ways to access the env
     let env = workspace.elements.headers().env().clone();
 
-    let values = fresh_reader!(env, |r| workspace
-        .authored()
-        .iter(&r)?
-        .filter_map(|(k, mut r)| {
-            Ok(if r.receipt_count < DEFAULT_RECEIPT_BUNDLE_SIZE {
-                let needs_publish = r
-                    .last_publish_time
-                    .and_then(|last| now.checked_difference_signed(&last))
-                    .map(|duration| duration > interval)
-                    .unwrap_or(true);
-                if needs_publish {
-                    r.last_publish_time = Some(now);
-                    // HACK: Incrementing the receipt count to prevent publishing
-                    // forever although without receipts this could lead to data loss
-                    // and relies on gossip for data integrity.
-                    // This should be removed when receipts are implemented.
-                    r.receipt_count += 1;
-                    Some((DhtOpHash::from_raw_39_panicky(k.to_vec()), r))
-                } else {
-                    None
-                }
-            } else {
-                None
-            })
-        })
-        .collect::<Vec<_>>())?;
+    let dna_file = conductor_api.get_this_dna().await.map_err(Box::new)?;
+    let mut required_receipts = RequiredReceipts::new(dna_file.dna());
 
-    // Ops to publish by basis
-    let mut to_publish = HashMap::new();
+    for (op_hash, mut queued) in due {
+        let value = match workspace.authored_dht_ops.get(&op_hash)? {
+            Some(value) => value,
+            // Nothing to publish
+            None => {
+                workspace.publish_queue.remove(&op_hash, &queued)?;
+                continue;
+            }
+        };
 
-    for (op_hash, value) in values {
-        // Insert updated values into database for items about to be published
-        let op = value.op.clone();
-        workspace.authored().put(op_hash.clone(), value)?;
+        // Enough validators have told us this op is invalid
+        // so there's no point in publishing it again
+        let rejected = fresh_reader!(env, |r| workspace
+            .validation_receipts
+            .count_rejected(&r, &op_hash))?;
+        if rejected >= REJECTED_RECEIPT_LIMIT {
+            workspace.publish_queue.remove(&op_hash, &queued)?;
+            continue;
+        }
 
-        let op = match light_to_op(op, workspace.elements()) {
+        let op = match light_to_op(value.op.clone(), workspace.elements()) {
             // Ignore StoreEntry ops on private
-            Err(DhtOpConvertError::StoreEntryOnPrivate) => continue,
+            Err(DhtOpConvertError::StoreEntryOnPrivate) => {
+                workspace.publish_queue.remove(&op_hash, &queued)?;
+                continue;
+            }
             r => r?,
         };
+
+        // Enough validators are holding this op
+        if value.receipt_count >= required_receipts.for_op(&op, conductor_api).await? {
+            workspace.publish_queue.remove(&op_hash, &queued)?;
+            continue;
+        }
+
+        // Record the publish time for items about to be published
+        workspace.published.push((op_hash.clone(), now));
+        // The op is keyed by when it is next due so it moves in the queue
+        workspace.publish_queue.remove(&op_hash, &queued)?;
+        queued.published(now, MIN_PUBLISH_INTERVAL, MAX_PUBLISH_INTERVAL);
+        workspace.publish_queue.put(op_hash.clone(), queued)?;
+
         // For every op publish a request
         // Collect and sort ops by basis
         to_publish
@@ -136,9 +161,67 @@ pub async fn publish_dht_ops_workflow_inner(
     Ok(to_publish)
 }
 
+/// Looks up how many validation receipts an op needs,
+/// remembering the entry defs it has already found.
+struct RequiredReceipts<'a> {
+    dna_def: &'a DnaDefHashed,
+    entry_defs: HashMap<(ZomeId, EntryDefIndex), u32>,
+}
+
+impl<'a> RequiredReceipts<'a> {
+    fn new(dna_def: &'a DnaDefHashed) -> Self {
+        Self {
+            dna_def,
+            entry_defs: HashMap::new(),
+        }
+    }
+
+    /// The number of receipts for ops without an entry def.
+    /// A default of 0 is treated as 1 so ops are always published.
+    fn dna_default(&self) -> u32 {
+        u32::from(u8::from(self.dna_def.default_required_validations)).max(1)
+    }
+
+    /// Ops on app entries use the `required_validations` of their entry def.
+    /// Everything else uses the DNA default.
+    async fn for_op(
+        &mut self,
+        op: &DhtOp,
+        conductor_api: &impl CellConductorApiT,
+    ) -> WorkflowResult<u32> {
+        let aet = match op.header().entry_type() {
+            Some(EntryType::App(aet)) => aet.clone(),
+            _ => return Ok(self.dna_default()),
+        };
+        let key = (aet.zome_id(), aet.id());
+        if let Some(required) = self.entry_defs.get(&key) {
+            return Ok(*required);
+        }
+        let required =
+            match get_entry_def_from_ids(aet.zome_id(), aet.id(), self.dna_def, conductor_api)
+                .await?
+            {
+                Some(entry_def) => u8::from(entry_def.required_validations).into(),
+                None => self.dna_default(),
+            };
+        self.entry_defs.insert(key, required);
+        Ok(required)
+    }
+}
+
 impl Workspace for PublishDhtOpsWorkspace {
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        // The authored values are read in this transaction so the receipt
+        // counts written by the validation receipt handler are kept
+        let store = self.authored_dht_ops.store();
+        for (op_hash, publish_time) in self.published.drain(..) {
+            if let Some(mut value) = store.get(writer, &op_hash)? {
+                value.last_publish_time = Some(publish_time);
+                store.put(writer, &op_hash, &value)?;
+            }
+        }
         self.authored_dht_ops.flush_to_txn_ref(writer)?;
+        self.publish_queue.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -147,18 +230,19 @@ impl PublishDhtOpsWorkspace {
     pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
         let db = env.get_db(&*AUTHORED_DHT_OPS)?;
         let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let publish_queue = PublishQueue::new(env.clone())?;
         // Note that this must always be false as we don't want private entries being published
-        let elements = ElementBuf::authored(env, false)?;
+        let elements = ElementBuf::authored(env.clone(), false)?;
+        let validation_receipts = ValidationReceiptsBuf::new(&env)?;
         Ok(Self {
             authored_dht_ops,
+            publish_queue,
             elements,
+            validation_receipts,
+            published: Vec::new(),
         })
     }
 
-    fn authored(&mut self) -> &mut AuthoredDhtOpsStore {
-        &mut self.authored_dht_ops
-    }
-
     fn elements(&self) -> &ElementBuf<AuthoredPrefix> {
         &self.elements
     }
@@ -167,17 +251,25 @@ impl PublishDhtOpsWorkspace {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conductor::api::MockCellConductorApi;
     use crate::core::queue_consumer::TriggerSender;
+    use crate::core::queue_consumer::WorkComplete;
     use crate::core::workflow::fake_genesis;
     use crate::core::workflow::produce_dht_ops_workflow::produce_dht_ops_workflow;
     use crate::core::workflow::produce_dht_ops_workflow::ProduceDhtOpsWorkspace;
     use crate::core::SourceChainError;
+    use crate::fixt::CreateFixturator;
     use crate::fixt::CreateLinkFixturator;
+    use crate::fixt::DnaDefFixturator;
+    use crate::fixt::EntryDefFixturator;
     use crate::fixt::EntryFixturator;
+    use crate::fixt::ZomeDefFixturator;
     use crate::test_utils::test_network_with_events;
     use crate::test_utils::TestNetwork;
     use ::fixt::prelude::*;
+    use fallible_iterator::FallibleIterator;
     use futures::future::FutureExt;
+    use holochain_keystore::KeystoreSenderExt;
     use holochain_p2p::actor::HolochainP2pSender;
     use holochain_p2p::HolochainP2pRef;
     use matches::assert_matches;
@@ -194,6 +286,22 @@ mod tests {
 
     const RECV_TIMEOUT: Duration = Duration::from_millis(3000);
 
+    /// The DNA default number of receipts used in these tests
+    const REQUIRED_RECEIPTS: u8 = 5;
+
+    /// A conductor api for a dna without zomes
+    async fn test_conductor_api() -> MockCellConductorApi {
+        let mut dna_def = fixt!(DnaDef);
+        dna_def.zomes = Vec::new();
+        dna_def.default_required_validations = REQUIRED_RECEIPTS.into();
+        let dna_file = DnaFile::new(dna_def, Vec::new()).await.unwrap();
+        let mut conductor_api = MockCellConductorApi::new();
+        conductor_api
+            .expect_sync_get_this_dna()
+            .returning(move || Ok(dna_file.clone()));
+        conductor_api
+    }
+
     /// publish ops setup
     async fn setup<'env>(
         env: EnvironmentWrite,
@@ -239,6 +347,10 @@ mod tests {
                     .authored_dht_ops
                     .put(op_hash.clone(), authored_value)
                     .unwrap();
+                workspace
+                    .publish_queue
+                    .add(op_hash.clone(), timestamp::now())
+                    .unwrap();
                 // Put data into element store
                 let signed_header = SignedHeaderHashed::with_presigned(header_hash, sig);
                 workspace.elements.put(signed_header, None).unwrap();
@@ -247,6 +359,7 @@ mod tests {
             env_ref
                 .with_commit::<DatabaseError, _, _>(|writer| {
                     workspace.authored_dht_ops.flush_to_txn(writer)?;
+                    workspace.publish_queue.flush_to_txn(writer)?;
                     workspace.elements.flush_to_txn(writer)?;
                     Ok(())
                 })
@@ -318,9 +431,15 @@ mod tests {
     /// Call the workflow
     async fn call_workflow(env: EnvironmentWrite, mut cell_network: HolochainP2pCell) {
         let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
-        publish_dht_ops_workflow(workspace, env.clone().into(), &mut cell_network)
-            .await
-            .unwrap();
+        let conductor_api = test_conductor_api().await;
+        publish_dht_ops_workflow(
+            workspace,
+            env.clone().into(),
+            &mut cell_network,
+            &conductor_api,
+        )
+        .await
+        .unwrap();
     }
 
     /// There is a test that shows that network messages would be sent to all agents via broadcast.
@@ -359,11 +478,15 @@ mod tests {
                 let env_ref = env.guard();
                 recv_task.await.unwrap();
                 let reader = env_ref.reader().unwrap();
-                let mut workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
-                for i in workspace.authored().iter(&reader).unwrap().iterator() {
+                let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+                for i in workspace.authored_dht_ops.iter(&reader).unwrap().iterator() {
                     // Check that each item now has a publish time
                     assert!(i.expect("can iterate").1.last_publish_time.is_some())
                 }
+                for (_, queued) in workspace.publish_queue.all().unwrap() {
+                    // Check that each item is waiting to be published again
+                    assert_eq!(queued.attempts, 1);
+                }
             };
 
             // Shutdown
@@ -408,7 +531,7 @@ mod tests {
                     .iter(&reader)
                     .unwrap()
                     .map(|(k, mut v)| {
-                        v.receipt_count = DEFAULT_RECEIPT_BUNDLE_SIZE;
+                        v.receipt_count = REQUIRED_RECEIPTS.into();
                         Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v))
                     })
                     .collect::<Vec<_>>()
@@ -430,6 +553,10 @@ mod tests {
             // Call the workflow
             call_workflow(env.clone().into(), cell_network).await;
 
+            // The ops are no longer queued for publishing
+            let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            assert!(workspace.publish_queue.all().unwrap().is_empty());
+
             // If we can wait a while without receiving any publish, we have succeeded
             tokio::time::delay_for(Duration::from_millis(
                 std::cmp::min(50, std::cmp::max(2000, 10 * num_agents * num_hash)).into(),
@@ -443,6 +570,190 @@ mod tests {
         });
     }
 
+    /// There is a test that shows that if enough validators have
+    /// rejected a DhtOp we don't re-publish it
+    #[test_case(1, 1)]
+    #[test_case(1, 10)]
+    #[test_case(10, 1)]
+    #[test_case(10, 10)]
+    fn test_no_republish_rejected(num_agents: u32, num_hash: u32) {
+        crate::conductor::tokio_runtime().block_on(async {
+            observability::test_run().ok();
+
+            // Create test env
+            let test_env = test_cell_env();
+            let env = test_env.env();
+            let env_ref = env.guard();
+            let keystore = holochain_lmdb::test_utils::test_keystore();
+
+            // Setup
+            let (_network, cell_network, recv_task, _) =
+                setup(env.clone(), num_agents, num_hash, true).await;
+
+            // Add rejected receipts for every op
+            {
+                let reader = env_ref.reader().unwrap();
+                let mut workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+
+                let hashes = workspace
+                    .authored_dht_ops
+                    .iter(&reader)
+                    .unwrap()
+                    .map(|(k, _)| Ok(DhtOpHash::from_raw_39_panicky(k.to_vec())))
+                    .collect::<Vec<_>>()
+                    .unwrap();
+
+                for hash in hashes {
+                    for _ in 0..REJECTED_RECEIPT_LIMIT {
+                        let validator = keystore
+                            .clone()
+                            .generate_sign_keypair_from_pure_entropy()
+                            .await
+                            .unwrap();
+                        let receipt = ValidationReceipt {
+                            dht_op_hash: hash.clone(),
+                            validation_result: ValidationResult::Rejected,
+                            validator,
+                        }
+                        .sign(&keystore)
+                        .await
+                        .unwrap();
+                        workspace
+                            .validation_receipts
+                            .add_if_unique(receipt)
+                            .unwrap();
+                    }
+                }
+
+                // Manually commit because this workspace doesn't commit to all dbs
+                env_ref
+                    .with_commit::<DatabaseError, _, _>(|writer| {
+                        workspace.validation_receipts.flush_to_txn(writer)?;
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+
+            // Call the workflow
+            call_workflow(env.clone().into(), cell_network).await;
+
+            // The ops are no longer queued for publishing
+            let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            assert!(workspace.publish_queue.all().unwrap().is_empty());
+
+            // If we can wait a while without receiving any publish, we have succeeded
+            tokio::time::delay_for(Duration::from_millis(
+                std::cmp::min(50, std::cmp::max(2000, 10 * num_agents * num_hash)).into(),
+            ))
+            .await;
+
+            // Shutdown
+            tokio::time::timeout(Duration::from_secs(10), recv_task)
+                .await
+                .ok();
+        });
+    }
+
+    /// Run the workflow once, returning the number of ops
+    /// published and the queue after the run
+    async fn publish_once(
+        env: &EnvironmentWrite,
+        conductor_api: &MockCellConductorApi,
+    ) -> (u32, Vec<(DhtOpHash, PublishQueueValue)>) {
+        let mut workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let to_publish = publish_dht_ops_workflow_inner(&mut workspace, conductor_api)
+            .await
+            .unwrap();
+        let queue = workspace.publish_queue.all().unwrap();
+        env.guard()
+            .with_commit(|writer| workspace.flush_to_txn(writer))
+            .unwrap();
+        let published = to_publish.values().map(|ops| ops.len() as u32).sum();
+        (published, queue)
+    }
+
+    /// There is a test that shows that an op is not published again
+    /// until its backoff has passed
+    #[tokio::test(threaded_scheduler)]
+    async fn test_publish_backoff() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let num_hash = 10;
+        let (_network, _cell_network, _recv_task, _) = setup(env.clone(), 1, num_hash, false).await;
+        let conductor_api = test_conductor_api().await;
+
+        let now = timestamp::now();
+        let (published, queue) = publish_once(&env, &conductor_api).await;
+        assert_eq!(published, num_hash);
+        assert_eq!(queue.len(), num_hash as usize);
+        for (_, queued) in queue {
+            assert_eq!(queued.attempts, 1);
+            assert!(queued.next_publish >= (now + MIN_PUBLISH_INTERVAL).unwrap());
+        }
+
+        // Nothing is due yet
+        let (published, queue) = publish_once(&env, &conductor_api).await;
+        assert_eq!(published, 0);
+        assert!(queue.iter().all(|(_, queued)| queued.attempts == 1));
+    }
+
+    /// There is a test that shows the number of receipts an op needs
+    /// comes from its entry def or falls back to the dna default
+    #[tokio::test(threaded_scheduler)]
+    async fn required_receipts_from_entry_def() {
+        let mut dna_def = fixt!(DnaDef);
+        dna_def.zomes = vec![("zome".into(), fixt!(ZomeDef))];
+        dna_def.default_required_validations = REQUIRED_RECEIPTS.into();
+        let dna_file = DnaFile::new(dna_def, Vec::new()).await.unwrap();
+        let mut entry_def = fixt!(EntryDef);
+        entry_def.required_validations = 20.into();
+
+        let mut conductor_api = MockCellConductorApi::new();
+        conductor_api
+            .expect_sync_get_entry_def()
+            .times(1)
+            .return_const(Some(entry_def));
+        conductor_api
+            .expect_sync_get_dna()
+            .return_const(Some(dna_file.clone()));
+
+        let mut required_receipts = RequiredReceipts::new(dna_file.dna());
+
+        // Ops on app entries use the entry def
+        // and the entry def is only looked up once
+        let mut create = fixt!(Create);
+        create.entry_type = EntryType::App(AppEntryType::new(
+            0.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        let op = DhtOp::StoreElement(fixt!(Signature), create.into(), None);
+        for _ in 0..2 {
+            assert_eq!(
+                required_receipts.for_op(&op, &conductor_api).await.unwrap(),
+                20
+            );
+        }
+
+        // Ops without an entry def use the dna default
+        let op = DhtOp::RegisterAddLink(fixt!(Signature), fixt!(CreateLink));
+        assert_eq!(
+            required_receipts.for_op(&op, &conductor_api).await.unwrap(),
+            REQUIRED_RECEIPTS as u32
+        );
+
+        // A dna default of 0 still needs one receipt
+        let mut dna_def = dna_file.dna_def().clone();
+        dna_def.default_required_validations = 0.into();
+        let dna_def = DnaDefHashed::from_content_sync(dna_def);
+        let mut required_receipts = RequiredReceipts::new(&dna_def);
+        assert_eq!(
+            required_receipts.for_op(&op, &conductor_api).await.unwrap(),
+            1
+        );
+    }
+
     /// There is a test to shows that DHTOps that were produced on private entries are not published.
     /// Some do get published
     /// Current private constraints:
@@ -0,0 +1,145 @@
+//! Calls the zome functions which have been scheduled with the `schedule` host fn
+//! once they are due.
+
+use super::error::WorkflowResult;
+use crate::conductor::api::CellConductorApiT;
+use crate::conductor::api::ZomeCall;
+use crate::core::queue_consumer::OneshotWriter;
+use holochain_lmdb::prelude::*;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use tracing::*;
+
+#[instrument(skip(workspace, writer, conductor_api))]
+/// Run all the scheduled functions that are due and
+/// return the next time a function will be due, if any.
+pub async fn schedule_workflow(
+    mut workspace: ScheduleWorkspace,
+    writer: OneshotWriter,
+    conductor_api: impl CellConductorApiT,
+) -> WorkflowResult<Option<Timestamp>> {
+    let now = holochain_types::timestamp::now();
+    let due = schedule_workflow_inner(&mut workspace, &now)?;
+    let next_run = workspace.scheduled.next_run()?;
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace before making the calls so a
+    // function that fails or crashes the conductor is not
+    // called again in a tight loop
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    let cell_id = conductor_api.cell_id().clone();
+    for scheduled_fn in due {
+        let ScheduledFn {
+            zome_name, fn_name, ..
+        } = scheduled_fn;
+        let call = ZomeCall {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.clone(),
+            fn_name: fn_name.clone(),
+            cap: None,
+            payload: ExternIO::encode(())?,
+            provenance: cell_id.agent_pubkey().clone(),
+        };
+        match conductor_api.call_zome(&cell_id, call).await {
+            Ok(Ok(ZomeCallResponse::Ok(_))) => {}
+            r => warn!(
+                msg = "Scheduled zome function failed",
+                ?zome_name,
+                ?fn_name,
+                result = ?r
+            ),
+        }
+    }
+
+    Ok(next_run)
+}
+
+/// Remove the due functions from the schedule, rescheduling
+/// any that are recurring.
+fn schedule_workflow_inner(
+    workspace: &mut ScheduleWorkspace,
+    now: &Timestamp,
+) -> WorkflowResult<Vec<ScheduledFn>> {
+    let due = workspace.scheduled.due(now)?;
+    for scheduled_fn in due.iter() {
+        match scheduled_fn.interval {
+            Some(interval) => {
+                // Don't try to catch up on missed calls
+                // (e.g. while the conductor was off)
+                let next_run = (*now + interval).unwrap_or_else(|_| *now);
+                let mut next = scheduled_fn.clone();
+                next.next_run = next_run;
+                workspace.scheduled.schedule(next)?;
+            }
+            None => workspace
+                .scheduled
+                .unschedule(&scheduled_fn.zome_name, &scheduled_fn.fn_name)?,
+        }
+    }
+    Ok(due)
+}
+
+pub struct ScheduleWorkspace {
+    pub scheduled: ScheduleStore,
+}
+
+impl ScheduleWorkspace {
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        Ok(Self {
+            scheduled: ScheduleStore::new(env)?,
+        })
+    }
+}
+
+impl Workspace for ScheduleWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.scheduled.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::test_utils::test_cell_env;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn due_fns_are_removed_or_rescheduled() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let now = holochain_types::timestamp::now();
+        let interval = core::time::Duration::from_secs(10);
+        let zome_name: ZomeName = "zome".into();
+
+        let mut workspace = ScheduleWorkspace::new(env.clone().into()).unwrap();
+        workspace
+            .scheduled
+            .schedule(ScheduledFn::new(
+                zome_name.clone(),
+                "once".into(),
+                now,
+                None,
+            ))
+            .unwrap();
+        workspace
+            .scheduled
+            .schedule(ScheduledFn::new(
+                zome_name.clone(),
+                "recurring".into(),
+                now,
+                Some(interval),
+            ))
+            .unwrap();
+
+        let due = schedule_workflow_inner(&mut workspace, &now).unwrap();
+        assert_eq!(due.len(), 2);
+
+        let remaining = workspace.scheduled.all().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].fn_name, "recurring".into());
+        assert_eq!(remaining[0].next_run, (now + interval).unwrap());
+        assert!(workspace.scheduled.due(&now).unwrap().is_empty());
+    }
+}
@@ -151,6 +151,16 @@ async fn sys_validation_workflow_inner(
                 workspace.put_val_limbo(op_hash, vlv)?;
             }
             Outcome::SkipAppValidation => {
+                if vlv.send_receipt {
+                    send_validation_receipt(
+                        op_hash.clone(),
+                        &op,
+                        ValidationResult::Valid,
+                        &conductor_api,
+                        network.clone(),
+                    )
+                    .await;
+                }
                 let iv = IntegrationLimboValue {
                     op: vlv.op,
                     validation_status: ValidationStatus::Valid,
@@ -175,6 +185,16 @@ async fn sys_validation_workflow_inner(
                 workspace.put_val_limbo(op_hash, vlv)?;
             }
             Outcome::Rejected => {
+                if vlv.send_receipt {
+                    send_validation_receipt(
+                        op_hash.clone(),
+                        &op,
+                        ValidationResult::Rejected,
+                        &conductor_api,
+                        network.clone(),
+                    )
+                    .await;
+                }
                 let iv = IntegrationLimboValue {
                     op: vlv.op,
                     validation_status: ValidationStatus::Rejected,
@@ -856,6 +876,7 @@ impl TryFrom<&CallZomeWorkspace> for SysValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            ..
         } = call_zome;
         let mut sys_val = Self::new(call_zome.env().clone())?;
         sys_val.element_authored = source_chain.elements().into();
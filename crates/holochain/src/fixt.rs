@@ -152,6 +152,7 @@ fixturator!(
                 zome_name_fixturator.next().unwrap(),
                 WasmZome {
                     wasm_hash: hash.to_owned(),
+                    host_fn_groups: None,
                 }
                 .into(),
             ));
@@ -179,6 +180,7 @@ fixturator!(
                 zome_name_fixturator.next().unwrap(),
                 WasmZome {
                     wasm_hash: hash.to_owned(),
+                    host_fn_groups: None,
                 }
                 .into(),
             ));
@@ -481,6 +481,8 @@ async fn setup(
             uuid,
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: zomes.clone().into_iter().map(Into::into).collect(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         zomes.into_iter().map(Into::into),
     )
@@ -234,13 +234,24 @@ pub async fn install_app(
     cell_data: Vec<(InstalledCell, Option<SerializedBytes>)>,
     dnas: Vec<DnaFile>,
     conductor_handle: ConductorHandle,
+) {
+    install_app_with_bridges(name, cell_data, dnas, AppBridges::new(), conductor_handle).await
+}
+
+/// Install an app which restricts which of its cells may call which
+pub async fn install_app_with_bridges(
+    name: &str,
+    cell_data: Vec<(InstalledCell, Option<SerializedBytes>)>,
+    dnas: Vec<DnaFile>,
+    bridges: AppBridges,
+    conductor_handle: ConductorHandle,
 ) {
     for dna in dnas {
         conductor_handle.install_dna(dna).await.unwrap();
     }
     conductor_handle
         .clone()
-        .install_app(name.to_string(), cell_data)
+        .install_app(name.to_string(), cell_data, bridges)
         .await
         .unwrap();
 
@@ -286,7 +297,12 @@ pub async fn setup_app_inner(
     let conductor_handle = ConductorBuilder::new()
         .config(ConductorConfig {
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::Websocket {
+                    port: 0,
+                    bind_address: None,
+                    tls: None,
+                },
+                require_token: false,
             }]),
             network,
             ..Default::default()
@@ -168,6 +168,8 @@ impl ConductorTestData {
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
                 zomes: zomes.clone().into_iter().map(Into::into).collect(),
+                default_required_validations: Default::default(),
+                entry_type_properties: Default::default(),
             },
             zomes.into_iter().map(Into::into),
         )
@@ -14,7 +14,7 @@ use hdk3::prelude::*;
 use holo_hash::DnaHash;
 use holochain_keystore::KeystoreSender;
 use holochain_lmdb::test_utils::{test_environments, TestEnvironments};
-use holochain_types::{app::InstalledCell, signal::Signal};
+use holochain_types::{app::AppBridges, app::InstalledCell, signal::Signal};
 
 use holochain_types::dna::DnaFile;
 use kitsune_p2p::KitsuneP2pConfig;
@@ -248,7 +248,7 @@ impl SweetConductor {
         self.handle()
             .0
             .clone()
-            .install_app(installed_app_id.clone(), installed_cells)
+            .install_app(installed_app_id.clone(), installed_cells, AppBridges::new())
             .await
             .expect("Could not install app");
 
@@ -62,7 +62,7 @@ impl TestConductorHandle {
                 .collect();
             self.0
                 .clone()
-                .install_app(installed_app_id.clone(), cells)
+                .install_app(installed_app_id.clone(), cells, AppBridges::new())
                 .await
                 .expect("Could not install app");
             info.push((installed_app_id, cell_ids));
@@ -172,6 +172,8 @@ async fn get_from_another_agent() {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::Create.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::Create.into()],
     )
@@ -316,6 +318,8 @@ async fn get_links_from_another_agent() {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::Create.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::Create.into()],
     )
@@ -349,7 +353,7 @@ async fn get_links_from_another_agent() {
     )
     .await;
 
-    let link_options = GetLinksOptions { timeout_ms: None };
+    let link_options = GetLinksOptions::default();
 
     // Bob store links
     let base = Post("Bananas are good for you".into());
@@ -53,6 +53,8 @@ async fn ser_regression_test() {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::SerRegression.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::SerRegression.into()],
     )
@@ -189,7 +191,7 @@ pub async fn setup_app(
 
     conductor_handle
         .clone()
-        .install_app("test app".to_string(), cell_data)
+        .install_app("test app".to_string(), cell_data, AppBridges::new())
         .await
         .unwrap();
 
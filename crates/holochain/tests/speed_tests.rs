@@ -133,6 +133,8 @@ async fn speed_test(n: Option<usize>) -> TestEnvironments {
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
             zomes: vec![TestWasm::Anchor.into()].into(),
+            default_required_validations: Default::default(),
+            entry_type_properties: Default::default(),
         },
         vec![TestWasm::Anchor.into()],
     )
@@ -192,7 +194,12 @@ async fn speed_test(n: Option<usize>) -> TestEnvironments {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        require_token: false,
+        bind_address: None,
+        tls: None,
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
@@ -319,7 +326,12 @@ pub async fn setup_app(
     let conductor_handle = ConductorBuilder::with_mock_dna_store(dna_store)
         .config(ConductorConfig {
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::Websocket {
+                    port: 0,
+                    bind_address: None,
+                    tls: None,
+                },
+                require_token: false,
             }]),
             ..Default::default()
         })
@@ -329,7 +341,7 @@ pub async fn setup_app(
 
     conductor_handle
         .clone()
-        .install_app("test app".to_string(), cell_data)
+        .install_app("test app".to_string(), cell_data, AppBridges::new())
         .await
         .unwrap();
 
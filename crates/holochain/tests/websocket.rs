@@ -73,7 +73,12 @@ pub async fn check_started(holochain: &mut Child) {
 fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
     ConductorConfig {
         admin_interfaces: Some(vec![AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::Websocket {
+                port,
+                bind_address: None,
+                tls: None,
+            },
+            require_token: false,
         }]),
         environment_path: environment_path.into(),
         network: None,
@@ -86,6 +91,7 @@ fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
         }),
         keystore_path: None,
         use_dangerous_test_keystore: true,
+        wasm_limits: Default::default(),
     }
 }
 
@@ -165,6 +171,7 @@ async fn call_admin() {
         nick: "nick".into(),
         properties: Some(properties.clone()),
         membrane_proof: None,
+        bridges: None,
     };
     let agent_key = fake_agent_pubkey_1();
     let payload = InstallAppPayload {
@@ -261,7 +268,12 @@ pub async fn attach_app_interface(
     holochain: &mut Child,
     port: Option<u16>,
 ) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        require_token: false,
+        bind_address: None,
+        tls: None,
+    };
     let response = client.request(request);
     let response = check_timeout(holochain, response, 1000).await;
     match response {
@@ -438,7 +450,7 @@ async fn remote_signals() -> anyhow::Result<()> {
     for mut rx in rxs {
         let r = rx.try_recv();
         // Each handle should recv a signal
-        assert_matches!(r, Ok(Signal::App(_, a)) if a == signal);
+        assert_matches!(r, Ok(Signal::App(_, _, a)) if a == signal);
     }
 
     Ok(())
@@ -530,7 +542,11 @@ async fn emit_signals() {
     let sig2: SerializedBytes = unwrap_to::unwrap_to!(msg2 => WebsocketMessage::Signal).clone();
 
     assert_eq!(
-        Signal::App(cell_id, AppSignal::new(ExternIO::encode(()).unwrap())),
+        Signal::App(
+            cell_id,
+            TestWasm::EmitSignal.into(),
+            AppSignal::new(ExternIO::encode(()).unwrap())
+        ),
         Signal::try_from(sig1.clone()).unwrap(),
     );
     assert_eq!(sig1, sig2);
@@ -571,6 +587,64 @@ async fn conductor_admin_interface_runs_from_config() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn conductor_admin_interface_over_tls() -> Result<()> {
+    observability::test_run().ok();
+
+    let tmp_dir = TempDir::new("conductor_cfg").unwrap();
+    let environment_path = tmp_dir.path().to_path_buf();
+    let cert_path = tmp_dir.path().join("admin.cert.pem");
+    let key_path = tmp_dir.path().join("admin.key.pem");
+    let mut config = create_config(0, environment_path);
+    config.admin_interfaces = Some(vec![AdminInterfaceConfig {
+        driver: InterfaceDriver::Websocket {
+            port: 0,
+            bind_address: None,
+            tls: Some(InterfaceTlsConfig {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                hosts: vec!["conductor.example.com".to_string()],
+            }),
+        },
+        require_token: false,
+    }]);
+    let conductor_handle = Conductor::builder().config(config).build().await?;
+    let port = admin_port(&conductor_handle).await;
+
+    // A self-signed certificate was generated for the interface
+    let cert_pem = std::fs::read(&cert_path)?;
+    assert!(key_path.exists());
+
+    // Plain websocket connections are refused
+    let plain_client = websocket_connect(
+        url2!("ws://127.0.0.1:{}", port),
+        Arc::new(WebsocketConfig::default()),
+    )
+    .await;
+    assert!(plain_client.is_err());
+
+    // The certificate is trusted by name and by IP address
+    for url in vec![
+        url2!("wss://localhost:{}", port),
+        url2!("wss://127.0.0.1:{}", port),
+    ] {
+        let (mut client, _rx) = websocket_connect(
+            url,
+            Arc::new(
+                WebsocketConfig::default()
+                    .scheme("wss")
+                    .tls_trusted_cert(cert_pem.clone()),
+            ),
+        )
+        .await?;
+        let response: AdminResponse = client.request(AdminRequest::ListDnas).await?;
+        assert_matches!(response, AdminResponse::DnasListed(_));
+    }
+
+    conductor_handle.shutdown().await;
+    Ok(())
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn conductor_admin_interface_ends_with_shutdown() -> Result<()> {
     if let Err(e) = conductor_admin_interface_ends_with_shutdown_inner().await {
@@ -18,7 +18,6 @@ use holochain_state::metadata::LinkMetaKey;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
 use holochain_types::prelude::*;
-use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::convert::TryInto;
 use tracing::*;
@@ -32,6 +31,7 @@ pub fn handle_get_entry(
     // Get the vaults
     let element_vault = ElementBuf::vault(state_env.clone().into(), false)?;
     let element_rejected = ElementBuf::rejected(state_env.clone().into())?;
+    let element_abandoned = ElementBuf::abandoned(state_env.clone().into())?;
     let meta_vault = MetadataBuf::vault(state_env.clone().into())?;
 
     // ## Helper closures to DRY and make more readable
@@ -46,6 +46,10 @@ pub fn handle_get_entry(
             r = element_rejected.get_header(&header_hash)?;
             status = ValidationStatus::Rejected;
         }
+        if r.is_none() {
+            r = element_abandoned.get_header(&header_hash)?;
+            status = ValidationStatus::Abandoned;
+        }
         let r = r.ok_or_else(|| AuthorityDataError::missing_data(header_hash))?;
         CascadeResult::Ok((r, status))
     };
@@ -82,7 +86,11 @@ pub fn handle_get_entry(
 
         // We want all the live headers and deletes
         if options.all_live_headers_with_metadata {
-            for hash in headers {
+            // Abandoned headers are only returned with the metadata
+            let abandoned = meta_vault
+                .get_abandoned_headers(&reader, hash.clone())?
+                .collect::<Vec<_>>()?;
+            for hash in headers.into_iter().chain(abandoned) {
                 deletes.extend(
                     meta_vault
                         .get_deletes_on_header(&reader, hash.header_hash.clone())?
@@ -91,17 +99,6 @@ pub fn handle_get_entry(
                 let header_status = render_header_and_status(hash)?;
                 live_headers.insert(header_status.try_into()?);
             }
-            let updates_returns = meta_vault
-                .get_updates(&reader, hash.clone().into())?
-                .collect::<Vec<_>>()?;
-            let updates_returns = updates_returns.into_iter().map(|update| {
-                let update: WireHeaderStatus<WireUpdateRelationship> =
-                    render_header_and_status(update)?
-                        .try_into()
-                        .map_err(AuthorityDataError::from)?;
-                CascadeResult::Ok(update)
-            });
-            updates = updates_returns.collect::<Result<_, _>>()?;
 
         // We only want the headers if they are live and all deletes
         } else {
@@ -128,6 +125,21 @@ pub fn handle_get_entry(
             }
         }
 
+        // The updates are needed to find where the entry redirects to
+        if options.all_live_headers_with_metadata || options.follow_redirects {
+            let updates_returns = meta_vault
+                .get_updates(&reader, hash.clone().into())?
+                .collect::<Vec<_>>()?;
+            let updates_returns = updates_returns.into_iter().map(|update| {
+                let update: WireHeaderStatus<WireUpdateRelationship> =
+                    render_header_and_status(update)?
+                        .try_into()
+                        .map_err(AuthorityDataError::from)?;
+                CascadeResult::Ok(update)
+            });
+            updates = updates_returns.collect::<Result<_, _>>()?;
+        }
+
         let mut return_deletes = Vec::with_capacity(deletes.len());
         for delete in deletes {
             let header = render_header_and_status(delete?)?;
@@ -183,10 +195,13 @@ pub fn handle_get_element(
     let element_vault = ElementBuf::vault(env.clone().into(), false)?;
     let meta_vault = MetadataBuf::vault(env.clone().into())?;
     let element_rejected = ElementBuf::rejected(env.clone().into())?;
+    let element_abandoned = ElementBuf::abandoned(env.clone().into())?;
 
     // Check that we have the authority to serve this request because we have
     // done the StoreElement validation
-    if !meta_vault.has_any_registered_store_element(&hash)? {
+    if !meta_vault.has_any_registered_store_element(&hash)?
+        && !meta_vault.has_abandoned_registered_store_element(&hash)?
+    {
         return Ok(GetElementResponse::GetHeader(None));
     }
 
@@ -239,6 +254,11 @@ pub fn handle_get_element(
         r = element_rejected.get_element(&hash)?;
         status = ValidationStatus::Rejected;
     }
+    // Abandoned elements are returned without their entry
+    if r.is_none() {
+        r = element_abandoned.get_element(&hash)?;
+        status = ValidationStatus::Abandoned;
+    }
     let r = r
         .map(|e| WireElement::from_element(ElementStatus::new(e, status), deletes, updates))
         .map(Box::new);
@@ -263,6 +283,7 @@ pub fn handle_get_agent_activity(
         .get_activity_status(&agent)?
         .unwrap_or(ChainStatus::Empty);
     let highest_observed = meta_integrated.get_activity_observed(&agent)?;
+    let warrants = meta_integrated.get_warrants(&agent)?;
 
     // Valid headers
     let valid_activity = if options.include_valid_activity {
@@ -302,6 +323,7 @@ pub fn handle_get_agent_activity(
         agent,
         status,
         highest_observed,
+        warrants,
     })
 }
 
@@ -393,11 +415,11 @@ fn _show_agent_activity_read_times(env: EnvironmentRead, agent: AgentPubKey) {
     });
 }
 
-#[instrument(skip(env, _options))]
+#[instrument(skip(env, options))]
 pub fn handle_get_links(
     env: EnvironmentRead,
     link_key: WireLinkMetaKey,
-    _options: holochain_p2p::event::GetLinksOptions,
+    options: holochain_p2p::event::GetLinksOptions,
 ) -> CascadeResult<GetLinksResponse> {
     // Get the vaults
     let env_ref = env.guard();
@@ -405,28 +427,28 @@ pub fn handle_get_links(
     let element_vault = ElementBuf::vault(env.clone(), false)?;
     let meta_vault = MetadataBuf::vault(env.clone())?;
 
-    let links = meta_vault
-        .get_links_all(&reader, &LinkMetaKey::from(&link_key))?
+    // Only the link adds in the requested page.
+    // Deleted links are still returned so the requester can merge
+    // the removes on them with what other authorities return.
+    let (links, _) =
+        meta_vault.get_links_page(&reader, &LinkMetaKey::from(&link_key), &options.page)?;
+    let links = links
+        .into_iter()
         .map(|link_add| {
             // Collect the link removes on this link add
             let link_removes = meta_vault
                 .get_link_removes_on_link_add(&reader, link_add.link_add_hash.clone())?
                 .collect::<BTreeSet<_>>()?;
-            // Create timed header hash
-            let link_add = TimedHeaderHash {
-                timestamp: link_add.timestamp,
-                header_hash: link_add.link_add_hash,
-            };
             // Return all link removes with this link add
-            Ok((link_add, link_removes))
+            Ok((link_add.link_add_hash, link_removes))
         })
-        .collect::<BTreeMap<_, _>>()?;
+        .collect::<CascadeResult<Vec<_>>>()?;
 
     // Get the headers from the element stores
     let mut result_adds: Vec<(CreateLink, Signature)> = Vec::with_capacity(links.len());
     let mut result_removes: Vec<(DeleteLink, Signature)> = Vec::with_capacity(links.len());
     for (link_add, link_removes) in links {
-        if let Some(link_add) = element_vault.get_header(&link_add.header_hash)? {
+        if let Some(link_add) = element_vault.get_header(&link_add)? {
             for link_remove in link_removes {
                 if let Some(link_remove) = element_vault.get_header(&link_remove.header_hash)? {
                     let (h, s) = link_remove.into_header_and_signature();
@@ -26,6 +26,7 @@ use holochain_state::prelude::*;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::convert::TryFrom;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -310,7 +311,8 @@ where
             highest_observed,
             valid_activity,
             rejected_activity,
-            ..
+            // Cache any warrants that check out
+            warrants,
         } = agent_activity;
         match valid_activity {
             ChainItems::Full(headers) => {
@@ -365,12 +367,34 @@ where
                 .meta
                 .register_activity_observed(&agent, highest_observed)?;
         }
+        for warrant in warrants {
+            // Don't trust warrants that aren't about this agent
+            // or don't have valid evidence.
+            if warrant.agent() != &agent {
+                warn!(?warrant, "Authority returned a warrant for the wrong agent");
+                continue;
+            }
+            // The signature only proves the agent authored the header,
+            // not that the header is invalid, so these aren't cached
+            // without validating the header ourselves.
+            if let Warrant::InvalidHeader(_) = warrant {
+                continue;
+            }
+            match warrant.verify().await {
+                Ok(true) => cache_data.meta.register_warrant(warrant)?,
+                Ok(false) => warn!(?warrant, "Authority returned an invalid warrant"),
+                Err(e) => warn!(?warrant, ?e, "Failed to verify warrant"),
+            }
+        }
         Ok(())
     }
 
     fn update_stores(&mut self, element_status: ElementStatus) -> CascadeResult<()> {
         let cache_data = ok_or_return!(self.cache_data.as_mut());
         let ElementStatus { element, status } = element_status;
+        if let ValidationStatus::Abandoned = status {
+            return self.put_abandoned_header_in_cache(element.into_inner().0);
+        }
         let op_lights = produce_op_lights_from_elements(vec![&element])?;
         let (shh, e) = element.into_inner();
         cache_data
@@ -383,6 +407,20 @@ where
         Ok(())
     }
 
+    /// Abandoned headers have no entry and are not integrated
+    /// as metadata so they are kept apart from valid headers.
+    fn put_abandoned_header_in_cache(&mut self, shh: SignedHeaderHashed) -> CascadeResult<()> {
+        let cache_data = ok_or_return!(self.cache_data.as_mut());
+        cache_data
+            .meta
+            .register_validation_status(shh.header_address().clone(), ValidationStatus::Abandoned);
+        if let Ok(h) = NewEntryHeader::try_from(shh.header().clone()) {
+            cache_data.meta.register_abandoned_header(h)?;
+        }
+        cache_data.element.put(shh, None)?;
+        Ok(())
+    }
+
     // HACK: This is dumb but correct and will be easily
     // avoided with indexing.
     // This just the fastest way to implement getting the integrated
@@ -550,6 +588,11 @@ where
                     entry_type,
                     updates,
                 } = *raw;
+                // Abandoned headers can't be part of an element group
+                let (abandoned, live_headers): (Vec<_>, Vec<_>) = live_headers
+                    .into_iter()
+                    .partition(|h| matches!(h.validation_status, ValidationStatus::Abandoned));
+                let abandoned_type = entry_type.clone();
                 let entry_hash = if !live_headers.is_empty() {
                     let elements =
                         ElementGroup::from_wire_elements(live_headers, entry_type, entry)?;
@@ -559,6 +602,12 @@ where
                 } else {
                     EntryHash::with_data_sync(&entry)
                 };
+                for header in abandoned {
+                    let shh = header
+                        .header
+                        .into_header(abandoned_type.clone(), entry_hash.clone());
+                    self.put_abandoned_header_in_cache(shh)?;
+                }
                 for delete in deletes {
                     let element_status = delete.into_element_status();
                     self.update_stores(element_status)?;
@@ -651,6 +700,8 @@ where
         Ok(network.get_meta(basis.clone(), options).await?)
     }
 
+    /// Cache the links from our integrated data, or from network responses
+    /// that have been through [GetLinksResponse::merge] to check their signatures.
     fn put_link_in_cache(&mut self, response: GetLinksResponse) -> CascadeResult<()> {
         let GetLinksResponse {
             link_adds,
@@ -663,7 +714,7 @@ where
                 SignedHeaderHashed::from_content_sync(SignedHeader(link_add.into(), signature)),
                 None,
             );
-            // TODO: Assuming links are also valid headers.
+            // TODO: Assuming signed links are also valid headers.
             // We will need to prove this is the case in the future.
             self.update_stores(ElementStatus::new(element, ValidationStatus::Valid))?;
         }
@@ -673,7 +724,7 @@ where
                 SignedHeaderHashed::from_content_sync(SignedHeader(link_remove.into(), signature)),
                 None,
             );
-            // TODO: Assuming links are also valid headers.
+            // TODO: Assuming signed links are also valid headers.
             // We will need to prove this is the case in the future.
             self.update_stores(ElementStatus::new(element, ValidationStatus::Valid))?;
         }
@@ -690,9 +741,10 @@ where
         let network = ok_or_return!(self.network.as_mut());
         let results = network.get_links(link_key, options).await?;
 
-        for response in results {
-            self.put_link_in_cache(response)?;
-        }
+        // Take the union of what every authority returned
+        // so a link or removal is cached if any of them knows about it.
+        // Only headers signed by their authors are kept.
+        self.put_link_in_cache(GetLinksResponse::merge(results).await)?;
         Ok(())
     }
 
@@ -833,6 +885,16 @@ where
                     .chain(authored_data.meta.get_rejected_headers(&r, hash.clone())?)
                     .collect::<BTreeSet<_>>()?;
 
+                // Get the abandoned "headers that created this entry" hashes.
+                // These are cached from authorities or held in our own abandoned store.
+                let meta_vault = MetadataBuf::vault(env.clone())?;
+                let element_abandoned = ElementBuf::abandoned(env.clone())?;
+                let abandoned_headers = cache_data
+                    .meta
+                    .get_abandoned_headers(&r, hash.clone())?
+                    .chain(meta_vault.get_abandoned_headers(&r, hash.clone())?)
+                    .collect::<BTreeSet<_>>()?;
+
                 // Get the delete hashes
                 let deletes = cache_data
                     .meta
@@ -857,12 +919,22 @@ where
                 let rejected_headers = self.render_headers(rejected_headers, |h| {
                     h == HeaderType::Update || h == HeaderType::Create
                 })?;
+                let mut abandoned = Vec::with_capacity(abandoned_headers.len());
+                for thh in abandoned_headers {
+                    match element_abandoned.get_header_with_reader(&r, &thh.header_hash)? {
+                        Some(h) => abandoned.push(h),
+                        None => abandoned.extend(self.render_headers(Some(thh), |h| {
+                            h == HeaderType::Update || h == HeaderType::Create
+                        })?),
+                    }
+                }
                 let deletes = self.render_headers(deletes, |h| h == HeaderType::Delete)?;
                 let updates = self.render_headers(updates, |h| h == HeaderType::Update)?;
                 Ok(Some(EntryDetails {
                     entry: entry.into_content(),
                     headers,
                     rejected_headers,
+                    abandoned_headers: abandoned,
                     deletes,
                     updates,
                     entry_dht_status,
@@ -876,8 +948,15 @@ where
         let cache_data = ok_or_return!(self.cache_data.as_ref(), None);
         let authored_data = ok_or_return!(self.authored_data.as_ref(), None);
         let env = ok_or_return!(self.env.as_ref(), None);
-        match self.get_element_local_raw(&hash)? {
-            Some(element) => {
+        let mut element = self.get_element_local_raw(&hash)?.map(|e| (e, None));
+        // Abandoned elements are kept in their own store without their entry
+        if element.is_none() {
+            element = ElementBuf::abandoned(env.clone())?
+                .get_element(&hash)?
+                .map(|e| (e, Some(ValidationStatus::Abandoned)));
+        }
+        match element {
+            Some((element, abandoned)) => {
                 let hash = element.header_address().clone();
                 let (deletes, updates, validation_status) = fresh_reader!(env, |r| {
                     let deletes = cache_data
@@ -893,8 +972,8 @@ where
                     let validation_status = cache_data.meta.get_validation_status(&r, &hash)?;
                     DatabaseResult::Ok((deletes, updates, validation_status))
                 })?;
-                let validation_status = validation_status
-                    .resolve()
+                let validation_status = abandoned
+                    .or_else(|| validation_status.resolve())
                     .unwrap_or(ValidationStatus::Valid);
                 let deletes = self.render_headers(deletes, |h| h == HeaderType::Delete)?;
                 let updates = self.render_headers(updates, |h| h == HeaderType::Update)?;
@@ -1100,15 +1179,197 @@ where
         })
     }
 
+    /// The [Update]s on this basis in the cache, authored and integrated
+    /// stores, newest first, without any deleted in one of those stores.
+    fn get_live_updates_local(&self, basis: AnyDhtHash) -> CascadeResult<Vec<TimedHeaderHash>> {
+        let env = ok_or_return!(self.env.as_ref(), Vec::new());
+        fresh_reader!(env, |r| {
+            let mut updates = BTreeSet::new();
+            if let Some(db) = self.cache_data.as_ref() {
+                updates.extend(
+                    db.meta
+                        .get_updates(&r, basis.clone())?
+                        .collect::<Vec<_>>()?,
+                );
+            }
+            if let Some(db) = self.authored_data.as_ref() {
+                updates.extend(
+                    db.meta
+                        .get_updates(&r, basis.clone())?
+                        .collect::<Vec<_>>()?,
+                );
+            }
+            if let Some(db) = self.integrated_data.as_ref() {
+                updates.extend(
+                    db.meta
+                        .get_updates(&r, basis.clone())?
+                        .collect::<Vec<_>>()?,
+                );
+            }
+            // A delete in any store hides the update in every store
+            let is_deleted = |hash: &HeaderHash| {
+                if let Some(db) = self.cache_data.as_ref() {
+                    if db
+                        .meta
+                        .get_deletes_on_header(&r, hash.clone())?
+                        .next()?
+                        .is_some()
+                    {
+                        return DatabaseResult::Ok(true);
+                    }
+                }
+                if let Some(db) = self.authored_data.as_ref() {
+                    if db
+                        .meta
+                        .get_deletes_on_header(&r, hash.clone())?
+                        .next()?
+                        .is_some()
+                    {
+                        return Ok(true);
+                    }
+                }
+                if let Some(db) = self.integrated_data.as_ref() {
+                    if db
+                        .meta
+                        .get_deletes_on_header(&r, hash.clone())?
+                        .next()?
+                        .is_some()
+                    {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            };
+            let mut live = Vec::with_capacity(updates.len());
+            for update in updates.into_iter().rev() {
+                if !is_deleted(&update.header_hash)? {
+                    live.push(update);
+                }
+            }
+            CascadeResult::Ok(live)
+        })
+    }
+
+    /// Find the newest redirect for this [Entry] across
+    /// the cache, authored and integrated stores.
+    /// The [Entry] it redirects to is read from the [Update] header,
+    /// so an update whose header isn't held is skipped for the next newest.
+    fn get_entry_redirect_local(
+        &self,
+        entry_hash: &EntryHash,
+    ) -> CascadeResult<Option<(TimedHeaderHash, EntryHash)>> {
+        for update in self.get_live_updates_local(entry_hash.clone().into())? {
+            if let Some(header) = self.get_header_local_raw_with_sig(&update.header_hash)? {
+                if let Header::Update(u) = header.header() {
+                    let next = u.entry_hash.clone();
+                    return Ok(Some((update, next)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the newest redirect for this [Header] across
+    /// the cache, authored and integrated stores.
+    fn get_header_redirect_local(
+        &self,
+        header_hash: &HeaderHash,
+    ) -> CascadeResult<Option<TimedHeaderHash>> {
+        Ok(self
+            .get_live_updates_local(header_hash.clone().into())?
+            .into_iter()
+            .next())
+    }
+
+    /// Fetch the updates on this hash so the next
+    /// redirect can be found.
+    /// The content strategy only uses what is already local.
+    async fn fetch_redirects(
+        &mut self,
+        hash: AnyDhtHash,
+        options: &GetOptions,
+    ) -> CascadeResult<()> {
+        if let GetStrategy::Content = options.strategy {
+            return Ok(());
+        }
+        let network_options: NetworkGetOptions = options.clone().into();
+        if self.am_i_an_authority(hash.clone()).await? {
+            // Short circuit as the authority
+            self.update_cache_from_integrated(hash, network_options)?;
+        } else {
+            match *hash.hash_type() {
+                AnyDht::Entry => {
+                    self.fetch_element_via_entry(hash.into(), network_options)
+                        .await?
+                }
+                AnyDht::Header => {
+                    self.fetch_element_via_header(hash.into(), network_options)
+                        .await?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, options))]
+    /// Follow the updates on an [Entry] to its newest version.
+    /// Each hop must be newer than the last so an update back
+    /// to an earlier version doesn't loop forever.
+    async fn follow_entry_redirects(
+        &mut self,
+        mut entry_hash: EntryHash,
+        options: &GetOptions,
+    ) -> CascadeResult<EntryHash> {
+        let mut last: Option<TimedHeaderHash> = None;
+        loop {
+            self.fetch_redirects(entry_hash.clone().into(), options)
+                .await?;
+            match self.get_entry_redirect_local(&entry_hash)? {
+                Some((update, next)) if last.as_ref().map_or(true, |last| update > *last) => {
+                    last = Some(update);
+                    entry_hash = next;
+                }
+                _ => return Ok(entry_hash),
+            }
+        }
+    }
+
+    #[instrument(skip(self, options))]
+    /// Follow the updates on a [Header] to its newest version.
+    async fn follow_header_redirects(
+        &mut self,
+        mut header_hash: HeaderHash,
+        options: &GetOptions,
+    ) -> CascadeResult<HeaderHash> {
+        loop {
+            self.fetch_redirects(header_hash.clone().into(), options)
+                .await?;
+            match self.get_header_redirect_local(&header_hash)? {
+                Some(update) => header_hash = update.header_hash,
+                None => return Ok(header_hash),
+            }
+        }
+    }
+
     #[instrument(skip(self, options))]
     /// Returns the oldest live [Element] for this [EntryHash] by getting the
     /// latest available metadata from authorities combined with this agents authored data.
+    /// If the options follow redirects this is the oldest live [Element]
+    /// of the newest version of the [Entry].
     pub async fn dht_get_entry(
         &mut self,
         entry_hash: EntryHash,
-        options: GetOptions,
+        mut options: GetOptions,
     ) -> CascadeResult<Option<Element>> {
         debug!("in get entry");
+        let entry_hash = if options.follow_redirects {
+            let entry_hash = self.follow_entry_redirects(entry_hash, &options).await?;
+            // Already at the newest version
+            options.follow_redirects = false;
+            entry_hash
+        } else {
+            entry_hash
+        };
         let get_call = options.strategy;
         let mut oldest_live_element = Search::NotInCascade;
         let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
@@ -1190,12 +1451,22 @@ where
     /// Returns the [Element] for this [HeaderHash] if it is live
     /// by getting the latest available metadata from authorities
     /// combined with this agents authored data.
+    /// If the options follow redirects this is the [Element]
+    /// for the newest update of the [Header].
     /// _Note: Deleted headers are a tombstone set_
     pub async fn dht_get_header(
         &mut self,
         header_hash: HeaderHash,
-        options: GetOptions,
+        mut options: GetOptions,
     ) -> CascadeResult<Option<Element>> {
+        let header_hash = if options.follow_redirects {
+            let header_hash = self.follow_header_redirects(header_hash, &options).await?;
+            // Already at the newest version
+            options.follow_redirects = false;
+            header_hash
+        } else {
+            header_hash
+        };
         let cache_data = ok_or_return!(self.cache_data.as_ref(), None);
         let integrated_data = ok_or_return!(self.integrated_data.as_ref(), None);
         let authored_data = ok_or_return!(self.authored_data.as_ref(), None);
@@ -1569,6 +1840,60 @@ where
         })
     }
 
+    #[instrument(skip(self, key, options))]
+    /// Gets a page of the live links on a base.
+    /// The page is taken from the union of the links the authorities
+    /// return for it and the links we authored, so the `next` cursor
+    /// moves past deleted links that aren't in the page.
+    pub async fn dht_get_links_page<'link>(
+        &mut self,
+        key: &'link LinkMetaKey<'link>,
+        options: GetLinksOptions,
+    ) -> CascadeResult<LinkPage> {
+        let page = options.page.clone();
+        if self.am_i_an_authority(key.base().clone().into()).await? {
+            // Short circuit. This makes sense for full sharding.
+            self.update_link_cache_from_integrated(key, options)?;
+        } else {
+            // Update the cache from the network
+            self.fetch_links(key.into(), options).await?;
+        }
+
+        let cache_data = ok_or_return!(self.cache_data.as_ref(), LinkPage::default());
+        let authored_data = ok_or_return!(self.authored_data.as_ref(), LinkPage::default());
+        let env = ok_or_return!(self.env.as_ref(), LinkPage::default());
+        fresh_reader!(env, |r| {
+            // Page the links in the cache and authored together
+            let (mut links, _) = cache_data.meta.get_links_page(&r, key, &page)?;
+            let (authored, _) = authored_data.meta.get_links_page(&r, key, &page)?;
+            links.extend(authored);
+            let (links, next) = page.page(links, |l| (l.timestamp, l.link_add_hash.clone()));
+
+            // Return any links from the page that don't have removes
+            let mut live_links = Vec::with_capacity(links.len());
+            for link in links {
+                let hash = link.link_add_hash.clone();
+                if cache_data
+                    .meta
+                    .get_link_removes_on_link_add(&r, hash.clone())?
+                    .next()?
+                    .is_none()
+                    && authored_data
+                        .meta
+                        .get_link_removes_on_link_add(&r, hash)?
+                        .next()?
+                        .is_none()
+                {
+                    live_links.push(link.into_link());
+                }
+            }
+            Ok(LinkPage {
+                links: live_links,
+                next,
+            })
+        })
+    }
+
     #[instrument(skip(self, key, options))]
     /// Return all CreateLink headers
     /// and DeleteLink headers ordered by time.
@@ -1644,6 +1969,9 @@ where
     ) -> CascadeResult<()> {
         let network = ok_or_return!(self.network.as_mut());
         let all_agent_activity = network.get_agent_activity(agent, query, options).await?;
+        // Every authority's response is merged into the cache.
+        // The metadata store keeps the highest observed header and
+        // turns disagreeing chain heads into a fork.
         for agent_activity in all_agent_activity {
             self.update_agent_activity_stores(agent_activity).await?;
         }
@@ -1812,6 +2140,7 @@ where
         );
         // Now try getting the latest activity from cache
         let highest_observed = cache_data.meta.get_activity_observed(&agent)?;
+        let warrants = cache_data.meta.get_warrants(&agent)?;
         match cache_data.meta.get_activity_status(&agent)? {
             Some(status) => Ok(AgentActivityResponse {
                 agent,
@@ -1819,6 +2148,7 @@ where
                 rejected_activity: ChainItems::NotRequested,
                 status,
                 highest_observed,
+                warrants,
             }),
             // If we don't have any status then we must return an empty chain
             None => Ok(AgentActivityResponse {
@@ -1827,6 +2157,7 @@ where
                 rejected_activity: ChainItems::NotRequested,
                 status: ChainStatus::Empty,
                 highest_observed,
+                warrants,
             }),
         }
     }
@@ -1,7 +1,14 @@
+use crate::auth::InterfaceToken;
+use crate::auth::IssueTokenPayload;
+use crate::auth::IssuedToken;
+use crate::config::InterfaceTlsConfig;
+use crate::state_dump::CellStateDump;
+use crate::state_dump::DumpStateOptions;
 use holo_hash::*;
 use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::net::IpAddr;
 
 /// Represents the available conductor functions to call over an Admin interface
 /// and will result in a corresponding [`AdminResponse`] message being sent back over the
@@ -123,6 +130,43 @@ pub enum AdminRequest {
         /// The InstalledAppId to deactivate
         installed_app_id: InstalledAppId,
     },
+    /// Removes the `App` specified by argument `installed_app_id` from the conductor,
+    /// whether it is active or inactive. The App's Cells leave the network and
+    /// all of their data is deleted, unless a Cell is also part of another installed App.
+    /// **This can't be undone.**
+    ///
+    /// Will be responded to with an [`AdminResponse::AppUninstalled`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppUninstalled`]: enum.AdminResponse.html#variant.AppUninstalled
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    UninstallApp {
+        /// The InstalledAppId to uninstall
+        installed_app_id: InstalledAppId,
+    },
+    /// Migrates the agent in the Cell specified by `cell_nick` in an active `App`
+    /// to a new version of that Cell's `Dna`.
+    /// The `migrate_agent` callback is run on the old and new chains,
+    /// the old chain is closed with a `CloseChain` header and the new chain is
+    /// opened with an `OpenChain` header, so the agent's history can be followed.
+    /// The `App` keeps its `installed_app_id` and uses the new Cell from then on.
+    /// The new `Dna` must already be installed.
+    ///
+    /// Will be responded to with an [`AdminResponse::AgentMigrated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AgentMigrated`]: enum.AdminResponse.html#variant.AgentMigrated
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    MigrateAgent {
+        /// The InstalledAppId of the app the Cell is in
+        installed_app_id: InstalledAppId,
+        /// The CellNick of the Cell to migrate
+        cell_nick: CellNick,
+        /// The hash of the new version of the Dna
+        new_dna_hash: DnaHash,
+        /// A membrane proof for joining the new Dna
+        membrane_proof: Option<MembraneProof>,
+    },
     /// Open up a new websocket interface at the networking port
     /// (optionally) specified by argument `port` (or using any free port if argument `port` is `None`)
     /// over which you can then use the [`AppRequest`] API.
@@ -139,9 +183,47 @@ pub enum AdminRequest {
         /// Optional port, use None to let the
         /// OS choose a free port
         port: Option<u16>,
+        /// If true, each connection to the interface must authenticate
+        /// with a token issued via [`AdminRequest::IssueInterfaceToken`]
+        ///
+        /// [`AdminRequest::IssueInterfaceToken`]: enum.AdminRequest.html#variant.IssueInterfaceToken
+        #[serde(default)]
+        require_token: bool,
+        /// Optional address to listen on, use None to only accept
+        /// connections from `127.0.0.1`
+        #[serde(default)]
+        bind_address: Option<IpAddr>,
+        /// Optional certificate and key paths on the conductor's machine.
+        /// If set, clients must connect to the interface over TLS ("wss").
+        /// A self-signed certificate is generated if neither file exists.
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
     },
-    /// Dump the full state of the `Cell` specified by argument `cell_id`,
-    /// including its chain, as a string containing JSON.
+    /// Open up a new app interface on a unix domain socket at the path
+    /// specified by argument `path`, over which you can then use the [`AppRequest`] API.
+    /// No TCP port is opened, and only processes running as the same user as the
+    /// conductor can connect.
+    /// Any active `App` will be callable via this interface.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppUnixSocketAttached`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppUnixSocketAttached`]: enum.AdminResponse.html#variant.AppUnixSocketAttached
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    AttachAppUnixSocket {
+        /// The path at which to create the socket
+        path: std::path::PathBuf,
+        /// If true, each connection to the interface must authenticate
+        /// with a token issued via [`AdminRequest::IssueInterfaceToken`]
+        ///
+        /// [`AdminRequest::IssueInterfaceToken`]: enum.AdminRequest.html#variant.IssueInterfaceToken
+        #[serde(default)]
+        require_token: bool,
+    },
+    /// Dump the state of the `Cell` specified by argument `cell_id`:
+    /// its source chain, how many ops are waiting for validation and
+    /// integration, the validation receipts for its authored ops
+    /// and the size of each of its databases.
     ///
     /// Will be responded to with an [`AdminResponse::StateDumped`]
     /// or an [`AdminResponse::Error`]
@@ -151,6 +233,24 @@ pub enum AdminRequest {
     DumpState {
         /// The `CellId` for which to dump state
         cell_id: Box<CellId>,
+        /// What to include in the dump
+        #[serde(default)]
+        options: DumpStateOptions,
+    },
+    /// List the validation receipts the `Cell` specified by argument `cell_id`
+    /// has received for one of its authored ops, including any receipts
+    /// from validators that rejected or abandoned the op.
+    ///
+    /// Will be responded to with an [`AdminResponse::ValidationReceipts`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::ValidationReceipts`]: enum.AdminResponse.html#variant.ValidationReceipts
+    GetValidationReceipts {
+        /// The `CellId` that authored the op
+        cell_id: Box<CellId>,
+        /// The hash of the op to list receipts for
+        dht_op_hash: DhtOpHash,
     },
     /// Add a list [AgentInfoSigned] to this conductor's peer store.
     /// This is another way of finding peers on a dht.
@@ -177,6 +277,47 @@ pub enum AdminRequest {
         /// Optionally choose a specific agent info
         cell_id: Option<CellId>,
     },
+    /// Issue a token which can be used to authenticate a connection to an
+    /// interface which requires one. See [`IssueTokenPayload`] for details.
+    ///
+    /// Tokens are only held in memory, so all tokens are invalidated
+    /// when the conductor restarts.
+    ///
+    /// Will be responded to with an [`AdminResponse::InterfaceTokenIssued`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`IssueTokenPayload`]: ../auth/struct.IssueTokenPayload.html
+    /// [`AdminResponse::InterfaceTokenIssued`]: enum.AdminResponse.html#variant.InterfaceTokenIssued
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    IssueInterfaceToken(IssueTokenPayload),
+    /// Revoke a previously issued token.
+    /// Connections which authenticated with the token can no longer
+    /// make requests or receive signals.
+    ///
+    /// Will be responded to with an [`AdminResponse::InterfaceTokenRevoked`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::InterfaceTokenRevoked`]: enum.AdminResponse.html#variant.InterfaceTokenRevoked
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    RevokeInterfaceToken {
+        /// The token to revoke
+        token: InterfaceToken,
+    },
+    /// Authenticate this connection with an admin token.
+    /// This must be the first request on a connection to an Admin interface
+    /// which requires a token.
+    ///
+    /// Will be responded to with an [`AdminResponse::Authenticated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Authenticated`]: enum.AdminResponse.html#variant.Authenticated
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    Authenticate {
+        /// A token with [`TokenScope::Admin`]
+        ///
+        /// [`TokenScope::Admin`]: ../auth/enum.TokenScope.html#variant.Admin
+        token: InterfaceToken,
+    },
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -257,6 +398,15 @@ pub enum AdminResponse {
         /// Networking port of the new `AppInterfaceApi`
         port: u16,
     },
+    /// The succesful response to an [`AdminRequest::AttachAppUnixSocket`].
+    ///
+    /// `AppInterfaceApi` successfully attached on the unix domain socket.
+    ///
+    /// [`AdminRequest::AttachAppUnixSocket`]: enum.AdminRequest.html#variant.AttachAppUnixSocket
+    AppUnixSocketAttached {
+        /// Path of the unix domain socket of the new `AppInterfaceApi`
+        path: std::path::PathBuf,
+    },
     /// The succesful response to an [`AdminRequest::ActivateApp`].
     ///
     /// It means the `App` was activated successfully
@@ -269,13 +419,28 @@ pub enum AdminResponse {
     ///
     /// [`AdminRequest::DeactivateApp`]: enum.AdminRequest.html#variant.DeactivateApp
     AppDeactivated,
-    /// The succesful response to an [`AdminRequest::DumpState`].
+    /// The succesful response to an [`AdminRequest::UninstallApp`].
+    ///
+    /// It means the `App` was removed and its Cells' data was deleted.
+    ///
+    /// [`AdminRequest::UninstallApp`]: enum.AdminRequest.html#variant.UninstallApp
+    AppUninstalled,
+    /// The succesful response to an [`AdminRequest::MigrateAgent`].
     ///
-    /// The result contains a string of serialized JSON data which can be deserialized to access the
-    /// full state dump, and inspect the source chain.
+    /// Contains the [`CellId`] of the new Cell.
+    ///
+    /// [`AdminRequest::MigrateAgent`]: enum.AdminRequest.html#variant.MigrateAgent
+    AgentMigrated(CellId),
+    /// The succesful response to an [`AdminRequest::DumpState`].
     ///
     /// [`AdminRequest::DumpState`]: enum.AdminRequest.html#variant.DumpState
-    StateDumped(String),
+    StateDumped(Box<CellStateDump>),
+    /// The succesful response to an [`AdminRequest::GetValidationReceipts`].
+    ///
+    /// Contains every signed receipt received for the op.
+    ///
+    /// [`AdminRequest::GetValidationReceipts`]: enum.AdminRequest.html#variant.GetValidationReceipts
+    ValidationReceipts(Vec<SignedValidationReceipt>),
     /// The succesful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -288,6 +453,22 @@ pub enum AdminResponse {
     ///
     /// [`AdminRequest::RequestAgentInfo`]: enum.AdminRequest.html#variant.RequestAgentInfo
     AgentInfoRequested(Vec<AgentInfoSigned>),
+    /// The succesful response to an [`AdminRequest::IssueInterfaceToken`].
+    ///
+    /// Contains the new token and when it expires.
+    ///
+    /// [`AdminRequest::IssueInterfaceToken`]: enum.AdminRequest.html#variant.IssueInterfaceToken
+    InterfaceTokenIssued(IssuedToken),
+    /// The succesful response to an [`AdminRequest::RevokeInterfaceToken`].
+    ///
+    /// [`AdminRequest::RevokeInterfaceToken`]: enum.AdminRequest.html#variant.RevokeInterfaceToken
+    InterfaceTokenRevoked,
+    /// The succesful response to an [`AdminRequest::Authenticate`].
+    ///
+    /// The connection may now make any other request.
+    ///
+    /// [`AdminRequest::Authenticate`]: enum.AdminRequest.html#variant.Authenticate
+    Authenticated,
 }
 
 /// Error type that goes over the websocket wire.
@@ -311,6 +492,9 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized
     ZomeCallUnauthorized(String),
+    /// The connection has not authenticated with a valid token,
+    /// or the token does not allow this request
+    Unauthorized(String),
 }
 
 impl ExternalApiWireError {
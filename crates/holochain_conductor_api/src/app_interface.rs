@@ -1,4 +1,4 @@
-use crate::{signal_subscription::SignalSubscription, ExternalApiWireError};
+use crate::{auth::InterfaceToken, signal_subscription::SignalSubscription, ExternalApiWireError};
 use holo_hash::AgentPubKey;
 use holochain_types::prelude::*;
 
@@ -39,11 +39,35 @@ pub enum AppRequest {
     /// DEPRECATED. Use `ZomeCall`.
     ZomeCallInvocation(Box<ZomeCall>),
 
-    /// Update signal subscriptions.
+    /// Update the signal subscription for an App on this connection.
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// Replaces any previous subscription for the same App. Signals from the App's
+    /// Cells are only sent over this connection if they are allowed by the filters.
+    /// Each connection starts out subscribed to all signals.
+    ///
+    /// Will be responded to with an [`AppResponse::SignalSubscriptionUpdated`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::SignalSubscriptionUpdated`]: enum.AppResponse.html#variant.SignalSubscriptionUpdated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     SignalSubscription(SignalSubscription),
+
+    /// Authenticate this connection with an app token.
+    /// This must be the first request on a connection to an App interface
+    /// which requires a token. The connection can then only access the
+    /// Apps the token was issued for.
+    ///
+    /// Will be responded to with an [`AppResponse::Authenticated`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::Authenticated`]: enum.AppResponse.html#variant.Authenticated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    Authenticate {
+        /// A token with [`TokenScope::App`]
+        ///
+        /// [`TokenScope::App`]: ../auth/enum.TokenScope.html#variant.App
+        token: InterfaceToken,
+    },
 }
 
 /// Responses to requests received on an App interface
@@ -83,6 +107,16 @@ pub enum AppResponse {
 
     /// DEPRECATED. See `ZomeCall`.
     ZomeCallInvocation(Box<ExternIO>),
+
+    /// The successful response to an [`AppRequest::SignalSubscription`].
+    ///
+    /// [`AppRequest::SignalSubscription`]: enum.AppRequest.html#variant.SignalSubscription
+    SignalSubscriptionUpdated,
+
+    /// The successful response to an [`AppRequest::Authenticate`].
+    ///
+    /// [`AppRequest::Authenticate`]: enum.AppRequest.html#variant.Authenticate
+    Authenticated,
 }
 
 /// The data provided across an App interface in order to make a zome call
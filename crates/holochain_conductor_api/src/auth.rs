@@ -0,0 +1,75 @@
+//! Types for authenticating connections to Admin and App interfaces.
+//!
+//! Interfaces which are configured to require a token will reject every
+//! request on a connection, other than `Authenticate`, until a valid
+//! token has been presented on that connection.
+
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+/// The file in the conductor's environment directory which an admin token is
+/// written to at startup, if any admin interface requires a token.
+/// Only the user running the conductor can read it.
+pub const ADMIN_TOKEN_FILE: &str = "admin_token";
+
+/// An opaque token which authenticates a connection to an interface.
+#[derive(Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(transparent)]
+pub struct InterfaceToken(pub String);
+
+impl From<String> for InterfaceToken {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl std::fmt::Display for InterfaceToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak tokens into logs
+        write!(f, "InterfaceToken(..)")
+    }
+}
+
+impl std::fmt::Debug for InterfaceToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Requests and responses holding tokens are debug logged
+        write!(f, "InterfaceToken(..)")
+    }
+}
+
+/// What a connection authenticated with a token is allowed to do.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum TokenScope {
+    /// Full access to an Admin interface.
+    /// Admin tokens can't be used on App interfaces.
+    Admin,
+    /// Access to these Apps on an App interface.
+    /// Zome calls, app info and signals for any other App are rejected.
+    App(Vec<InstalledAppId>),
+}
+
+/// The payload for [`AdminRequest::IssueInterfaceToken`].
+///
+/// [`AdminRequest::IssueInterfaceToken`]: enum.AdminRequest.html#variant.IssueInterfaceToken
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct IssueTokenPayload {
+    /// What the token will give access to
+    pub scope: TokenScope,
+    /// How long the token is valid for.
+    /// `None` means the token is valid until it is revoked
+    /// or the conductor is restarted.
+    #[serde(default)]
+    pub expires_in_seconds: Option<u64>,
+}
+
+/// A newly issued token, the response to [`AdminRequest::IssueInterfaceToken`].
+///
+/// [`AdminRequest::IssueInterfaceToken`]: enum.AdminRequest.html#variant.IssueInterfaceToken
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct IssuedToken {
+    /// The token to pass to `Authenticate`
+    pub token: InterfaceToken,
+    /// When the token stops being valid, if ever
+    pub expires_at: Option<Timestamp>,
+}
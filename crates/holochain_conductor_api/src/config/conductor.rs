@@ -11,6 +11,7 @@ mod dpki_config;
 mod error;
 mod passphrase_service_config;
 pub mod paths;
+mod wasm_limits_config;
 //mod logger_config;
 //mod signal_config;
 pub use paths::EnvironmentRootPath;
@@ -20,6 +21,7 @@ pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use passphrase_service_config::PassphraseServiceConfig;
+pub use wasm_limits_config::*;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 use std::path::PathBuf;
@@ -75,6 +77,11 @@ pub struct ConductorConfig {
 
     /// Config options for the network module. Optional.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
+
+    /// Limits on the instructions, memory and time zome calls and callbacks can use.
+    /// If omitted, nothing is limited.
+    #[serde(default)]
+    pub wasm_limits: WasmLimitsConfig,
     //
     //
     // /// Which signals to emit
@@ -150,6 +157,7 @@ pub mod tests {
                 keystore_path: None,
                 admin_interfaces: None,
                 use_dangerous_test_keystore: false,
+                wasm_limits: Default::default(),
             }
         );
     }
@@ -176,6 +184,12 @@ pub mod tests {
       - driver:
           type: websocket
           port: 1234
+          bind_address: 0.0.0.0
+          tls:
+            cert_path: /path/to/admin.cert.pem
+            key_path: /path/to/admin.key.pem
+            hosts:
+              - conductor.example.com
 
     network:
       bootstrap_service: https://bootstrap.holo.host
@@ -196,6 +210,15 @@ pub mod tests {
         default_rpc_multi_timeout_ms: 42
         agent_info_expires_after_ms: 42
 
+    wasm_limits:
+      zome_call:
+        max_fuel: 100000000
+        max_memory_bytes: 16777216
+        timeout_ms: 30000
+      callback:
+        max_fuel: 10000000
+        timeout_ms: 5000
+
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         use holochain_p2p::kitsune_p2p::*;
@@ -236,9 +259,30 @@ pub mod tests {
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port: 1234 }
+                    driver: InterfaceDriver::Websocket {
+                        port: 1234,
+                        bind_address: Some([0, 0, 0, 0].into()),
+                        tls: Some(InterfaceTlsConfig {
+                            cert_path: PathBuf::from("/path/to/admin.cert.pem"),
+                            key_path: PathBuf::from("/path/to/admin.key.pem"),
+                            hosts: vec!["conductor.example.com".to_string()],
+                        }),
+                    },
+                    require_token: false,
                 }]),
                 network: Some(network_config),
+                wasm_limits: WasmLimitsConfig {
+                    zome_call: WasmCallLimits {
+                        max_fuel: Some(100000000),
+                        max_memory_bytes: Some(16777216),
+                        timeout_ms: Some(30000),
+                    },
+                    callback: WasmCallLimits {
+                        max_fuel: Some(10000000),
+                        max_memory_bytes: None,
+                        timeout_ms: Some(5000),
+                    },
+                },
             }
         );
     }
@@ -270,6 +314,7 @@ pub mod tests {
                 keystore_path: Some(PathBuf::from("/path/to/keystore").into()),
                 admin_interfaces: None,
                 use_dangerous_test_keystore: true,
+                wasm_limits: Default::default(),
             }
         );
     }
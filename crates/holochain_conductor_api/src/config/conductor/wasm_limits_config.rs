@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits on the resources a single call into a wasm zome can use.
+/// A limit that is not set is not enforced.
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq)]
+pub struct WasmCallLimits {
+    /// The most wasm instructions the call can run before it is stopped.
+    /// Every conductor counts the same instructions so this is deterministic.
+    pub max_fuel: Option<u64>,
+    /// The most linear memory the wasm can grow to.
+    /// This is rounded down to whole 64KiB wasm pages.
+    pub max_memory_bytes: Option<u64>,
+    /// The longest the call can take, including time spent in host functions
+    /// such as network gets, which fuel doesn't count.
+    pub timeout_ms: Option<u64>,
+}
+
+/// Limits on the resources zome calls and callbacks can use
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq)]
+pub struct WasmLimitsConfig {
+    /// Limits for zome functions called through an interface or by another zome
+    #[serde(default)]
+    pub zome_call: WasmCallLimits,
+    /// Limits for the callbacks the conductor calls,
+    /// such as `validate`, `init` and `post_commit`
+    #[serde(default)]
+    pub callback: WasmCallLimits,
+}
@@ -1,15 +1,20 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::net::IpAddr;
+use std::path::PathBuf;
 
 /// Information neeeded to spawn an Admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means will the interface be exposed?
-    /// Current only option is a local websocket running on a configurable port.
+    /// Either a local websocket running on a configurable port,
+    /// or a unix domain socket at a configurable path.
     pub driver: InterfaceDriver,
-    // /// How long will this interface be accessible between authentications?
-    // /// TODO: implement once we have authentication
-    // _session_duration_seconds: Option<u32>,
+    /// If true, each connection must present a token issued via
+    /// `AdminRequest::IssueInterfaceToken` with an `Authenticate` request
+    /// before any other request is accepted.
+    #[serde(default)]
+    pub require_token: bool,
 }
 
 /// Configuration for interfaces, specifying the means by which an interface
@@ -24,5 +29,40 @@ pub enum InterfaceDriver {
     Websocket {
         /// The port on which to establish the WebsocketListener
         port: u16,
+        /// The address to listen on. Defaults to `127.0.0.1`, so only local
+        /// clients can connect. Use e.g. `0.0.0.0` to accept remote clients.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bind_address: Option<IpAddr>,
+        /// If set, clients must connect over TLS ("wss") using this certificate
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls: Option<InterfaceTlsConfig>,
     },
+    /// An interface implemented via Websockets over a unix domain socket.
+    ///
+    /// No TCP port is opened. The socket file is created so that only the user
+    /// running the conductor can connect to it, so the filesystem permissions
+    /// of the socket and its directory control who can use the interface.
+    UnixSocket {
+        /// The path at which to create the socket.
+        /// Any stale socket already at this path is replaced.
+        path: PathBuf,
+    },
+}
+
+/// The certificate a websocket interface serves TLS ("wss") connections with.
+///
+/// If neither file exists when the interface starts, a self-signed certificate
+/// is generated and saved to these paths, so that it stays the same across
+/// restarts and can be given to clients to trust. It is valid for `localhost`,
+/// the loopback addresses, the bind address and the `hosts`.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct InterfaceTlsConfig {
+    /// Path to the PEM encoded certificate (chain)
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded PKCS #8 private key of the certificate
+    pub key_path: PathBuf,
+    /// Other host names and IP addresses that clients use to reach the
+    /// interface, which a generated certificate is also valid for
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
 }
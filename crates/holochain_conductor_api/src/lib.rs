@@ -1,7 +1,9 @@
 mod admin_interface;
 mod app_interface;
+pub mod auth;
 pub mod config;
 pub mod signal_subscription;
+pub mod state_dump;
 
 pub use admin_interface::*;
 pub use app_interface::*;
@@ -1,7 +1,10 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::signal::AppSignal;
+use holochain_zome_types::zome::ZomeName;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -13,6 +16,31 @@ pub struct SignalSubscription {
     filters: SignalFilterSet,
 }
 
+impl SignalSubscription {
+    /// Constructor
+    pub fn new(installed_app_id: InstalledAppId, filters: SignalFilterSet) -> Self {
+        Self {
+            installed_app_id,
+            filters,
+        }
+    }
+
+    /// The app for which to manage subscription
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// The per-cell filters
+    pub fn filters(&self) -> &SignalFilterSet {
+        &self.filters
+    }
+
+    /// Consume into the app id and the filters
+    pub fn into_inner(self) -> (InstalledAppId, SignalFilterSet) {
+        (self.installed_app_id, self.filters)
+    }
+}
+
 /// Associate a SignalFilter with each Cell in an App.
 /// The filtering can be interpreted as inclusive or exclusive,
 /// depending on the use case.
@@ -46,22 +74,165 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
-}
 
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-pub struct SignalFilter;
-
-impl Default for SignalFilter {
-    fn default() -> Self {
-        // The default is no filter
-        Self::empty()
+    /// Should this app signal be let through?
+    pub fn allows(&self, cell_id: &CellId, zome_name: &ZomeName, signal: &AppSignal) -> bool {
+        match self {
+            SignalFilterSet::Include(filters) => filters
+                .get(cell_id)
+                .map(|filter| filter.matches(zome_name, signal))
+                .unwrap_or(false),
+            SignalFilterSet::Exclude(filters) => !filters
+                .get(cell_id)
+                .map(|filter| filter.matches(zome_name, signal))
+                .unwrap_or(false),
+        }
     }
 }
 
+/// Specifies fine-grained filter controls for the signals from a single Cell.
+///
+/// A signal matches the filter if it was emitted by one of the zomes AND its
+/// type matches one of the type patterns. An empty set of zomes or patterns
+/// matches everything, so the empty filter matches every signal from the Cell.
+#[derive(
+    Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct SignalFilter {
+    /// Only match signals emitted by these zomes
+    #[serde(default)]
+    pub zomes: HashSet<ZomeName>,
+    /// Only match signals whose app-defined type matches one of these patterns.
+    /// A `*` in a pattern matches any sequence of characters,
+    /// e.g. `"chat.*"` matches `"chat.message"` and `"chat.typing"`.
+    /// Signals without a type never match a non-empty set of patterns.
+    #[serde(default)]
+    pub signal_types: Vec<String>,
+}
+
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        Self::default()
+    }
+
+    /// Only match signals from these zomes
+    pub fn zomes<I: IntoIterator<Item = ZomeName>>(mut self, zomes: I) -> Self {
+        self.zomes = zomes.into_iter().collect();
+        self
+    }
+
+    /// Only match signals with a type that matches one of these patterns
+    pub fn signal_types<I: IntoIterator<Item = String>>(mut self, patterns: I) -> Self {
+        self.signal_types = patterns.into_iter().collect();
+        self
+    }
+
+    /// Does this signal match the filter?
+    pub fn matches(&self, zome_name: &ZomeName, signal: &AppSignal) -> bool {
+        let zome_matches = self.zomes.is_empty() || self.zomes.contains(zome_name);
+        let type_matches = self.signal_types.is_empty()
+            || signal.signal_type().map_or(false, |signal_type| {
+                self.signal_types
+                    .iter()
+                    .any(|pattern| pattern_matches(pattern, signal_type))
+            });
+        zome_matches && type_matches
+    }
+}
+
+/// Match a string against a pattern where `*` matches any sequence of characters.
+fn pattern_matches(pattern: &str, s: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // There is always a first part, which must be a prefix
+    let first = parts.next().unwrap_or_default();
+    if !s.starts_with(first) {
+        return false;
+    }
+    let mut rest = &s[first.len()..];
+    let mut parts: Vec<&str> = parts.collect();
+    // With no wildcards the pattern must match exactly
+    let last = match parts.pop() {
+        Some(last) => last,
+        None => return rest.is_empty(),
+    };
+    // Each middle part must appear in order
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    // The last part must be a suffix of what remains
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::test_utils::fake_cell_id;
+    use holochain_zome_types::ExternIO;
+
+    fn signal(signal_type: Option<&str>) -> AppSignal {
+        let payload = ExternIO::encode(()).unwrap();
+        match signal_type {
+            Some(signal_type) => AppSignal::typed(signal_type.into(), payload),
+            None => AppSignal::new(payload),
+        }
+    }
+
+    #[test]
+    fn patterns() {
+        assert!(pattern_matches("chat", "chat"));
+        assert!(!pattern_matches("chat", "chats"));
+        assert!(pattern_matches("*", ""));
+        assert!(pattern_matches("chat.*", "chat.message"));
+        assert!(!pattern_matches("chat.*", "chat"));
+        assert!(pattern_matches("*.message", "chat.message"));
+        assert!(pattern_matches("c*t.*e", "chat.message"));
+        assert!(!pattern_matches("c*t.*x", "chat.message"));
+        assert!(!pattern_matches("a*a", "a"));
+    }
+
+    #[test]
+    fn filter_by_zome_and_type() {
+        let zome_a: ZomeName = "a".into();
+        let zome_b: ZomeName = "b".into();
+
+        let filter = SignalFilter::empty();
+        assert!(filter.matches(&zome_a, &signal(None)));
+
+        let filter = SignalFilter::empty()
+            .zomes(vec![zome_a.clone()])
+            .signal_types(vec!["chat.*".to_string()]);
+        assert!(filter.matches(&zome_a, &signal(Some("chat.message"))));
+        assert!(!filter.matches(&zome_b, &signal(Some("chat.message"))));
+        assert!(!filter.matches(&zome_a, &signal(Some("presence"))));
+        assert!(!filter.matches(&zome_a, &signal(None)));
+    }
+
+    #[test]
+    fn filter_sets() {
+        let zome: ZomeName = "a".into();
+        let cell_1 = fake_cell_id(1);
+        let cell_2 = fake_cell_id(2);
+        let typed = signal(Some("chat"));
+        let untyped = signal(None);
+        let chat_filter = SignalFilter::empty().signal_types(vec!["chat".to_string()]);
+
+        assert!(SignalFilterSet::allow_all().allows(&cell_1, &zome, &untyped));
+        assert!(!SignalFilterSet::block_all().allows(&cell_1, &zome, &untyped));
+
+        let mut filters = HashMap::new();
+        filters.insert(cell_1.clone(), chat_filter);
+        let include = SignalFilterSet::Include(filters.clone());
+        assert!(include.allows(&cell_1, &zome, &typed));
+        assert!(!include.allows(&cell_1, &zome, &untyped));
+        assert!(!include.allows(&cell_2, &zome, &typed));
+
+        let exclude = SignalFilterSet::Exclude(filters);
+        assert!(!exclude.allows(&cell_1, &zome, &typed));
+        assert!(exclude.allows(&cell_1, &zome, &untyped));
+        assert!(exclude.allows(&cell_2, &zome, &typed));
     }
 }
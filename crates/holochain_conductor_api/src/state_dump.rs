@@ -0,0 +1,79 @@
+//! Types for the structured dump of a Cell's state returned by
+//! [`AdminRequest::DumpState`].
+//!
+//! [`AdminRequest::DumpState`]: ../enum.AdminRequest.html#variant.DumpState
+
+use holo_hash::DhtOpHash;
+use holo_hash::HeaderHash;
+use holochain_types::prelude::*;
+
+/// What to include in a [`CellStateDump`].
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DumpStateOptions {
+    /// Include the entry of each source chain element.
+    /// Entries can be large so they are left out by default.
+    #[serde(default)]
+    pub include_entries: bool,
+}
+
+/// The state of a Cell, for debugging.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CellStateDump {
+    /// The Cell this state belongs to
+    pub cell_id: CellId,
+    /// The source chain, from the `Dna` header to the chain head
+    pub source_chain: Vec<SourceChainDumpElement>,
+    /// How many ops are in each stage of validation and integration
+    pub dht_ops: DhtOpsDump,
+    /// The validation receipts received for each op this Cell authored
+    pub validation_receipts: Vec<OpReceiptsDump>,
+    /// The size of each database in the Cell's environment
+    pub databases: Vec<DbSizeDump>,
+}
+
+/// A single element of a source chain.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SourceChainDumpElement {
+    /// The header's hash
+    pub header_address: HeaderHash,
+    /// The header
+    pub header: Header,
+    /// The author's signature of the header
+    pub signature: Signature,
+    /// The entry, if the header has one and entries were requested.
+    /// Also `None` if the entry is missing from the vault.
+    pub entry: Option<Entry>,
+}
+
+/// Counts of the [DhtOp]s held by a Cell.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DhtOpsDump {
+    /// Ops this Cell authored
+    pub authored: usize,
+    /// Ops waiting for validation, or for their dependencies
+    pub validation_limbo: usize,
+    /// Ops which are validated and waiting to be integrated
+    pub integration_limbo: usize,
+    /// Ops which are integrated
+    pub integrated: usize,
+}
+
+/// The validation receipts for an authored op.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OpReceiptsDump {
+    /// The op the receipts are for
+    pub dht_op_hash: DhtOpHash,
+    /// The receipts received from validators
+    pub receipts: Vec<SignedValidationReceipt>,
+}
+
+/// The size of one of a Cell's databases.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DbSizeDump {
+    /// The database's name
+    pub db: String,
+    /// Number of records
+    pub entries: u64,
+    /// Total bytes of all keys and values
+    pub bytes: u64,
+}
@@ -1,6 +1,8 @@
 //! Functionality for safely accessing LMDB database references.
 
 use crate::env::EnvironmentKind;
+use crate::env::EnvironmentRead;
+use crate::env::ReadManager;
 use crate::error::DatabaseError;
 use crate::error::DatabaseResult;
 use crate::exports::IntegerStore;
@@ -68,6 +70,10 @@ pub enum DbName {
     ValidationLimbo,
     /// KVV store to accumulate validation receipts for a published EntryHash
     ValidationReceipts,
+    /// KV store of zome functions scheduled to be called by the host
+    ScheduledFns,
+    /// KV store of authored DhtOps still waiting for enough validation receipts
+    PublishQueue,
     /// Single store for all known agents on the network
     Agent,
 }
@@ -99,6 +105,8 @@ impl DbName {
             IntegrationLimbo => Single,
             ValidationLimbo => Single,
             ValidationReceipts => Multi,
+            ScheduledFns => Single,
+            PublishQueue => Single,
             Agent => Single,
         }
     }
@@ -170,6 +178,10 @@ lazy_static! {
     pub static ref VALIDATION_LIMBO: DbKey<SingleStore> = DbKey::new(DbName::ValidationLimbo);
     /// The key to access the ValidationReceipts database
     pub static ref VALIDATION_RECEIPTS: DbKey<MultiStore> = DbKey::new(DbName::ValidationReceipts);
+    /// The key to access the ScheduledFns database
+    pub static ref SCHEDULED_FNS: DbKey<SingleStore> = DbKey::new(DbName::ScheduledFns);
+    /// The key to access the PublishQueue database
+    pub static ref PUBLISH_QUEUE: DbKey<SingleStore> = DbKey::new(DbName::PublishQueue);
     /// The key to access the Agent database
     pub static ref AGENT: DbKey<SingleStore> = DbKey::new(DbName::Agent);
 }
@@ -196,6 +208,12 @@ pub(super) fn initialize_databases(rkv: &Rkv, kind: &EnvironmentKind) -> Databas
     Ok(())
 }
 
+/// Forget the databases of an environment which is being removed,
+/// so that an environment can be created again at the same path
+pub(super) fn deinitialize_databases(path: &Path) {
+    DB_MAP_MAP.write().remove(path);
+}
+
 pub(super) fn get_db<V: 'static + Copy + Send + Sync>(
     path: &Path,
     key: &'static DbKey<V>,
@@ -230,6 +248,8 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*INTEGRATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_RECEIPTS)?;
+            register_db(env, um, &*SCHEDULED_FNS)?;
+            register_db(env, um, &*PUBLISH_QUEUE)?;
         }
         EnvironmentKind::Conductor => {
             register_db(env, um, &*CONDUCTOR_STATE)?;
@@ -289,6 +309,92 @@ fn register_db<V: 'static + Send + Sync>(
     Ok(())
 }
 
+/// The number of records in a database and the total size of their keys and values.
+/// This is the size of the data, not of the pages LMDB uses to store it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DbStats {
+    /// Number of records
+    pub entries: u64,
+    /// Total bytes of all keys and values
+    pub bytes: u64,
+}
+
+/// Count the records and bytes in each database of an environment.
+///
+/// Multi-value databases are counted once per value.
+pub fn db_stats(env: &EnvironmentRead) -> DatabaseResult<Vec<(DbName, DbStats)>> {
+    let single: &[&'static DbKey<SingleStore>] = match env.kind() {
+        EnvironmentKind::Cell(_) => &[
+            &*ELEMENT_VAULT_PUBLIC_ENTRIES,
+            &*ELEMENT_VAULT_PRIVATE_ENTRIES,
+            &*ELEMENT_VAULT_HEADERS,
+            &*META_VAULT_LINKS,
+            &*META_VAULT_MISC,
+            &*ELEMENT_CACHE_ENTRIES,
+            &*ELEMENT_CACHE_HEADERS,
+            &*CACHE_LINKS_META,
+            &*CACHE_STATUS_META,
+            &*AUTHORED_DHT_OPS,
+            &*INTEGRATED_DHT_OPS,
+            &*INTEGRATION_LIMBO,
+            &*VALIDATION_LIMBO,
+            &*SCHEDULED_FNS,
+            &*PUBLISH_QUEUE,
+        ],
+        EnvironmentKind::Conductor => &[&*CONDUCTOR_STATE],
+        EnvironmentKind::Wasm => &[&*WASM, &*DNA_DEF, &*ENTRY_DEF],
+        EnvironmentKind::P2p => &[&*AGENT],
+    };
+    let multi: &[&'static DbKey<MultiStore>] = match env.kind() {
+        EnvironmentKind::Cell(_) => &[&*META_VAULT_SYS, &*CACHE_SYSTEM_META, &*VALIDATION_RECEIPTS],
+        _ => &[],
+    };
+    let g = env.guard();
+    // `rkv` can't iterate a MultiStore, but LMDB hands back the same
+    // already-open handle when a database is reopened by name, and a
+    // cursor over that handle visits every duplicate value.
+    let multi = multi
+        .iter()
+        .map(|key| {
+            let db_str = format!("{}", key.key());
+            let db = g
+                .inner()
+                .open_single(db_str.as_str(), StoreOptions::default())?;
+            Ok((key.key().clone(), db))
+        })
+        .collect::<DatabaseResult<Vec<_>>>()?;
+    let r = g.reader()?;
+    let mut stats = Vec::with_capacity(single.len() + multi.len() + 1);
+    if let EnvironmentKind::Cell(_) = env.kind() {
+        let db = env.get_db(&*CHAIN_SEQUENCE)?;
+        stats.push((CHAIN_SEQUENCE.key().clone(), sum_stats(db.iter_start(&r)?)?));
+    }
+    for key in single {
+        let db = env.get_db(*key)?;
+        stats.push((key.key().clone(), sum_stats(db.iter_start(&r)?)?));
+    }
+    for (name, db) in multi {
+        stats.push((name, sum_stats(db.iter_start(&r)?)?));
+    }
+    Ok(stats)
+}
+
+fn sum_stats<'i>(
+    iter: impl Iterator<Item = Result<(&'i [u8], Option<rkv::Value<'i>>), rkv::StoreError>>,
+) -> DatabaseResult<DbStats> {
+    let mut stats = DbStats::default();
+    for item in iter {
+        let (k, v) = item?;
+        let v = match v {
+            Some(v) => v.to_bytes()?.len(),
+            None => 0,
+        };
+        stats.entries += 1;
+        stats.bytes += (k.len() + v) as u64;
+    }
+    Ok(stats)
+}
+
 /// GetDb allows access to the UniversalMap which stores the heterogeneously typed
 /// LMDB Database references.
 pub trait GetDb {
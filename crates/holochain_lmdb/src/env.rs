@@ -1,6 +1,7 @@
 //! Functions dealing with obtaining and referencing singleton LMDB environments
 
 use crate::db::get_db;
+use crate::db::deinitialize_databases;
 use crate::db::initialize_databases;
 use crate::db::DbKey;
 use crate::db::GetDb;
@@ -201,7 +202,7 @@ impl EnvironmentWrite {
     pub async fn remove(self) -> DatabaseResult<()> {
         let mut map = ENVIRONMENTS.write();
         map.remove(&self.0.path);
-        // TODO remove this db from the DB_MAP_MAP?
+        deinitialize_databases(&self.0.path);
         // remove the directory
         std::fs::remove_dir_all(&self.0.path)?;
         Ok(())
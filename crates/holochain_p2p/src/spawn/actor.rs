@@ -733,15 +733,15 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
-            // TODO - We're just targeting a single remote node for now
-            //        without doing any pagination / etc...
-            //        Setting up RpcMulti to act like RpcSingle
+            // Ask several authorities and return all their responses
+            // so a single stale or malicious authority can't hide anything.
+            // We wait for them all rather than racing.
             let result = kitsune_p2p
                 .rpc_multi(kitsune_p2p::actor::RpcMulti {
                     space,
                     from_agent,
                     basis,
-                    remote_agent_count: Some(1),
+                    remote_agent_count: options.remote_agent_count,
                     timeout_ms: options.timeout_ms,
                     as_race: false,
                     race_timeout_ms: options.timeout_ms,
@@ -783,15 +783,15 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
-            // TODO - We're just targeting a single remote node for now
-            //        without doing any pagination / etc...
-            //        Setting up RpcMulti to act like RpcSingle
+            // Ask several authorities and return all their responses
+            // so a single stale or malicious authority can't hide anything.
+            // We wait for them all rather than racing.
             let result = kitsune_p2p
                 .rpc_multi(kitsune_p2p::actor::RpcMulti {
                     space,
                     from_agent,
                     basis,
-                    remote_agent_count: Some(1),
+                    remote_agent_count: options.remote_agent_count,
                     timeout_ms: options.timeout_ms,
                     as_race: false,
                     race_timeout_ms: options.timeout_ms,
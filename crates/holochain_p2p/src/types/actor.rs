@@ -48,8 +48,8 @@ pub struct GetOptions {
     pub race_timeout_ms: Option<u64>,
 
     /// [Remote]
-    /// Whether the remote-end should follow redirects or just return the
-    /// requested entry.
+    /// Whether the remote-end should also return the updates
+    /// needed to follow redirects to the newest version.
     pub follow_redirects: bool,
 
     /// [Remote]
@@ -65,15 +65,18 @@ impl Default for GetOptions {
             timeout_ms: None,
             as_race: true,
             race_timeout_ms: None,
-            follow_redirects: true,
+            follow_redirects: false,
             all_live_headers_with_metadata: false,
         }
     }
 }
 
 impl From<holochain_zome_types::entry::GetOptions> for GetOptions {
-    fn from(_: holochain_zome_types::entry::GetOptions) -> Self {
-        Self::default()
+    fn from(options: holochain_zome_types::entry::GetOptions) -> Self {
+        Self {
+            follow_redirects: options.follow_redirects,
+            ..Self::default()
+        }
     }
 }
 
@@ -130,17 +133,33 @@ impl Default for GetMetaOptions {
 /// Fields tagged with `[Remote]` are controls that will be forwarded to the
 /// remote agent processing this `GetLinks` request.
 pub struct GetLinksOptions {
+    /// [Network]
+    /// How many remote nodes should we make requests of / aggregate.
+    /// Set to `None` for a default "best-effort".
+    /// All the responses are merged by the cascade so no single
+    /// authority can hide links from us.
+    pub remote_agent_count: Option<u8>,
+
     /// [Network]
     /// Timeout to await responses for aggregation.
     /// Set to `None` for a default "best-effort".
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// [Remote]
+    /// Which page of links to return.
+    /// The default is every link in one page.
+    pub page: LinkPageQuery,
 }
 
 impl Default for GetLinksOptions {
     fn default() -> Self {
-        Self { timeout_ms: None }
+        Self {
+            remote_agent_count: None,
+            timeout_ms: None,
+            page: LinkPageQuery::default(),
+        }
     }
 }
 
@@ -150,6 +169,13 @@ impl Default for GetLinksOptions {
 /// Fields tagged with `[Remote]` are controls that will be forwarded to the
 /// remote agent processing this `GetLinks` request.
 pub struct GetActivityOptions {
+    /// [Network]
+    /// How many remote nodes should we make requests of / aggregate.
+    /// Set to `None` for a default "best-effort".
+    /// All the responses are merged by the cascade so no single
+    /// authority can hide chain activity from us.
+    pub remote_agent_count: Option<u8>,
+
     /// [Network]
     /// Timeout to await responses for aggregation.
     /// Set to `None` for a default "best-effort".
@@ -178,6 +204,7 @@ pub struct GetActivityOptions {
 impl Default for GetActivityOptions {
     fn default() -> Self {
         Self {
+            remote_agent_count: None,
             timeout_ms: None,
             retry_gets: 0,
             include_valid_activity: true,
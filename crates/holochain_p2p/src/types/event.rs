@@ -8,8 +8,8 @@ use kitsune_p2p::agent_store::AgentInfoSigned;
 /// Get options help control how the get is processed at various levels.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct GetOptions {
-    /// Whether the remote-end should follow redirects or just return the
-    /// requested entry.
+    /// Whether the remote-end should also return the updates
+    /// needed to follow redirects to the newest version.
     pub follow_redirects: bool,
     /// Return all live headers even if there is deletes.
     /// Useful for metadata calls.
@@ -37,11 +37,18 @@ impl From<&actor::GetMetaOptions> for GetMetaOptions {
 
 /// GetLinks options help control how the get is processed at various levels.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetLinksOptions {}
+pub struct GetLinksOptions {
+    /// Which page of links to return.
+    /// Only the link adds in this page and the removes on them are returned.
+    #[serde(default)]
+    pub page: LinkPageQuery,
+}
 
 impl From<&actor::GetLinksOptions> for GetLinksOptions {
-    fn from(_a: &actor::GetLinksOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetLinksOptions) -> Self {
+        Self {
+            page: a.page.clone(),
+        }
     }
 }
 
@@ -89,6 +89,9 @@ pub struct IntegratedDhtOpsValue {
     pub validation_status: ValidationStatus,
     /// Signatures and hashes of the op
     pub op: DhtOpLight,
+    /// Time when the op's header was authored.
+    /// Unlike `when_integrated` this is the same for every agent holding the op.
+    pub when_authored: Timestamp,
     /// Time when the op was integrated
     pub when_integrated: Timestamp,
 }
@@ -117,8 +120,8 @@ impl IntegratedDhtOpsBuf {
     }
 
     /// Get ops that match optional queries:
-    /// - from a time (Inclusive)
-    /// - to a time (Exclusive)
+    /// - authored from a time (Inclusive)
+    /// - authored to a time (Exclusive)
     /// - match a dht location
     pub fn query<'r, R: Readable>(
         &'r self,
@@ -137,12 +140,12 @@ impl IntegratedDhtOpsBuf {
                 .iter(r)?
                 .map(move |(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
                 .filter_map(move |(k, v)| match from {
-                    Some(time) if v.when_integrated >= time => Ok(Some((k, v))),
+                    Some(time) if v.when_authored >= time => Ok(Some((k, v))),
                     None => Ok(Some((k, v))),
                     _ => Ok(None),
                 })
                 .filter_map(move |(k, v)| match to {
-                    Some(time) if v.when_integrated < time => Ok(Some((k, v))),
+                    Some(time) if v.when_authored < time => Ok(Some((k, v))),
                     None => Ok(Some((k, v))),
                     _ => Ok(None),
                 })
@@ -189,10 +192,11 @@ mod tests {
         let times_exp = times.clone();
         let values = times
             .into_iter()
-            .map(|when_integrated| IntegratedDhtOpsValue {
+            .map(|when_authored| IntegratedDhtOpsValue {
                 validation_status: ValidationStatus::Valid,
                 op: DhtOpLight::RegisterAgentActivity(fixt!(HeaderHash), basis.next().unwrap()),
-                when_integrated: when_integrated.into(),
+                when_authored: when_authored.into(),
+                when_integrated: now.into(),
             });
 
         // Put them in the db
@@ -222,7 +226,7 @@ mod tests {
                 .map(|(_, v)| Ok(v))
                 .collect::<Vec<_>>()
                 .unwrap();
-            r.sort_by_key(|v| v.when_integrated.clone());
+            r.sort_by_key(|v| v.when_authored.clone());
             assert_eq!(&r[..], &expected[..]);
             // From now
             let mut r = buf
@@ -231,7 +235,7 @@ mod tests {
                 .map(|(_, v)| Ok(v))
                 .collect::<Vec<_>>()
                 .unwrap();
-            r.sort_by_key(|v| v.when_integrated.clone());
+            r.sort_by_key(|v| v.when_authored.clone());
             assert!(r.contains(&expected[2]));
             assert!(r.contains(&expected[4]));
             assert!(r.contains(&expected[3]));
@@ -246,7 +250,7 @@ mod tests {
                 .map(|(_, v)| Ok(v))
                 .collect::<Vec<_>>()
                 .unwrap();
-            r.sort_by_key(|v| v.when_integrated.clone());
+            r.sort_by_key(|v| v.when_authored.clone());
 
             assert!(r.contains(&expected[0]));
             assert!(r.contains(&expected[1]));
@@ -267,7 +271,7 @@ mod tests {
                 .map(|(_, v)| Ok(v))
                 .collect::<Vec<_>>()
                 .unwrap();
-            r.sort_by_key(|v| v.when_integrated.clone());
+            r.sort_by_key(|v| v.when_authored.clone());
             assert!(r.contains(&expected[1]));
             assert!(r.contains(&expected[3]));
             assert_eq!(r.len(), 2);
@@ -283,7 +287,7 @@ mod tests {
                 .map(|(_, v)| Ok(v))
                 .collect::<Vec<_>>()
                 .unwrap();
-            r.sort_by_key(|v| v.when_integrated.clone());
+            r.sort_by_key(|v| v.when_authored.clone());
             assert!(r.contains(&expected[1]));
             assert!(r.contains(&expected[3]));
             assert!(r.contains(&expected[5]));
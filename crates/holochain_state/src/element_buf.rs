@@ -77,6 +77,14 @@ impl ElementBuf<RejectedPrefix> {
     }
 }
 
+impl ElementBuf<AbandonedPrefix> {
+    /// Create a element buf for all elements that have been abandoned.
+    /// Only the headers are kept, the entries of abandoned data are dropped.
+    pub fn abandoned(env: EnvironmentRead) -> DatabaseResult<Self> {
+        ElementBuf::new_vault(env, false)
+    }
+}
+
 impl ElementBuf<AuthoredPrefix> {
     /// Create a element buf for all authored elements.
     /// This reuses the database but is the data is completely separate.
@@ -40,6 +40,8 @@ pub mod element_buf;
 pub mod metadata;
 #[allow(missing_docs)]
 pub mod prelude;
+pub mod publish_queue_db;
+pub mod schedule_db;
 #[allow(missing_docs)]
 pub mod source_chain;
 pub mod validation_db;
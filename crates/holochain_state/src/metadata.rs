@@ -7,6 +7,7 @@
 use fallible_iterator::FallibleIterator;
 use holo_hash::AgentPubKey;
 use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
 use holo_hash::EntryHash;
 use holo_hash::HasHash;
 use holo_hash::HeaderHash;
@@ -77,6 +78,16 @@ where
         key: &'k LinkMetaKey<'k>,
     ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>;
 
+    /// Get a page of the links on this base that match the tag regardless of removes.
+    /// The links are in the order they were created.
+    /// Also returns the cursor of the next page if the page is full.
+    fn get_links_page<'r, 'k, R: Readable>(
+        &'r self,
+        r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        page: &LinkPageQuery,
+    ) -> DatabaseResult<(Vec<LinkMetaVal>, Option<LinkCursor>)>;
+
     /// Add a link
     fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()>;
 
@@ -132,6 +143,16 @@ where
         new_entry_header: NewEntryHeader,
     ) -> DatabaseResult<()>;
 
+    /// Registers an abandoned [Header::NewEntryHeader] on the referenced [Entry]
+    fn register_abandoned_header(&mut self, new_entry_header: NewEntryHeader)
+        -> DatabaseResult<()>;
+
+    /// Deregister an abandoned [Header::NewEntryHeader] on the referenced [Entry]
+    fn deregister_abandoned_header(
+        &mut self,
+        new_entry_header: NewEntryHeader,
+    ) -> DatabaseResult<()>;
+
     /// Registers a [Header] when a StoreElement is processed.
     /// Useful for knowing if we can serve a header from our element vault
     fn register_element_header(&mut self, header: &Header) -> DatabaseResult<()>;
@@ -148,6 +169,24 @@ where
     /// Useful for knowing if we can serve a header from our element vault
     fn deregister_rejected_element_header(&mut self, header: HeaderHash) -> DatabaseResult<()>;
 
+    /// Registers an abandoned [Header] when a StoreElement is processed.
+    /// Useful for knowing if we can serve a header from our abandoned store
+    fn register_abandoned_element_header(&mut self, header: &Header) -> DatabaseResult<()>;
+
+    /// Deregister an abandoned [Header] when a StoreElement is processed.
+    fn deregister_abandoned_element_header(&mut self, header: HeaderHash) -> DatabaseResult<()>;
+
+    /// Registers an abandoned op with the dependencies it was missing
+    /// so it can be retried if they turn up
+    fn register_abandoned_op(
+        &mut self,
+        op_hash: DhtOpHash,
+        missing_dependencies: Vec<AnyDhtHash>,
+    ) -> DatabaseResult<()>;
+
+    /// Deregister an abandoned op
+    fn deregister_abandoned_op(&mut self, op_hash: &DhtOpHash) -> DatabaseResult<()>;
+
     /// Registers a published [Header] on the authoring agent's public key
     fn register_activity(
         &mut self,
@@ -207,6 +246,12 @@ where
     /// Deregister the highest observed sequence number on an agents chain
     fn deregister_activity_observed(&mut self, agent: &AgentPubKey) -> DatabaseResult<()>;
 
+    /// Registers a [Warrant] on the warranted agent's public key
+    fn register_warrant(&mut self, warrant: Warrant) -> DatabaseResult<()>;
+
+    /// Deregister a [Warrant] on the warranted agent's public key
+    fn deregister_warrant(&mut self, warrant: &Warrant) -> DatabaseResult<()>;
+
     /// Registers a [Header::Update] on the referenced [Header] or [Entry]
     fn register_update(&mut self, update: header::Update) -> DatabaseResult<()>;
 
@@ -239,6 +284,13 @@ where
         entry_hash: EntryHash,
     ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError> + '_>>;
 
+    /// Returns all the abandoned [HeaderHash]es of headers that created this [Entry]
+    fn get_abandoned_headers<'r, R: Readable>(
+        &'r self,
+        reader: &'r R,
+        entry_hash: EntryHash,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError> + '_>>;
+
     /// Returns all the valid and rejected [HeaderHash]es of headers that created this [Entry]
     fn get_all_headers<'r, R: Readable>(
         &'r self,
@@ -284,6 +336,12 @@ where
     fn get_activity_observed(&self, agent: &AgentPubKey)
         -> DatabaseResult<Option<HighestObserved>>;
 
+    /// Get all the [Warrant]s held against this agent
+    fn get_warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<Warrant>>;
+
+    /// Get all the abandoned ops and the dependencies they were missing
+    fn get_abandoned_ops(&self) -> DatabaseResult<Vec<(DhtOpHash, Vec<AnyDhtHash>)>>;
+
     /// Returns all the hashes of [Update] headers registered on an [Entry]
     fn get_updates<'r, R: Readable>(
         &'r self,
@@ -321,6 +379,24 @@ where
         header_hash: &HeaderHash,
     ) -> DatabaseResult<DisputedStatus>;
 
+    /// Returns the newest live [Update] on an [Entry] and the [Entry] it
+    /// redirects to.
+    /// Branching updates are broken by the newest timestamp and then
+    /// the highest header hash so every node picks the same branch.
+    fn get_entry_redirect<'r, R: Readable>(
+        &'r self,
+        reader: &'r R,
+        entry_hash: &EntryHash,
+    ) -> DatabaseResult<Option<(TimedHeaderHash, EntryHash)>>;
+
+    /// Returns the newest live [Update] on a [Header].
+    /// Branches are broken the same way as [MetadataBufT::get_entry_redirect].
+    fn get_header_redirect<'r, R: Readable>(
+        &'r self,
+        reader: &'r R,
+        header_hash: &HeaderHash,
+    ) -> DatabaseResult<Option<TimedHeaderHash>>;
+
     /// Finds the redirect path and returns the final [Entry]
     fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash>;
 
@@ -343,6 +419,9 @@ where
     /// Finds if there is a rejected StoreElement for this header
     fn has_rejected_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
 
+    /// Finds if there is an abandoned StoreElement for this header
+    fn has_abandoned_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
+
     /// Finds if there is a StoreEntry for this header
     fn has_registered_store_entry(
         &self,
@@ -467,6 +546,27 @@ where
         Ok(())
     }
 
+    /// The [Update]s on this basis that haven't been deleted, newest first
+    fn live_updates<R: Readable>(
+        &self,
+        r: &R,
+        basis: AnyDhtHash,
+    ) -> DatabaseResult<Vec<TimedHeaderHash>> {
+        let mut updates = self.get_updates(r, basis)?.collect::<Vec<_>>()?;
+        updates.sort_unstable();
+        let mut live = Vec::with_capacity(updates.len());
+        for update in updates.into_iter().rev() {
+            if self
+                .get_deletes_on_header(r, update.header_hash.clone())?
+                .next()?
+                .is_none()
+            {
+                live.push(update);
+            }
+        }
+        Ok(live)
+    }
+
     #[instrument(skip(self))]
     fn update_entry_dht_status(&mut self, basis: EntryHash) -> DatabaseResult<()> {
         let status = fresh_reader!(self.env, |r| self.get_headers(&r, basis.clone())?.find_map(
@@ -597,6 +697,58 @@ where
         Ok(())
     }
 
+    /// Index the links that were added before links were indexed by time.
+    /// Only reads the links table the first time it runs on a database.
+    pub fn backfill_link_times(&mut self) -> DatabaseResult<()> {
+        let backfilled: PrefixBytesKey<P> = MiscMetaKey::link_adds_backfilled().into();
+        let links: Vec<(EntryHash, LinkMetaVal)> = fresh_reader!(self.env, |r| {
+            if self.misc_meta.contains(&r, &backfilled)? {
+                return Ok(Vec::new());
+            }
+            // Link keys start with the raw bytes of the base
+            self.links_meta
+                .iter_all_key_matches(&r, PrefixBytesKey::new(std::iter::empty()))?
+                .map(|(k, link)| {
+                    let base = EntryHash::from_raw_39_panicky(
+                        k[1..=holo_hash::HOLO_HASH_FULL_LEN].to_vec(),
+                    );
+                    Ok((base, link))
+                })
+                .collect()
+        })?;
+        for (base, link) in links {
+            let key = MiscMetaKey::link_add(&base, link.timestamp, &link.link_add_hash);
+            self.misc_meta
+                .put(key.into(), MiscMetaValue::LinkAdd(link))?;
+        }
+        self.misc_meta
+            .put(backfilled, MiscMetaValue::LinkAddsBackfilled)
+    }
+
+    /// Index the redirects of the updates integrated before
+    /// update redirects were indexed.
+    /// `updates` reads every [Update] header this database has metadata for
+    /// and is only called the first time this runs on a database.
+    pub fn backfill_update_redirects<F>(&mut self, updates: F) -> DatabaseResult<()>
+    where
+        F: FnOnce() -> DatabaseResult<Vec<header::Update>>,
+    {
+        let backfilled: PrefixBytesKey<P> = MiscMetaKey::update_redirects_backfilled().into();
+        if fresh_reader!(self.env, |r| self.misc_meta.contains(&r, &backfilled))? {
+            return Ok(());
+        }
+        for update in updates()? {
+            let key = MiscMetaKey::update_redirect(&HeaderHash::with_data_sync(&Header::Update(
+                update.clone(),
+            )))
+            .into();
+            self.misc_meta
+                .put(key, MiscMetaValue::UpdateRedirect(update.entry_hash))?;
+        }
+        self.misc_meta
+            .put(backfilled, MiscMetaValue::UpdateRedirectsBackfilled)
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn clear_all(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
         self.links_meta.clear_all(writer)?;
@@ -643,6 +795,39 @@ where
         ))
     }
 
+    fn get_links_page<'r, 'k, R: Readable>(
+        &'r self,
+        r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        page: &LinkPageQuery,
+    ) -> DatabaseResult<(Vec<LinkMetaVal>, Option<LinkCursor>)> {
+        // The links on a base are also indexed by time so reading starts
+        // at the first link that can be in the page and stops when it is full
+        let base = key.base();
+        let on_base: PrefixBytesKey<P> = MiscMetaKey::link_adds(base).into();
+        let start = match page.start() {
+            Some(start) => MiscMetaKey::link_adds_from(base, start).into(),
+            None => on_base.clone(),
+        };
+        let matches = BytesKey::from(key);
+        let limit = page.limit.map_or(usize::MAX, |limit| limit.get() as usize);
+        let links = self
+            .misc_meta
+            .iter_from(r, start)?
+            .take_while(|(k, _)| Ok(k.starts_with(on_base.as_ref())))
+            .map(|(_, v)| Ok(v.link_add()))
+            .take_while(|link| Ok(page.until.map_or(true, |until| link.timestamp < until)))
+            .filter(|link| {
+                let link_key =
+                    LinkMetaKey::Full(base, link.zome_id, &link.tag, &link.link_add_hash);
+                Ok(page.contains(&link.timestamp, &link.link_add_hash)
+                    && BytesKey::from(link_key).0.starts_with(&matches.0))
+            })
+            .take(limit)
+            .collect::<Vec<_>>()?;
+        Ok(page.page(links, |link| (link.timestamp, link.link_add_hash.clone())))
+    }
+
     fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()> {
         // Register the add link onto the base
         let link_add_hash =
@@ -650,22 +835,28 @@ where
 
         // Put the link add to the links table
         let key = LinkMetaKey::from((&link_add, &link_add_hash));
+        let time_key =
+            MiscMetaKey::link_add(&link_add.base_address, link_add.timestamp, &link_add_hash);
+        let link = LinkMetaVal {
+            link_add_hash,
+            target: link_add.target_address,
+            timestamp: link_add.timestamp,
+            zome_id: link_add.zome_id,
+            tag: link_add.tag,
+        };
 
-        self.links_meta.put(
-            key.into(),
-            LinkMetaVal {
-                link_add_hash,
-                target: link_add.target_address,
-                timestamp: link_add.timestamp,
-                zome_id: link_add.zome_id,
-                tag: link_add.tag,
-            },
-        )
+        // Also index it by time for paging
+        self.misc_meta
+            .put(time_key.into(), MiscMetaValue::LinkAdd(link.clone()))?;
+        self.links_meta.put(key.into(), link)
     }
 
     fn deregister_add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()> {
         let link_add_hash = HeaderHash::with_data_sync(&Header::CreateLink(link_add.clone()));
         let key = LinkMetaKey::from((&link_add, &link_add_hash));
+        let time_key =
+            MiscMetaKey::link_add(&link_add.base_address, link_add.timestamp, &link_add_hash);
+        self.misc_meta.delete(time_key.into())?;
         self.links_meta.delete(key.into())
     }
 
@@ -754,6 +945,30 @@ where
         Ok(())
     }
 
+    fn register_abandoned_header(
+        &mut self,
+        new_entry_header: NewEntryHeader,
+    ) -> DatabaseResult<()> {
+        let basis = new_entry_header.entry().clone();
+        let header: Header = new_entry_header.into();
+        let header = HeaderHashed::from_content_sync(header);
+        let value = SysMetaVal::AbandonedNewEntry(header.into());
+        self.register_raw_on_entry(basis, value)?;
+        Ok(())
+    }
+
+    fn deregister_abandoned_header(
+        &mut self,
+        new_entry_header: NewEntryHeader,
+    ) -> DatabaseResult<()> {
+        let basis = new_entry_header.entry().clone();
+        let header: Header = new_entry_header.into();
+        let header = HeaderHashed::from_content_sync(header);
+        let value = SysMetaVal::AbandonedNewEntry(header.into());
+        self.deregister_raw_on_entry(basis, value)?;
+        Ok(())
+    }
+
     fn register_element_header(&mut self, header: &Header) -> DatabaseResult<()> {
         self.misc_meta.put(
             MiscMetaKey::store_element(&HeaderHash::with_data_sync(header)).into(),
@@ -778,9 +993,44 @@ where
             .delete(MiscMetaKey::rejected_store_element(&hash).into())
     }
 
+    fn register_abandoned_element_header(&mut self, header: &Header) -> DatabaseResult<()> {
+        self.misc_meta.put(
+            MiscMetaKey::abandoned_store_element(&HeaderHash::with_data_sync(header)).into(),
+            MiscMetaValue::new_store_element(),
+        )
+    }
+
+    fn deregister_abandoned_element_header(&mut self, hash: HeaderHash) -> DatabaseResult<()> {
+        self.misc_meta
+            .delete(MiscMetaKey::abandoned_store_element(&hash).into())
+    }
+
+    fn register_abandoned_op(
+        &mut self,
+        op_hash: DhtOpHash,
+        missing_dependencies: Vec<AnyDhtHash>,
+    ) -> DatabaseResult<()> {
+        let key = MiscMetaKey::abandoned_op(&op_hash).into();
+        let value = MiscMetaValue::AbandonedOp(op_hash, missing_dependencies);
+        self.misc_meta.put(key, value)
+    }
+
+    fn deregister_abandoned_op(&mut self, op_hash: &DhtOpHash) -> DatabaseResult<()> {
+        self.misc_meta
+            .delete(MiscMetaKey::abandoned_op(op_hash).into())
+    }
+
     fn register_update(&mut self, update: header::Update) -> DatabaseResult<()> {
         let header_hash = update.original_header_address.clone();
         let entry_hash = update.original_entry_address.clone();
+        let key = MiscMetaKey::update_redirect(&HeaderHash::with_data_sync(&Header::Update(
+            update.clone(),
+        )))
+        .into();
+        self.misc_meta.put(
+            key,
+            MiscMetaValue::UpdateRedirect(update.entry_hash.clone()),
+        )?;
         self.register_header_on_basis(header_hash, update.clone())?;
         self.register_header_on_basis(entry_hash, update)
     }
@@ -788,6 +1038,11 @@ where
     fn deregister_update(&mut self, update: header::Update) -> DatabaseResult<()> {
         let header_hash = update.original_header_address.clone();
         let entry_hash = update.original_entry_address.clone();
+        let key = MiscMetaKey::update_redirect(&HeaderHash::with_data_sync(&Header::Update(
+            update.clone(),
+        )))
+        .into();
+        self.misc_meta.delete(key)?;
         self.deregister_header_on_basis(header_hash, update.clone())?;
         self.deregister_header_on_basis(entry_hash, update)
     }
@@ -925,11 +1180,12 @@ where
                 && prev_observed.hash != observed.hash
             {
                 // If the observed are the same sequence
-                // Combine the hashes and overwrite
+                // Combine the hashes and overwrite.
+                // More than one hash here is a fork.
                 let diff = observed
                     .hash
                     .into_iter()
-                    .filter(|h| prev_observed.hash.contains(h))
+                    .filter(|h| !prev_observed.hash.contains(h))
                     .collect::<Vec<_>>();
                 prev_observed.hash.extend(diff);
 
@@ -954,6 +1210,17 @@ where
             .delete(MiscMetaKey::chain_observed(&agent).into())
     }
 
+    fn register_warrant(&mut self, warrant: Warrant) -> DatabaseResult<()> {
+        let key = MiscMetaKey::chain_warrant(&warrant).into();
+        let value = MiscMetaValue::Warrant(warrant);
+        self.misc_meta.put(key, value)
+    }
+
+    fn deregister_warrant(&mut self, warrant: &Warrant) -> DatabaseResult<()> {
+        self.misc_meta
+            .delete(MiscMetaKey::chain_warrant(warrant).into())
+    }
+
     fn get_headers<'r, R: Readable>(
         &'r self,
         r: &'r R,
@@ -1015,6 +1282,26 @@ where
         ))
     }
 
+    fn get_abandoned_headers<'r, R: Readable>(
+        &'r self,
+        r: &'r R,
+        entry_hash: EntryHash,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError> + '_>>
+    {
+        Ok(Box::new(
+            fallible_iterator::convert(
+                self.system_meta
+                    .get(r, &SysMetaKey::from(entry_hash).into())?,
+            )
+            .filter_map(|h| {
+                Ok(match h {
+                    SysMetaVal::AbandonedNewEntry(h) => Some(h),
+                    _ => None,
+                })
+            }),
+        ))
+    }
+
     fn get_updates<'r, R: Readable>(
         &'r self,
         r: &'r R,
@@ -1148,6 +1435,24 @@ where
             .map(MiscMetaValue::chain_observed))
     }
 
+    fn get_warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<Warrant>> {
+        let key = MiscMetaKey::chain_warrants(&agent).into();
+        fresh_reader!(self.env, |r| self
+            .misc_meta
+            .iter_all_key_matches(&r, key)?
+            .map(|(_, v)| Ok(MiscMetaValue::warrant(v)))
+            .collect())
+    }
+
+    fn get_abandoned_ops(&self) -> DatabaseResult<Vec<(DhtOpHash, Vec<AnyDhtHash>)>> {
+        let key = MiscMetaKey::abandoned_ops().into();
+        fresh_reader!(self.env, |r| self
+            .misc_meta
+            .iter_all_key_matches(&r, key)?
+            .map(|(_, v)| Ok(MiscMetaValue::abandoned_op(v)))
+            .collect())
+    }
+
     // TODO: For now this is only checking for deletes
     // Once the validation is finished this should check for that as well
     fn get_dht_status<'r, R: Readable>(
@@ -1181,12 +1486,60 @@ where
         .into())
     }
 
-    fn get_canonical_entry_hash(&self, _entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
-        todo!("Cannot implement until redirects are implemented")
+    fn get_entry_redirect<'r, R: Readable>(
+        &'r self,
+        r: &'r R,
+        entry_hash: &EntryHash,
+    ) -> DatabaseResult<Option<(TimedHeaderHash, EntryHash)>> {
+        // An update without a redirect can't be followed
+        // so the next newest is tried
+        for update in self.live_updates(r, entry_hash.clone().into())? {
+            let key = MiscMetaKey::update_redirect(&update.header_hash).into();
+            if let Some(v) = self.misc_meta.get(r, &key)? {
+                return Ok(Some((update, MiscMetaValue::update_redirect(v))));
+            }
+        }
+        Ok(None)
     }
 
-    fn get_canonical_header_hash(&self, _header_hash: HeaderHash) -> DatabaseResult<HeaderHash> {
-        todo!("Cannot implement until redirects are implemented")
+    fn get_header_redirect<'r, R: Readable>(
+        &'r self,
+        r: &'r R,
+        header_hash: &HeaderHash,
+    ) -> DatabaseResult<Option<TimedHeaderHash>> {
+        Ok(self
+            .live_updates(r, header_hash.clone().into())?
+            .into_iter()
+            .next())
+    }
+
+    // Each hop must be newer than the last so an update back
+    // to an earlier version doesn't loop forever.
+    fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
+        fresh_reader!(self.env, |r| {
+            let mut entry_hash = entry_hash;
+            let mut last: Option<TimedHeaderHash> = None;
+            while let Some((update, next)) = self.get_entry_redirect(&r, &entry_hash)? {
+                if last.as_ref().map_or(false, |last| update <= *last) {
+                    break;
+                }
+                last = Some(update);
+                entry_hash = next;
+            }
+            Ok(entry_hash)
+        })
+    }
+
+    // An update contains the hash of the header it updates
+    // so header redirects can't form a loop.
+    fn get_canonical_header_hash(&self, header_hash: HeaderHash) -> DatabaseResult<HeaderHash> {
+        fresh_reader!(self.env, |r| {
+            let mut header_hash = header_hash;
+            while let Some(update) = self.get_header_redirect(&r, &header_hash)? {
+                header_hash = update.header_hash;
+            }
+            Ok(header_hash)
+        })
     }
 
     fn get_link_removes_on_link_add<'r, R: Readable>(
@@ -1231,6 +1584,12 @@ where
             .contains(&r, &MiscMetaKey::rejected_store_element(hash).into()))
     }
 
+    fn has_abandoned_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool> {
+        fresh_reader!(self.env, |r| self
+            .misc_meta
+            .contains(&r, &MiscMetaKey::abandoned_store_element(hash).into()))
+    }
+
     fn has_registered_store_entry(
         &self,
         entry_hash: &EntryHash,
@@ -1,6 +1,9 @@
 use ::fixt::prelude::*;
 use fallible_iterator::FallibleIterator;
+use holo_hash::fixt::DhtOpHashFixturator;
+use holo_hash::fixt::EntryHashFixturator;
 use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
 use holo_hash::HeaderHash;
 use holochain_lmdb::env::ReadManager;
 use holochain_lmdb::test_utils::test_cell_env;
@@ -163,3 +166,96 @@ async fn check_large_seq_queries() {
         &[(1, h2_hash), (256, h1_hash)]
     );
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn warrants_are_stored_per_agent() {
+    let (_te, mut meta_buf, h1, h2, agent_pubkey) = setup();
+    let mut other = fixt!(Create);
+    other.author = fixt!(AgentPubKey);
+    let invalid = Warrant::InvalidHeader(SignedHeader(h1.clone().into(), fixt!(Signature)));
+    let fork = Warrant::chain_fork(
+        SignedHeader(h1.into(), fixt!(Signature)),
+        SignedHeader(h2.into(), fixt!(Signature)),
+    );
+    let other_warrant = Warrant::InvalidHeader(SignedHeader(other.into(), fixt!(Signature)));
+    meta_buf.register_warrant(invalid.clone()).unwrap();
+    meta_buf.register_warrant(fork.clone()).unwrap();
+    // Registering the same warrant twice doesn't duplicate it
+    meta_buf.register_warrant(fork.clone()).unwrap();
+    meta_buf.register_warrant(other_warrant).unwrap();
+
+    let warrants = meta_buf.get_warrants(&agent_pubkey).unwrap();
+    assert_eq!(warrants.len(), 2);
+    assert!(warrants.contains(&invalid));
+    assert!(warrants.contains(&fork));
+
+    meta_buf.deregister_warrant(&invalid).unwrap();
+    assert_eq!(meta_buf.get_warrants(&agent_pubkey).unwrap(), vec![fork]);
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn abandoned_ops_are_stored_with_missing_dependencies() {
+    let (_te, mut meta_buf, h1, h2, _) = setup();
+    let op_1 = fixt!(DhtOpHash);
+    let op_2 = fixt!(DhtOpHash);
+    let deps_1: Vec<AnyDhtHash> = vec![HeaderHash::with_data_sync(&Header::from(h1)).into()];
+    let deps_2: Vec<AnyDhtHash> = vec![
+        HeaderHash::with_data_sync(&Header::from(h2)).into(),
+        fixt!(EntryHash).into(),
+    ];
+    meta_buf
+        .register_abandoned_op(op_1.clone(), deps_1.clone())
+        .unwrap();
+    meta_buf
+        .register_abandoned_op(op_2.clone(), deps_2.clone())
+        .unwrap();
+
+    let abandoned = meta_buf.get_abandoned_ops().unwrap();
+    assert_eq!(abandoned.len(), 2);
+    assert!(abandoned.contains(&(op_1.clone(), deps_1)));
+    assert!(abandoned.contains(&(op_2.clone(), deps_2.clone())));
+
+    meta_buf.deregister_abandoned_op(&op_1).unwrap();
+    assert_eq!(meta_buf.get_abandoned_ops().unwrap(), vec![(op_2, deps_2)]);
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn highest_observed_is_merged_across_authorities() {
+    let (_te, mut meta_buf, _, _, agent_pubkey) = setup();
+    let hashes: Vec<_> = HeaderHashFixturator::new(Predictable).take(3).collect();
+    let observed = |header_seq, hash: &[HeaderHash]| HighestObserved {
+        header_seq,
+        hash: hash.to_vec(),
+    };
+    meta_buf
+        .register_activity_observed(&agent_pubkey, observed(2, &hashes[..1]))
+        .unwrap();
+
+    // A lower sequence doesn't overwrite
+    meta_buf
+        .register_activity_observed(&agent_pubkey, observed(1, &hashes[1..2]))
+        .unwrap();
+    assert_eq!(
+        meta_buf.get_activity_observed(&agent_pubkey).unwrap(),
+        Some(observed(2, &hashes[..1]))
+    );
+
+    // Another authority seeing a different header at the same
+    // sequence reveals a fork so both hashes are kept
+    meta_buf
+        .register_activity_observed(&agent_pubkey, observed(2, &hashes[..2]))
+        .unwrap();
+    assert_eq!(
+        meta_buf.get_activity_observed(&agent_pubkey).unwrap(),
+        Some(observed(2, &hashes[..2]))
+    );
+
+    // A higher sequence overwrites
+    meta_buf
+        .register_activity_observed(&agent_pubkey, observed(3, &hashes[2..]))
+        .unwrap();
+    assert_eq!(
+        meta_buf.get_activity_observed(&agent_pubkey).unwrap(),
+        Some(observed(3, &hashes[2..]))
+    );
+}
@@ -78,6 +78,9 @@ pub enum SysMetaVal {
     CustomPackage(HeaderHash),
     /// Validation Status
     ValidationStatus(ValidationStatus),
+    /// A header that results in a new entry
+    /// but whose StoreEntry op was abandoned
+    AbandonedNewEntry(TimedHeaderHash),
 }
 
 // #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -209,6 +212,7 @@ impl From<SysMetaVal> for HeaderHash {
         match v {
             SysMetaVal::NewEntry(h)
             | SysMetaVal::RejectedNewEntry(h)
+            | SysMetaVal::AbandonedNewEntry(h)
             | SysMetaVal::Update(h)
             | SysMetaVal::Delete(h)
             | SysMetaVal::DeleteLink(h)
@@ -48,6 +48,36 @@ impl PrefixType for RejectedStoreElementPrefix {
     const PREFIX: u8 = 0x5;
 }
 
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+pub struct ChainWarrantPrefix;
+impl PrefixType for ChainWarrantPrefix {
+    const PREFIX: u8 = 0x6;
+}
+
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+pub struct AbandonedStoreElementPrefix;
+impl PrefixType for AbandonedStoreElementPrefix {
+    const PREFIX: u8 = 0x7;
+}
+
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+pub struct AbandonedOpPrefix;
+impl PrefixType for AbandonedOpPrefix {
+    const PREFIX: u8 = 0x8;
+}
+
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+pub struct UpdateRedirectPrefix;
+impl PrefixType for UpdateRedirectPrefix {
+    const PREFIX: u8 = 0x9;
+}
+
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+pub struct LinkAddTimePrefix;
+impl PrefixType for LinkAddTimePrefix {
+    const PREFIX: u8 = 0xa;
+}
+
 impl<P: PrefixType> MiscMetaKey<P> {
     /// Create a new prefix bytes key
     pub fn new<I: IntoIterator<Item = u8>>(bytes: I) -> Self {
@@ -62,7 +92,7 @@ impl<P: PrefixType> MiscMetaKey<P> {
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 /// Values for the misc kv
 /// Matches the key
 pub enum MiscMetaValue {
@@ -77,6 +107,20 @@ pub enum MiscMetaValue {
     ChainStatus(ChainStatus),
     /// The highest observed header for an agents chain.
     ChainObserved(HighestObserved),
+    /// A warrant against an agents chain.
+    Warrant(Warrant),
+    /// An op that was abandoned and the dependencies
+    /// it was missing when validation gave up on it.
+    AbandonedOp(DhtOpHash, Vec<AnyDhtHash>),
+    /// The entry an update header redirects its original entry to.
+    UpdateRedirect(EntryHash),
+    /// A link on a base, indexed by the time it was created
+    /// so pages of links can be read without every link on the base.
+    LinkAdd(LinkMetaVal),
+    /// The links added before links were indexed by time have been indexed.
+    LinkAddsBackfilled,
+    /// The updates integrated before update redirects were indexed have been indexed.
+    UpdateRedirectsBackfilled,
 }
 
 impl MiscMetaKey<EntryStatusPrefix> {
@@ -107,6 +151,84 @@ impl MiscMetaKey<RejectedStoreElementPrefix> {
     }
 }
 
+impl MiscMetaKey<AbandonedStoreElementPrefix> {
+    /// Create an abandoned store element key
+    pub fn abandoned_store_element(hash: &HeaderHash) -> MiscMetaKey<AbandonedStoreElementPrefix> {
+        let bytes: SerializedBytes = hash
+            .try_into()
+            .expect("Header Hash can't fail to serialize");
+        MiscMetaKey::new(bytes.bytes().iter().copied())
+    }
+}
+
+impl MiscMetaKey<AbandonedOpPrefix> {
+    /// Create a key that matches all abandoned ops
+    pub fn abandoned_ops() -> MiscMetaKey<AbandonedOpPrefix> {
+        MiscMetaKey::new(std::iter::empty())
+    }
+
+    /// Create an abandoned op key
+    pub fn abandoned_op(hash: &DhtOpHash) -> MiscMetaKey<AbandonedOpPrefix> {
+        let bytes: SerializedBytes = hash.try_into().expect("Op Hash can't fail to serialize");
+        MiscMetaKey::new(bytes.bytes().iter().copied())
+    }
+}
+
+impl MiscMetaKey<UpdateRedirectPrefix> {
+    /// Create the key that marks the redirects of every update as indexed
+    pub fn update_redirects_backfilled() -> MiscMetaKey<UpdateRedirectPrefix> {
+        MiscMetaKey::new(std::iter::empty())
+    }
+
+    /// Create an update redirect key
+    pub fn update_redirect(hash: &HeaderHash) -> MiscMetaKey<UpdateRedirectPrefix> {
+        let bytes: SerializedBytes = hash
+            .try_into()
+            .expect("Header Hash can't fail to serialize");
+        MiscMetaKey::new(bytes.bytes().iter().copied())
+    }
+}
+
+impl MiscMetaKey<LinkAddTimePrefix> {
+    /// Create the key that marks the links on every base as indexed.
+    /// Sorts before the links on any base.
+    pub fn link_adds_backfilled() -> MiscMetaKey<LinkAddTimePrefix> {
+        MiscMetaKey::new(std::iter::empty())
+    }
+
+    /// Create a key that matches all the links on a base
+    pub fn link_adds(base: &EntryHash) -> MiscMetaKey<LinkAddTimePrefix> {
+        let bytes: SerializedBytes = base.try_into().expect("Entry Hash can't fail to serialize");
+        MiscMetaKey::new(bytes.bytes().iter().copied())
+    }
+
+    /// Create a key to start iterating the links on a base from a time.
+    /// Sorts before every link on the base created at or after that time.
+    pub fn link_adds_from(
+        base: &EntryHash,
+        timestamp: Timestamp,
+    ) -> MiscMetaKey<LinkAddTimePrefix> {
+        let mut key = Self::link_adds(base);
+        key.prefix_and_bytes
+            .extend_from_slice(TimestampKey::from(timestamp).as_ref());
+        key
+    }
+
+    /// Create a key for a single link.
+    /// The creation time comes after the base so the links on a base
+    /// are ordered by time, with ties broken by the [CreateLink] hash.
+    pub fn link_add(
+        base: &EntryHash,
+        timestamp: Timestamp,
+        link_add_hash: &HeaderHash,
+    ) -> MiscMetaKey<LinkAddTimePrefix> {
+        let mut key = Self::link_adds_from(base, timestamp);
+        key.prefix_and_bytes
+            .extend_from_slice(link_add_hash.get_raw_39());
+        key
+    }
+}
+
 impl MiscMetaKey<ChainItemPrefix> {
     /// Create an chain item key
     pub fn chain_item(key: &ChainItemKey) -> MiscMetaKey<ChainItemPrefix> {
@@ -131,6 +253,29 @@ impl MiscMetaKey<ChainObservedPrefix> {
     }
 }
 
+impl MiscMetaKey<ChainWarrantPrefix> {
+    /// Create a key that matches all the warrants against an agent
+    pub fn chain_warrants(agent: &AgentPubKey) -> MiscMetaKey<ChainWarrantPrefix> {
+        let bytes: SerializedBytes = agent.try_into().expect("Agent key can't fail to serialize");
+        MiscMetaKey::new(bytes.bytes().iter().copied())
+    }
+
+    /// Create a key for a single warrant.
+    /// The agent comes first so all warrants for an agent can be
+    /// found with [MiscMetaKey::chain_warrants].
+    pub fn chain_warrant(warrant: &Warrant) -> MiscMetaKey<ChainWarrantPrefix> {
+        let agent: SerializedBytes = warrant
+            .agent()
+            .try_into()
+            .expect("Agent key can't fail to serialize");
+        let mut bytes = agent.bytes().to_vec();
+        for signed_header in warrant.evidence() {
+            bytes.extend(signed_header.signature().as_ref());
+        }
+        MiscMetaKey::new(bytes.into_iter())
+    }
+}
+
 impl<PM, PB> From<MiscMetaKey<PM>> for PrefixBytesKey<PB>
 where
     PM: PrefixType,
@@ -183,6 +328,34 @@ impl MiscMetaValue {
         }
     }
 
+    pub fn warrant(self) -> Warrant {
+        match self {
+            MiscMetaValue::Warrant(w) => w,
+            _ => unreachable!("Tried to go from {:?} to {:?}", self, "warrant"),
+        }
+    }
+
+    pub fn abandoned_op(self) -> (DhtOpHash, Vec<AnyDhtHash>) {
+        match self {
+            MiscMetaValue::AbandonedOp(hash, deps) => (hash, deps),
+            _ => unreachable!("Tried to go from {:?} to {:?}", self, "abandoned_op"),
+        }
+    }
+
+    pub fn update_redirect(self) -> EntryHash {
+        match self {
+            MiscMetaValue::UpdateRedirect(e) => e,
+            _ => unreachable!("Tried to go from {:?} to {:?}", self, "update_redirect"),
+        }
+    }
+
+    pub fn link_add(self) -> LinkMetaVal {
+        match self {
+            MiscMetaValue::LinkAdd(l) => l,
+            _ => unreachable!("Tried to go from {:?} to {:?}", self, "link_add"),
+        }
+    }
+
     pub fn new_store_element() -> Self {
         Self::StoreElement
     }
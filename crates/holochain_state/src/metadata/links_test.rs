@@ -7,6 +7,7 @@ use holochain_lmdb::fresh_reader_test;
 use holochain_lmdb::test_utils::test_cell_env;
 
 use observability;
+use std::num::NonZeroU32;
 
 #[derive(Clone)]
 struct TestData {
@@ -900,3 +901,100 @@ async fn links_on_same_tag() {
         );
     }
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn links_on_same_base_are_paged_in_time_order() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let arc = test_env.env();
+
+    let mut td = fixtures(arc.clone(), 10).await;
+    let base_hash = td[0].base_hash.clone();
+    // Give the links times in the reverse order they are added
+    for (i, d) in td.iter_mut().enumerate() {
+        d.base_hash = base_hash.clone();
+        d.link_add.base_address = base_hash.clone();
+        d.link_add.timestamp = Timestamp(10 - i as i64, 0);
+        let (_, link_add_hash): (_, HeaderHash) =
+            HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into();
+        d.expected_link.link_add_hash = link_add_hash.clone();
+        d.expected_link.timestamp = d.link_add.timestamp;
+        d.link_remove.link_add_address = link_add_hash;
+    }
+    let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+    for d in td.iter() {
+        d.add_link(&mut meta_buf).await;
+    }
+    // Removes don't change the pages
+    td[3].delete_link(&mut meta_buf).await;
+    let expected = td
+        .iter()
+        .rev()
+        .map(|d| d.expected_link.clone())
+        .collect::<Vec<_>>();
+
+    let key = LinkMetaKey::Base(&base_hash);
+    let mut page = LinkPageQuery::new().limit(NonZeroU32::new(4).unwrap());
+    let mut links = Vec::new();
+    fresh_reader_test!(arc, |r| loop {
+        let (mut results, next) = meta_buf.get_links_page(&r, &key, &page).unwrap();
+        assert!(results.len() <= 4);
+        links.append(&mut results);
+        match next {
+            Some(next) => page = page.after(next),
+            None => break,
+        }
+    });
+    assert_eq!(links, expected);
+
+    let page = LinkPageQuery::new()
+        .since(Timestamp(3, 0))
+        .until(Timestamp(6, 0));
+    let (links, next) =
+        fresh_reader_test!(arc, |r| meta_buf.get_links_page(&r, &key, &page).unwrap());
+    assert_eq!(links, expected[2..5].to_vec());
+    assert_eq!(next, None);
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn links_added_before_the_time_index_are_backfilled() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let arc = test_env.env();
+
+    let mut td = fixtures(arc.clone(), 3).await;
+    let base_hash = td[0].base_hash.clone();
+    for (i, d) in td.iter_mut().enumerate() {
+        d.base_hash = base_hash.clone();
+        d.link_add.base_address = base_hash.clone();
+        d.link_add.timestamp = Timestamp(i as i64, 0);
+        let (_, link_add_hash): (_, HeaderHash) =
+            HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into();
+        d.expected_link.link_add_hash = link_add_hash;
+        d.expected_link.timestamp = d.link_add.timestamp;
+    }
+    let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+    for d in td.iter() {
+        d.add_link(&mut meta_buf).await;
+        // Links from before the index only have a links table entry
+        let key = MiscMetaKey::link_add(
+            &base_hash,
+            d.link_add.timestamp,
+            &d.expected_link.link_add_hash,
+        );
+        meta_buf.misc_meta.delete(key.into()).unwrap();
+    }
+    let expected = td
+        .iter()
+        .map(|d| d.expected_link.clone())
+        .collect::<Vec<_>>();
+
+    let key = LinkMetaKey::Base(&base_hash);
+    let page = LinkPageQuery::new();
+    let (links, _) = fresh_reader_test!(arc, |r| meta_buf.get_links_page(&r, &key, &page).unwrap());
+    assert!(links.is_empty());
+
+    meta_buf.backfill_link_times().unwrap();
+    let (links, _) = fresh_reader_test!(arc, |r| meta_buf.get_links_page(&r, &key, &page).unwrap());
+    assert_eq!(links, expected);
+}
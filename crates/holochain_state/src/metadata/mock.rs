@@ -11,12 +11,24 @@ mock! {
             &self,
             key: &'a LinkMetaKey<'a>,
         ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError>>>;
+        fn get_links_page<'a>(
+            &self,
+            key: &'a LinkMetaKey<'a>,
+            page: &LinkPageQuery,
+        ) -> DatabaseResult<(Vec<LinkMetaVal>, Option<LinkCursor>)>;
         fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()>;
         fn delete_link(&mut self, link_remove: DeleteLink) -> DatabaseResult<()>;
         fn register_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
         fn register_rejected_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
+        fn register_abandoned_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
         fn register_element_header(&mut self, header: &Header) -> DatabaseResult<()>;
         fn register_rejected_element_header(&mut self, header: &Header) -> DatabaseResult<()>;
+        fn register_abandoned_element_header(&mut self, header: &Header) -> DatabaseResult<()>;
+        fn register_abandoned_op(
+            &mut self,
+            op_hash: DhtOpHash,
+            missing_dependencies: Vec<AnyDhtHash>,
+        ) -> DatabaseResult<()>;
         fn register_activity(
             &mut self,
             header: &Header,
@@ -42,12 +54,17 @@ mock! {
             observed: HighestObserved,
         ) -> DatabaseResult<()>;
         fn deregister_activity_observed(&mut self, agent: &AgentPubKey) -> DatabaseResult<()>;
+        fn register_warrant(&mut self, warrant: Warrant) -> DatabaseResult<()>;
+        fn deregister_warrant(&mut self, warrant: &Warrant) -> DatabaseResult<()>;
         fn register_update(&mut self, update: header::Update) -> DatabaseResult<()>;
         fn register_delete(&mut self, delete: header::Delete) -> DatabaseResult<()>;
         fn deregister_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
         fn deregister_rejected_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
+        fn deregister_abandoned_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
         fn deregister_element_header(&mut self, header: HeaderHash) -> DatabaseResult<()>;
         fn deregister_rejected_element_header(&mut self, header: HeaderHash) -> DatabaseResult<()>;
+        fn deregister_abandoned_element_header(&mut self, header: HeaderHash) -> DatabaseResult<()>;
+        fn deregister_abandoned_op(&mut self, op_hash: &DhtOpHash) -> DatabaseResult<()>;
         fn deregister_activity(
             &mut self,
             header: &Header,
@@ -70,6 +87,14 @@ mock! {
         fn deregister_add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()>;
         fn deregister_delete_link(&mut self, link_remove: DeleteLink) -> DatabaseResult<()>;
         fn get_dht_status(&self, entry_hash: &EntryHash) -> DatabaseResult<EntryDhtStatus>;
+        fn get_entry_redirect(
+            &self,
+            entry_hash: &EntryHash,
+        ) -> DatabaseResult<Option<(TimedHeaderHash, EntryHash)>>;
+        fn get_header_redirect(
+            &self,
+            header_hash: &HeaderHash,
+        ) -> DatabaseResult<Option<TimedHeaderHash>>;
         fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash>;
         fn get_canonical_header_hash(&self, header_hash: HeaderHash) -> DatabaseResult<HeaderHash>;
         fn get_headers(
@@ -84,6 +109,10 @@ mock! {
             &self,
             entry_hash: EntryHash,
         ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError>>>;
+        fn get_abandoned_headers(
+            &self,
+            entry_hash: EntryHash,
+        ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError>>>;
         fn get_activity(
             &self,
             key: ChainItemKey,
@@ -101,6 +130,8 @@ mock! {
         fn get_activity_status(&self, agent: &AgentPubKey) -> DatabaseResult<Option<ChainStatus>>;
         fn get_activity_observed(&self, agent: &AgentPubKey)
         -> DatabaseResult<Option<HighestObserved>>;
+        fn get_warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<Warrant>>;
+        fn get_abandoned_ops(&self) -> DatabaseResult<Vec<(DhtOpHash, Vec<AnyDhtHash>)>>;
         fn get_updates(
             &self,
             hash: AnyDhtHash,
@@ -124,6 +155,7 @@ mock! {
         fn has_valid_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
         fn has_any_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
         fn has_rejected_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
+        fn has_abandoned_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool>;
         fn has_registered_store_entry(&self, entry_hash: &EntryHash, header_hash: &HeaderHash) -> DatabaseResult<bool>;
         fn has_any_registered_store_entry(&self, hash: &EntryHash) -> DatabaseResult<bool>;
         fn env(&self) -> &EnvironmentRead;
@@ -149,6 +181,31 @@ impl MetadataBufT for MockMetadataBuf {
         MockMetadataBuf::get_links_all(&self, key)
     }
 
+    fn get_links_page<'r, 'k, R: Readable>(
+        &'r self,
+        _r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        page: &LinkPageQuery,
+    ) -> DatabaseResult<(Vec<LinkMetaVal>, Option<LinkCursor>)> {
+        MockMetadataBuf::get_links_page(&self, key, page)
+    }
+
+    fn get_entry_redirect<'r, R: Readable>(
+        &'r self,
+        _reader: &'r R,
+        entry_hash: &EntryHash,
+    ) -> DatabaseResult<Option<(TimedHeaderHash, EntryHash)>> {
+        self.get_entry_redirect(entry_hash)
+    }
+
+    fn get_header_redirect<'r, R: Readable>(
+        &'r self,
+        _reader: &'r R,
+        header_hash: &HeaderHash,
+    ) -> DatabaseResult<Option<TimedHeaderHash>> {
+        self.get_header_redirect(header_hash)
+    }
+
     fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
         self.get_canonical_entry_hash(entry_hash)
     }
@@ -183,6 +240,15 @@ impl MetadataBufT for MockMetadataBuf {
         self.get_rejected_headers(entry_hash)
     }
 
+    fn get_abandoned_headers<'r, R: Readable>(
+        &'r self,
+        _reader: &'r R,
+        entry_hash: EntryHash,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = TimedHeaderHash, Error = DatabaseError> + '_>>
+    {
+        self.get_abandoned_headers(entry_hash)
+    }
+
     fn get_all_headers<'r, R: Readable>(
         &'r self,
         _reader: &'r R,
@@ -230,6 +296,14 @@ impl MetadataBufT for MockMetadataBuf {
         self.get_activity_observed(agent)
     }
 
+    fn get_warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<Warrant>> {
+        self.get_warrants(agent)
+    }
+
+    fn get_abandoned_ops(&self) -> DatabaseResult<Vec<(DhtOpHash, Vec<AnyDhtHash>)>> {
+        self.get_abandoned_ops()
+    }
+
     fn get_updates<'r, R: Readable>(
         &'r self,
         _reader: &'r R,
@@ -296,6 +370,20 @@ impl MetadataBufT for MockMetadataBuf {
         self.deregister_rejected_header(new_entry_header)
     }
 
+    fn register_abandoned_header(
+        &mut self,
+        new_entry_header: NewEntryHeader,
+    ) -> DatabaseResult<()> {
+        self.register_abandoned_header(new_entry_header)
+    }
+
+    fn deregister_abandoned_header(
+        &mut self,
+        new_entry_header: NewEntryHeader,
+    ) -> DatabaseResult<()> {
+        self.deregister_abandoned_header(new_entry_header)
+    }
+
     fn register_element_header(&mut self, header: &Header) -> DatabaseResult<()> {
         self.register_element_header(header)
     }
@@ -308,6 +396,26 @@ impl MetadataBufT for MockMetadataBuf {
         self.deregister_rejected_element_header(hash)
     }
 
+    fn register_abandoned_element_header(&mut self, header: &Header) -> DatabaseResult<()> {
+        self.register_abandoned_element_header(header)
+    }
+
+    fn deregister_abandoned_element_header(&mut self, hash: HeaderHash) -> DatabaseResult<()> {
+        self.deregister_abandoned_element_header(hash)
+    }
+
+    fn register_abandoned_op(
+        &mut self,
+        op_hash: DhtOpHash,
+        missing_dependencies: Vec<AnyDhtHash>,
+    ) -> DatabaseResult<()> {
+        self.register_abandoned_op(op_hash, missing_dependencies)
+    }
+
+    fn deregister_abandoned_op(&mut self, op_hash: &DhtOpHash) -> DatabaseResult<()> {
+        self.deregister_abandoned_op(op_hash)
+    }
+
     fn register_activity(
         &mut self,
         header: &Header,
@@ -353,6 +461,12 @@ impl MetadataBufT for MockMetadataBuf {
     fn deregister_activity_observed(&mut self, agent: &AgentPubKey) -> DatabaseResult<()> {
         self.deregister_activity_observed(agent)
     }
+    fn register_warrant(&mut self, warrant: Warrant) -> DatabaseResult<()> {
+        self.register_warrant(warrant)
+    }
+    fn deregister_warrant(&mut self, warrant: &Warrant) -> DatabaseResult<()> {
+        self.deregister_warrant(warrant)
+    }
 
     fn register_update(&mut self, update: header::Update) -> DatabaseResult<()> {
         self.register_update(update)
@@ -443,6 +557,10 @@ impl MetadataBufT for MockMetadataBuf {
     fn has_rejected_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool> {
         self.has_rejected_registered_store_element(hash)
     }
+
+    fn has_abandoned_registered_store_element(&self, hash: &HeaderHash) -> DatabaseResult<bool> {
+        self.has_abandoned_registered_store_element(hash)
+    }
     fn has_registered_store_entry(
         &self,
         entry_hash: &EntryHash,
@@ -10,6 +10,7 @@ mod tests {
     use crate::metadata::EntryDhtStatus;
     use crate::metadata::MetadataBuf;
     use crate::metadata::MetadataBufT;
+    use crate::metadata::MiscMetaKey;
     use crate::metadata::TimedHeaderHash;
     use ::fixt::prelude::*;
     use fallible_iterator::FallibleIterator;
@@ -27,6 +28,7 @@ mod tests {
     use holochain_zome_types::header::EntryType;
     use holochain_zome_types::header::HeaderBuilder;
     use holochain_zome_types::header::HeaderBuilderCommon;
+    use holochain_zome_types::timestamp::Timestamp;
     use holochain_zome_types::HeaderHashed;
 
     struct TestFixtures {
@@ -84,6 +86,27 @@ mod tests {
         (update, header)
     }
 
+    /// Same as [test_update] but at a fixed time so updates can be ordered
+    async fn test_update_at(
+        original_header_address: HeaderHash,
+        entry_hash: EntryHash,
+        original_entry_address: EntryHash,
+        seconds: i64,
+        fx: &mut TestFixtures,
+    ) -> (header::Update, HeaderHashed) {
+        let builder = builder::Update {
+            original_entry_address,
+            original_header_address,
+            entry_hash,
+            entry_type: fx.entry_type(),
+        };
+        let mut common = fx.common();
+        common.timestamp = Timestamp(seconds, 0);
+        let update = builder.build(common);
+        let header = HeaderHashed::from_content_sync(update.clone().into());
+        (update, header)
+    }
+
     async fn test_create(
         entry_hash: EntryHash,
         fx: &mut TestFixtures,
@@ -112,7 +135,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that a header can be redirected a single hop
     async fn test_redirect_header_one_hop() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -137,7 +159,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that a header can be redirected three hops
     async fn test_redirect_header_three_hops() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -179,7 +200,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that an entry can be redirected a single hop
     async fn test_redirect_entry_one_hop() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -212,7 +232,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that an entry can be redirected three hops
     async fn test_redirect_entry_three_hops() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -226,24 +245,27 @@ mod tests {
                 .1
                 .into_inner()
                 .1;
-            let (update1, _) = test_update(
+            let (update1, header1) = test_update_at(
                 header_hash,
                 fx.entry_hash(),
                 original_entry.clone(),
+                1,
                 &mut fx,
             )
             .await;
-            let (update2, _) = test_update(
-                update1.original_header_address.clone(),
+            let (update2, header2) = test_update_at(
+                header1.into_hash(),
                 fx.entry_hash(),
-                original_entry.clone(),
+                update1.entry_hash.clone(),
+                2,
                 &mut fx,
             )
             .await;
-            let (update3, _) = test_update(
-                update2.original_header_address.clone(),
+            let (update3, _) = test_update_at(
+                header2.into_hash(),
                 fx.entry_hash(),
-                original_entry.clone(),
+                update2.entry_hash.clone(),
+                3,
                 &mut fx,
             )
             .await;
@@ -260,8 +282,7 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
-    /// Test that a header can be redirected a single hop
+    /// Test that headers and entries are redirected independently
     async fn test_redirect_header_and_entry() -> anyhow::Result<()> {
         let test_env = test_cell_env();
         let arc = test_env.env();
@@ -298,7 +319,7 @@ mod tests {
             let original_header_hash = update_header.original_header_address;
             let canonical_header_hash =
                 buf.get_canonical_header_hash(original_header_hash.clone())?;
-            let canonical_entry_hash = buf.get_canonical_entry_hash(original_entry_1)?;
+            let canonical_entry_hash = buf.get_canonical_entry_hash(original_entry)?;
 
             assert_eq!(&canonical_header_hash, expected_header.as_hash());
             assert_eq!(canonical_entry_hash, expected_entry_hash);
@@ -306,6 +327,141 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    /// Test that branching updates redirect to the newest live update
+    /// no matter what order they were registered in
+    async fn test_redirect_entry_branches_to_newest() -> anyhow::Result<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let mut fx = TestFixtures::new();
+        {
+            let mut buf = MetadataBuf::vault(arc.clone().into())?;
+            let original_entry = fx.entry_hash();
+            let header_hash = test_create(original_entry.clone(), &mut fx)
+                .await
+                .1
+                .into_inner()
+                .1;
+            let mut updates = Vec::new();
+            for seconds in 1..4 {
+                updates.push(
+                    test_update_at(
+                        header_hash.clone(),
+                        fx.entry_hash(),
+                        original_entry.clone(),
+                        seconds,
+                        &mut fx,
+                    )
+                    .await,
+                );
+            }
+            for (update, _) in updates.iter().rev() {
+                buf.register_update(update.clone())?;
+            }
+
+            let canonical = buf.get_canonical_entry_hash(original_entry.clone())?;
+            assert_eq!(canonical, updates[2].0.entry_hash);
+            let canonical = buf.get_canonical_header_hash(header_hash.clone())?;
+            assert_eq!(&canonical, updates[2].1.as_hash());
+
+            // Deleting the newest update falls back to the next newest
+            let (delete, _) = test_delete(
+                updates[2].1.as_hash().clone(),
+                updates[2].0.entry_hash.clone(),
+                &mut fx,
+            )
+            .await;
+            buf.register_delete(delete)?;
+
+            let canonical = buf.get_canonical_entry_hash(original_entry)?;
+            assert_eq!(canonical, updates[1].0.entry_hash);
+            let canonical = buf.get_canonical_header_hash(header_hash)?;
+            assert_eq!(&canonical, updates[1].1.as_hash());
+        }
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    /// Test that an update integrated before its redirect was indexed
+    /// is skipped until the index is backfilled
+    async fn test_redirect_entry_backfill() -> anyhow::Result<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let mut fx = TestFixtures::new();
+        {
+            let mut buf = MetadataBuf::vault(arc.clone().into())?;
+            let original_entry = fx.entry_hash();
+            let header_hash = test_create(original_entry.clone(), &mut fx)
+                .await
+                .1
+                .into_inner()
+                .1;
+            let (older, _) = test_update_at(
+                header_hash.clone(),
+                fx.entry_hash(),
+                original_entry.clone(),
+                1,
+                &mut fx,
+            )
+            .await;
+            let (newer, newer_header) = test_update_at(
+                header_hash,
+                fx.entry_hash(),
+                original_entry.clone(),
+                2,
+                &mut fx,
+            )
+            .await;
+            buf.register_update(older.clone())?;
+            buf.register_update(newer.clone())?;
+            // The newer update was integrated before redirects were indexed
+            buf.misc_meta
+                .delete(MiscMetaKey::update_redirect(newer_header.as_hash()).into())?;
+
+            let canonical = buf.get_canonical_entry_hash(original_entry.clone())?;
+            assert_eq!(canonical, older.entry_hash);
+
+            buf.backfill_update_redirects(|| Ok(vec![older, newer.clone()]))?;
+            let canonical = buf.get_canonical_entry_hash(original_entry)?;
+            assert_eq!(canonical, newer.entry_hash);
+
+            // The backfill only runs once
+            buf.backfill_update_redirects(|| unreachable!("already backfilled"))?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    /// Test that updating an entry back to an earlier version
+    /// redirects to the newest version instead of looping
+    async fn test_redirect_entry_back_to_earlier_version() -> anyhow::Result<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let mut fx = TestFixtures::new();
+        {
+            let mut buf = MetadataBuf::vault(arc.clone().into())?;
+            let entry_a = fx.entry_hash();
+            let entry_b = fx.entry_hash();
+            let header_hash = test_create(entry_a.clone(), &mut fx).await.1.into_inner().1;
+            let (a_to_b, header) =
+                test_update_at(header_hash, entry_b.clone(), entry_a.clone(), 1, &mut fx).await;
+            let (b_to_a, _) = test_update_at(
+                header.into_hash(),
+                entry_a.clone(),
+                entry_b.clone(),
+                2,
+                &mut fx,
+            )
+            .await;
+            buf.register_update(a_to_b)?;
+            buf.register_update(b_to_a)?;
+
+            assert_eq!(buf.get_canonical_entry_hash(entry_a.clone())?, entry_a);
+            assert_eq!(buf.get_canonical_entry_hash(entry_b)?, entry_a);
+        }
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn add_entry_get_headers() {
         let test_env = test_cell_env();
@@ -2,6 +2,8 @@ pub use crate::chain_sequence::*;
 pub use crate::dht_op_integration::*;
 pub use crate::element_buf::*;
 pub use crate::metadata::*;
+pub use crate::publish_queue_db::*;
+pub use crate::schedule_db::*;
 pub use crate::source_chain::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts_db::*;
@@ -0,0 +1,296 @@
+//! # Publish Queue
+//!
+//! The authored DhtOps that still need to be published because they don't
+//! have enough validation receipts yet. Each op is retried with an
+//! exponential backoff so the cost of publishing scales with the number of
+//! ops still waiting for receipts rather than the size of the source chain.
+
+use fallible_iterator::FallibleIterator;
+use holo_hash::DhtOpHash;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::PUBLISH_QUEUE;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+use std::time::Duration;
+
+/// When a queued op should next be published.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PublishQueueValue {
+    /// The earliest time the op should next be published
+    pub next_publish: Timestamp,
+    /// How many times the op has been published so far
+    pub attempts: u32,
+}
+
+impl PublishQueueValue {
+    /// A newly authored op that can be published straight away
+    pub fn new(next_publish: Timestamp) -> Self {
+        Self {
+            next_publish,
+            attempts: 0,
+        }
+    }
+
+    /// Is this op due to be published at this time?
+    pub fn is_due(&self, now: &Timestamp) -> bool {
+        self.next_publish <= *now
+    }
+
+    /// How long to wait before publishing again.
+    /// Starts at `min_interval` and doubles with every attempt
+    /// until it reaches `max_interval`.
+    pub fn backoff(&self, min_interval: Duration, max_interval: Duration) -> Duration {
+        2u32.checked_pow(self.attempts)
+            .and_then(|factor| min_interval.checked_mul(factor))
+            .map(|interval| interval.min(max_interval))
+            .unwrap_or(max_interval)
+    }
+
+    /// Record that the op was published at `now`
+    /// and schedule the next attempt.
+    pub fn published(&mut self, now: Timestamp, min_interval: Duration, max_interval: Duration) {
+        let interval = self.backoff(min_interval, max_interval);
+        self.next_publish = (now + interval).unwrap_or(now);
+        self.attempts = self.attempts.saturating_add(1);
+    }
+}
+
+/// Key for the [PublishQueue] database.
+/// The time an op is next due comes first so the
+/// queue is ordered by when ops need publishing.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct PublishQueueKey(Vec<u8>);
+
+impl PublishQueueKey {
+    /// Create the key for an op due at this time
+    pub fn new(next_publish: Timestamp, op_hash: &DhtOpHash) -> Self {
+        let mut key = TimestampKey::from(next_publish).as_ref().to_vec();
+        key.extend_from_slice(op_hash.get_raw_39());
+        Self(key)
+    }
+
+    /// Marks that the authored ops from before the queue existed
+    /// have been added. It sorts after any real time so it is never due.
+    fn backfilled() -> Self {
+        Self(vec![0xff])
+    }
+
+    /// Get the op hash from the raw key bytes,
+    /// or None if this is not an op key.
+    fn op_hash(bytes: &[u8]) -> Option<DhtOpHash> {
+        if bytes.len() > TS_SIZE {
+            Some(DhtOpHash::from_raw_39_panicky(bytes[TS_SIZE..].to_vec()))
+        } else {
+            None
+        }
+    }
+}
+
+impl AsRef<[u8]> for PublishQueueKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for PublishQueueKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+/// The database of ops waiting to be published
+pub struct PublishQueue(KvBufFresh<PublishQueueKey, PublishQueueValue>);
+
+impl PublishQueue {
+    /// Create a new PublishQueue
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*PUBLISH_QUEUE)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Add a newly authored op so it is published as soon as possible
+    pub fn add(&mut self, op_hash: DhtOpHash, now: Timestamp) -> DatabaseResult<()> {
+        self.put(op_hash, PublishQueueValue::new(now))
+    }
+
+    /// Queue an op to be published at `value.next_publish`.
+    /// Any previous entry for the op must be removed first.
+    pub fn put(&mut self, op_hash: DhtOpHash, value: PublishQueueValue) -> DatabaseResult<()> {
+        self.0
+            .put(PublishQueueKey::new(value.next_publish, &op_hash), value)
+    }
+
+    /// Remove the entry for an op
+    pub fn remove(&mut self, op_hash: &DhtOpHash, value: &PublishQueueValue) -> DatabaseResult<()> {
+        self.0
+            .delete(PublishQueueKey::new(value.next_publish, op_hash))
+    }
+
+    /// Get all the queued ops in the order they are due
+    pub fn all(&self) -> DatabaseResult<Vec<(DhtOpHash, PublishQueueValue)>> {
+        fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter(&r)?
+            .filter_map(|(k, v)| Ok(PublishQueueKey::op_hash(k).map(|hash| (hash, v))))
+            .collect::<Vec<_>>())
+    }
+
+    /// Get all the ops which are due to be published at this time.
+    /// Only the due ops are read.
+    pub fn due(&self, now: &Timestamp) -> DatabaseResult<Vec<(DhtOpHash, PublishQueueValue)>> {
+        fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter(&r)?
+            .take_while(|(k, v)| Ok(PublishQueueKey::op_hash(k).is_some() && v.is_due(now)))
+            .filter_map(|(k, v)| Ok(PublishQueueKey::op_hash(k).map(|hash| (hash, v))))
+            .collect::<Vec<_>>())
+    }
+
+    /// The next time any op is due to be published
+    pub fn next_publish(&self) -> DatabaseResult<Option<Timestamp>> {
+        fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter(&r)?
+            .filter_map(|(k, v)| Ok(PublishQueueKey::op_hash(k).map(|_| v.next_publish)))
+            .next())
+    }
+
+    /// Have the authored ops from before the queue existed been added
+    pub fn is_backfilled(&self) -> DatabaseResult<bool> {
+        self.0.contains(&PublishQueueKey::backfilled())
+    }
+
+    /// Record that the authored ops from before the queue existed have been added
+    pub fn mark_backfilled(&mut self) -> DatabaseResult<()> {
+        self.0.put(
+            PublishQueueKey::backfilled(),
+            PublishQueueValue::new(Timestamp(i64::MAX, 0)),
+        )
+    }
+}
+
+impl BufferedStore for PublishQueue {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.0.is_clean()
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::DhtOpHashFixturator;
+    use holochain_lmdb::env::WriteManager;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let min = Duration::from_secs(5);
+        let max = Duration::from_secs(60);
+        let now = holochain_types::timestamp::now();
+        let mut value = PublishQueueValue::new(now);
+        let mut intervals = Vec::new();
+        for _ in 0..6 {
+            intervals.push(value.backoff(min, max));
+            value.published(now, min, max);
+        }
+        assert_eq!(
+            intervals,
+            [5, 10, 20, 40, 60, 60]
+                .iter()
+                .map(|s| Duration::from_secs(*s))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(value.attempts, 6);
+        assert_eq!(value.next_publish, (now + max).unwrap());
+
+        // Doesn't overflow after many attempts
+        value.attempts = u32::MAX;
+        assert_eq!(value.backoff(min, max), max);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn publish_queue_due_and_remove() -> DatabaseResult<()> {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let now = holochain_types::timestamp::now();
+        let later = (now + Duration::from_secs(60)).unwrap();
+        let a = fixt!(DhtOpHash);
+        let b = fixt!(DhtOpHash);
+
+        {
+            let mut queue = PublishQueue::new(env.clone().into())?;
+            queue.add(a.clone(), now)?;
+            queue.put(b.clone(), PublishQueueValue::new(later))?;
+            env.guard()
+                .with_commit(|writer| queue.flush_to_txn_ref(writer))?;
+        }
+
+        let mut queue = PublishQueue::new(env.clone().into())?;
+        assert!(!queue.is_backfilled()?);
+        assert_eq!(queue.all()?.len(), 2);
+        assert_eq!(queue.next_publish()?, Some(now));
+        assert_eq!(
+            queue.due(&now)?,
+            vec![(a.clone(), PublishQueueValue::new(now))]
+        );
+
+        queue.remove(&a, &PublishQueueValue::new(now))?;
+        assert_eq!(
+            queue.all()?,
+            vec![(b.clone(), PublishQueueValue::new(later))]
+        );
+        assert!(queue.due(&now)?.is_empty());
+        assert_eq!(queue.next_publish()?, Some(later));
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn publish_queue_is_ordered_by_time() -> DatabaseResult<()> {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let now = holochain_types::timestamp::now();
+        let times = (0..5)
+            .map(|i| (now + Duration::from_secs(i * 10)).unwrap())
+            .collect::<Vec<_>>();
+        let hashes = DhtOpHashFixturator::new(Unpredictable)
+            .take(times.len())
+            .collect::<Vec<_>>();
+
+        {
+            let mut queue = PublishQueue::new(env.clone().into())?;
+            queue.mark_backfilled()?;
+            // Added in reverse so the order comes from the key
+            for (hash, time) in hashes.iter().zip(times.iter()).rev() {
+                queue.put(hash.clone(), PublishQueueValue::new(*time))?;
+            }
+            env.guard()
+                .with_commit(|writer| queue.flush_to_txn_ref(writer))?;
+        }
+
+        let queue = PublishQueue::new(env.clone().into())?;
+        assert!(queue.is_backfilled()?);
+        // The backfill marker is never an op
+        assert_eq!(queue.all()?.len(), times.len());
+        assert_eq!(queue.next_publish()?, Some(times[0]));
+        let due = queue
+            .due(&times[2])?
+            .into_iter()
+            .map(|(hash, _)| hash)
+            .collect::<Vec<_>>();
+        assert_eq!(due, hashes[0..3].to_vec());
+        Ok(())
+    }
+}
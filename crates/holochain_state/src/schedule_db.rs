@@ -0,0 +1,196 @@
+//! # Scheduled Zome Functions
+//!
+//! Zome functions that a zome has asked the host to call at some later time.
+//! Each (zome, function) pair can only be scheduled once, so scheduling the
+//! same function again replaces the previous schedule.
+
+use fallible_iterator::FallibleIterator;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::SCHEDULED_FNS;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+/// A zome function which is waiting to be called by the host.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledFn {
+    /// The zome that scheduled the function
+    pub zome_name: ZomeName,
+    /// The function to call
+    pub fn_name: FunctionName,
+    /// The earliest time the function should next be called
+    pub next_run: Timestamp,
+    /// If set, how long to wait between calls after the first one
+    pub interval: Option<core::time::Duration>,
+}
+
+impl ScheduledFn {
+    /// Constructor
+    pub fn new(
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        next_run: Timestamp,
+        interval: Option<core::time::Duration>,
+    ) -> Self {
+        Self {
+            zome_name,
+            fn_name,
+            next_run,
+            interval,
+        }
+    }
+
+    /// Is this function due to be called at this time?
+    pub fn is_due(&self, now: &Timestamp) -> bool {
+        self.next_run <= *now
+    }
+
+    /// The key this function is stored under
+    pub fn key(&self) -> ScheduledFnKey {
+        ScheduledFnKey::new(&self.zome_name, &self.fn_name)
+    }
+}
+
+/// Key for the [ScheduledFn] database.
+/// Made from the zome and function name so each function
+/// can only be scheduled once.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct ScheduledFnKey(Vec<u8>);
+
+impl ScheduledFnKey {
+    /// Create the key for a zome function
+    pub fn new(zome_name: &ZomeName, fn_name: &FunctionName) -> Self {
+        let mut key = zome_name.0.as_bytes().to_vec();
+        // Zome names can't contain null bytes so this
+        // separator keeps the keys unique.
+        key.push(0);
+        key.extend_from_slice(fn_name.0.as_bytes());
+        Self(key)
+    }
+}
+
+impl AsRef<[u8]> for ScheduledFnKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for ScheduledFnKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+/// The database of functions scheduled to be called by the host
+pub struct ScheduleStore(KvBufFresh<ScheduledFnKey, ScheduledFn>);
+
+impl ScheduleStore {
+    /// Create a new ScheduleStore
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*SCHEDULED_FNS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Schedule a function, replacing any existing schedule for it
+    pub fn schedule(&mut self, scheduled_fn: ScheduledFn) -> DatabaseResult<()> {
+        self.0.put(scheduled_fn.key(), scheduled_fn)
+    }
+
+    /// Remove a function from the schedule
+    pub fn unschedule(
+        &mut self,
+        zome_name: &ZomeName,
+        fn_name: &FunctionName,
+    ) -> DatabaseResult<()> {
+        self.0.delete(ScheduledFnKey::new(zome_name, fn_name))
+    }
+
+    /// Get all the scheduled functions
+    pub fn all(&self) -> DatabaseResult<Vec<ScheduledFn>> {
+        fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter(&r)?
+            .map(|(_, v)| Ok(v))
+            .collect::<Vec<_>>())
+    }
+
+    /// Get all the functions which are due to be called at this time
+    pub fn due(&self, now: &Timestamp) -> DatabaseResult<Vec<ScheduledFn>> {
+        Ok(self
+            .all()?
+            .into_iter()
+            .filter(|scheduled_fn| scheduled_fn.is_due(now))
+            .collect())
+    }
+
+    /// The next time any function is due to be called
+    pub fn next_run(&self) -> DatabaseResult<Option<Timestamp>> {
+        Ok(self
+            .all()?
+            .into_iter()
+            .map(|scheduled_fn| scheduled_fn.next_run)
+            .min())
+    }
+}
+
+impl BufferedStore for ScheduleStore {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.0.is_clean()
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::env::WriteManager;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn schedule_due_and_replace() -> DatabaseResult<()> {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let now = holochain_types::timestamp::now();
+        let later = (now + core::time::Duration::from_secs(60)).unwrap();
+        let zome_name: ZomeName = "foo".into();
+
+        {
+            let mut store = ScheduleStore::new(env.clone().into())?;
+            store.schedule(ScheduledFn::new(zome_name.clone(), "a".into(), now, None))?;
+            store.schedule(ScheduledFn::new(
+                zome_name.clone(),
+                "b".into(),
+                later,
+                Some(core::time::Duration::from_secs(1)),
+            ))?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn_ref(writer))?;
+        }
+
+        let mut store = ScheduleStore::new(env.clone().into())?;
+        assert_eq!(store.all()?.len(), 2);
+        assert_eq!(store.next_run()?, Some(now));
+        let due = store.due(&now)?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].fn_name, "a".into());
+
+        // Scheduling the same function replaces the old schedule
+        store.schedule(ScheduledFn::new(zome_name.clone(), "a".into(), later, None))?;
+        assert!(store.due(&now)?.is_empty());
+        assert_eq!(store.all()?.len(), 2);
+
+        store.unschedule(&zome_name, &"b".into())?;
+        assert_eq!(store.all()?.len(), 1);
+        assert_eq!(store.next_run()?, Some(later));
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ use holochain_lmdb::prelude::*;
 use holochain_types::prelude::*;
 use shrinkwraprs::Shrinkwrap;
 pub use source_chain_buffer::*;
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 mod error;
@@ -108,67 +109,7 @@ impl SourceChain {
         // chain to see if there is a local grant that is valid for the provided secret/agent
         // combination
         let committed_valid_grant = fresh_reader!(self.env(), |r| {
-            let (references, headers): (
-                HashSet<HeaderHash>,
-                Vec<HoloHashed<holochain_zome_types::element::SignedHeader>>,
-            ) = self
-                .0
-                .headers()
-                .iter_fail(&r)?
-                .filter(|header| {
-                    Ok(match header.as_content().header() {
-                        Header::Create(create) => {
-                            // filter out authorship and everything else
-                            matches!(create.entry_type, EntryType::CapGrant)
-                        }
-                        Header::Update(update) => matches!(
-                            update.entry_type,
-                            // filter out authorship and everything else
-                            EntryType::CapGrant
-                        ),
-                        Header::Delete(_) => true,
-                        // no other headers are relevant
-                        _ => false,
-                    })
-                })
-                // extract all the header references
-                // if a header is referenced by an update/delete then it is no longer valid
-                // with all the references in a bucket we can use it to filter out entries below
-                .fold(
-                    (HashSet::new(), vec![]),
-                    |(mut references, mut headers), header| {
-                        match header.as_content().header() {
-                            Header::Update(update) => {
-                                references.insert(update.original_header_address.clone());
-                            }
-                            Header::Delete(delete) => {
-                                references.insert(delete.deletes_address.clone());
-                            }
-                            _ => {}
-                        }
-                        // this is a best-effort attempt to avoid putting things we already know as
-                        // referenced into the returned vec
-                        // it isn't comprehensive because it relies on ordering but it's an easy
-                        // and relatively safe optimisation to not do further processing here
-                        if !references.contains(header.as_hash()) {
-                            headers.push(header);
-                        }
-
-                        Ok((references, headers))
-                    },
-                )?;
-
-            // second pass over the headers to make sure that all referenced headers are removed
-            // this makes the process reliable even if the iterators don't follow the chain order
-            let live_cap_grants: HashSet<_> = headers
-                .iter()
-                .filter(|header| !references.contains(header.as_hash()))
-                .filter_map(|header| match header.as_content().header() {
-                    Header::Create(create) => Some(create.entry_hash.clone()),
-                    Header::Update(update) => Some(update.entry_hash.clone()),
-                    _ => None,
-                })
-                .collect();
+            let live_cap_grants = self.live_entry_headers(&r, &EntryType::CapGrant)?;
 
             self
             .0
@@ -180,7 +121,7 @@ impl SourceChain {
             .iter_fail(&r)?
             // ensure we respect the header filtering we already did above
             .filter(|entry| {
-                Ok(live_cap_grants.contains(entry.as_hash()))
+                Ok(live_cap_grants.contains_key(entry.as_hash()))
             })
             .filter_map(|entry| Ok(entry.as_cap_grant()))
             // filter down to only the grants for this function
@@ -236,6 +177,148 @@ impl SourceChain {
         Ok(committed_valid_grant)
     }
 
+    /// All the live (committed and not updated or deleted) CapGrants on the local chain,
+    /// optionally filtered by tag.
+    ///
+    /// The same grant committed by several headers is returned once for each header.
+    pub fn live_cap_grants(&self, tag: Option<&str>) -> SourceChainResult<Vec<LiveCapGrant>> {
+        let grants = fresh_reader!(self.env(), |r| {
+            let live_cap_grants = self.live_entry_headers(&r, &EntryType::CapGrant)?;
+            self.0
+                .elements()
+                .private_entries()
+                .expect(
+                    "SourceChainBuf must have access to private entries in order to access CapGrants",
+                )
+                .iter_fail(&r)?
+                .filter_map(|entry| {
+                    let (entry, entry_hash) = entry.into_inner();
+                    Ok(match (entry, live_cap_grants.get(&entry_hash)) {
+                        (Entry::CapGrant(grant), Some(header_hashes)) => {
+                            Some((grant, header_hashes.clone()))
+                        }
+                        _ => None,
+                    })
+                })
+                .filter(|(grant, _)| Ok(tag.map_or(true, |tag| grant.tag == tag)))
+                .collect::<Vec<_>>()
+        })?;
+        Ok(grants
+            .into_iter()
+            .flat_map(|(grant, header_hashes)| {
+                header_hashes
+                    .into_iter()
+                    .map(move |header_hash| LiveCapGrant {
+                        header_hash,
+                        grant: grant.clone(),
+                    })
+            })
+            .collect())
+    }
+
+    /// All the live CapClaims on the local chain, optionally filtered by tag.
+    ///
+    /// The same claim committed by several headers is returned once for each header.
+    pub fn live_cap_claims(&self, tag: Option<&str>) -> SourceChainResult<Vec<CapClaim>> {
+        let claims = fresh_reader!(self.env(), |r| {
+            let live_cap_claims = self.live_entry_headers(&r, &EntryType::CapClaim)?;
+            self.0
+                .elements()
+                .private_entries()
+                .expect(
+                    "SourceChainBuf must have access to private entries in order to access CapClaims",
+                )
+                .iter_fail(&r)?
+                .filter_map(|entry| {
+                    let (entry, entry_hash) = entry.into_inner();
+                    Ok(match (entry, live_cap_claims.get(&entry_hash)) {
+                        (Entry::CapClaim(claim), Some(header_hashes)) => {
+                            Some((claim, header_hashes.len()))
+                        }
+                        _ => None,
+                    })
+                })
+                .filter(|(claim, _)| Ok(tag.map_or(true, |tag| claim.tag() == tag)))
+                .collect::<Vec<_>>()
+        })?;
+        Ok(claims
+            .into_iter()
+            .flat_map(|(claim, count)| std::iter::repeat(claim).take(count))
+            .collect())
+    }
+
+    /// Find the entries of the given type that are live on the local chain,
+    /// i.e. committed by a Create or Update that has not since been updated or deleted.
+    /// Returns the live entry hashes with every live header that committed them,
+    /// as the same entry may be committed more than once.
+    fn live_entry_headers<R: Readable>(
+        &self,
+        r: &R,
+        entry_type: &EntryType,
+    ) -> DatabaseResult<HashMap<EntryHash, Vec<HeaderHash>>> {
+        let (references, headers): (
+            HashSet<HeaderHash>,
+            Vec<HoloHashed<holochain_zome_types::element::SignedHeader>>,
+        ) = self
+            .0
+            .headers()
+            .iter_fail(r)?
+            .filter(|header| {
+                Ok(match header.as_content().header() {
+                    // filter out authorship and everything else
+                    Header::Create(create) => &create.entry_type == entry_type,
+                    Header::Update(update) => &update.entry_type == entry_type,
+                    Header::Delete(_) => true,
+                    // no other headers are relevant
+                    _ => false,
+                })
+            })
+            // extract all the header references
+            // if a header is referenced by an update/delete then it is no longer valid
+            // with all the references in a bucket we can use it to filter out entries below
+            .fold(
+                (HashSet::new(), vec![]),
+                |(mut references, mut headers), header| {
+                    match header.as_content().header() {
+                        Header::Update(update) => {
+                            references.insert(update.original_header_address.clone());
+                        }
+                        Header::Delete(delete) => {
+                            references.insert(delete.deletes_address.clone());
+                        }
+                        _ => {}
+                    }
+                    // this is a best-effort attempt to avoid putting things we already know as
+                    // referenced into the returned vec
+                    // it isn't comprehensive because it relies on ordering but it's an easy
+                    // and relatively safe optimisation to not do further processing here
+                    if !references.contains(header.as_hash()) {
+                        headers.push(header);
+                    }
+
+                    Ok((references, headers))
+                },
+            )?;
+
+        // second pass over the headers to make sure that all referenced headers are removed
+        // this makes the process reliable even if the iterators don't follow the chain order
+        let mut live: HashMap<EntryHash, Vec<HeaderHash>> = HashMap::new();
+        for header in headers
+            .iter()
+            .filter(|header| !references.contains(header.as_hash()))
+        {
+            let entry_hash = match header.as_content().header() {
+                Header::Create(create) => &create.entry_hash,
+                Header::Update(update) => &update.entry_hash,
+                _ => continue,
+            };
+            live.entry(entry_hash.clone())
+                .or_default()
+                .push(header.as_hash().clone());
+        }
+        Ok(live)
+    }
+
     // @todo bring all this back when we want to administer cap claims better
     //         /// Fetch a CapClaim from the private entries.
     //         ///
@@ -484,6 +567,72 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn test_live_cap_grants_keeps_duplicates() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let mut functions: GrantedFunctions = HashSet::new();
+        functions.insert(("foo".into(), "bar".into()));
+        let grant = ZomeCallCapGrant::new("tag".into(), CapAccess::from(secret), functions);
+        let alice = AgentPubKeyFixturator::new(Predictable).next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice, None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        // commit the exact same grant twice
+        let (first_header, entry_hash) = {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            let mut headers = Vec::new();
+            let mut entry_hash = None;
+            for _ in 0..2 {
+                let (entry, hash) =
+                    EntryHashed::from_content_sync(Entry::CapGrant(grant.clone())).into_inner();
+                let header_builder = builder::Create {
+                    entry_type: EntryType::CapGrant,
+                    entry_hash: hash.clone(),
+                };
+                headers.push(chain.put(header_builder, Some(entry)).await?);
+                entry_hash = Some(hash);
+            }
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+
+            let chain = SourceChain::new(env.clone().into())?;
+            let mut live: Vec<HeaderHash> = chain
+                .live_cap_grants(None)?
+                .into_iter()
+                .map(|live| live.header_hash)
+                .collect();
+            live.sort();
+            headers.sort();
+            // both grants are kept, one per header
+            assert_eq!(live, headers);
+            (headers[0].clone(), entry_hash.unwrap())
+        };
+
+        // deleting one of them leaves the other live
+        {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            let header_builder = builder::Delete {
+                deletes_address: first_header.clone(),
+                deletes_entry_address: entry_hash,
+            };
+            chain.put(header_builder, None).await?;
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+        }
+        let chain = SourceChain::new(env.clone().into())?;
+        let live = chain.live_cap_grants(None)?;
+        assert_eq!(live.len(), 1);
+        assert_ne!(live[0].header_hash, first_header);
+
+        Ok(())
+    }
+
     // @todo bring all this back when we want to administer cap claims better
     // #[tokio::test(threaded_scheduler)]
     // async fn test_get_cap_claim() -> SourceChainResult<()> {
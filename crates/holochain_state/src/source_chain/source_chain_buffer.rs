@@ -148,7 +148,15 @@ impl SourceChainBuf {
     // TODO: TK-01747: Make this check more robust maybe?
     // PERF: This call must be fast
     pub fn has_initialized(&self) -> bool {
-        self.len() > 3
+        match self.len() {
+            0..=3 => false,
+            // A migrated chain has an OpenChain header before init has run
+            4 => !matches!(
+                self.get_at_index(3),
+                Ok(Some(element)) if matches!(element.header(), Header::OpenChain(_))
+            ),
+            _ => true,
+        }
     }
 
     /// Get the AgentPubKey from the entry committed to the chain.
@@ -37,6 +37,9 @@ pub struct ValidationLimboValue {
     pub num_tries: u32,
     /// The agent that sent you this op
     pub from_agent: Option<AgentPubKey>,
+    /// The author asked for a validation receipt
+    /// once this op has been validated
+    pub send_receipt: bool,
 }
 
 /// The status of a [DhtOp] in limbo
@@ -1,10 +1,7 @@
 //! Module for items related to aggregating validation_receipts
 
 use fallible_iterator::FallibleIterator;
-use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
-use holochain_keystore::AgentPubKeyExt;
-use holochain_keystore::KeystoreSender;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvvBufUsed;
 use holochain_lmdb::db::GetDb;
@@ -12,77 +9,11 @@ use holochain_lmdb::error::DatabaseError;
 use holochain_lmdb::error::DatabaseResult;
 use holochain_lmdb::prelude::Readable;
 use holochain_lmdb::prelude::Writer;
-use holochain_serialized_bytes::prelude::*;
-use holochain_zome_types::signature::Signature;
-
-/// The result of a DhtOp Validation.
-#[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
-)]
-#[serde(tag = "type")]
-pub enum ValidationResult {
-    /// Successful validation.
-    Valid,
-    // TODO - fill out with additional options, which may (or may not) have content
-    // Abandoned { .. },
-    // Warrant { .. },
-}
-
-/// Validation receipt content - to be signed.
-#[derive(
-    Debug,
-    Clone,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    serde::Serialize,
-    serde::Deserialize,
-    SerializedBytes,
-)]
-pub struct ValidationReceipt {
-    /// the op this validation receipt is for.
-    pub dht_op_hash: DhtOpHash,
-
-    /// the result of this validation.
-    pub validation_result: ValidationResult,
-
-    /// the remote validator which is signing this receipt.
-    pub validator: AgentPubKey,
-}
-
-impl ValidationReceipt {
-    /// Sign this validation receipt.
-    pub async fn sign(self, keystore: &KeystoreSender) -> DatabaseResult<SignedValidationReceipt> {
-        let signature = self.validator.sign(keystore, self.clone()).await?;
-        Ok(SignedValidationReceipt {
-            receipt: self,
-            validator_signature: signature,
-        })
-    }
-}
+use std::collections::HashSet;
 
-/// A full, signed validation receipt.
-#[derive(
-    Debug,
-    Clone,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    serde::Serialize,
-    serde::Deserialize,
-    SerializedBytes,
-)]
-pub struct SignedValidationReceipt {
-    /// the content of the validation receipt.
-    pub receipt: ValidationReceipt,
-
-    /// the signature of the remote validator.
-    pub validator_signature: Signature,
-}
+pub use holochain_types::validation_receipt::SignedValidationReceipt;
+pub use holochain_types::validation_receipt::ValidationReceipt;
+pub use holochain_types::validation_receipt::ValidationResult;
 
 /// The database/buffer for aggregating validation_receipts sent by remote
 /// nodes in charge of storage thereof.
@@ -111,21 +42,42 @@ impl ValidationReceiptsBuf {
         Ok(fallible_iterator::convert(self.0.get(r, dht_op_hash)?))
     }
 
-    /// Get the current valid receipt count for a given hash.
+    /// Get the number of distinct validators that
+    /// found the op with this hash valid.
     pub fn count_valid<'r, R: Readable>(
         &'r self,
         r: &'r R,
         dht_op_hash: &DhtOpHash,
     ) -> DatabaseResult<usize> {
-        let mut count = 0;
+        let mut validators = HashSet::new();
 
         let mut iter = self.list_receipts(r, dht_op_hash)?;
         while let Some(v) = iter.next()? {
             if v.receipt.validation_result == ValidationResult::Valid {
-                count += 1;
+                validators.insert(v.receipt.validator);
+            }
+        }
+        Ok(validators.len())
+    }
+
+    /// Get the number of distinct validators that
+    /// rejected the op with this hash.
+    /// Only rejections are counted because they are the
+    /// only definitive negative result.
+    pub fn count_rejected<'r, R: Readable>(
+        &'r self,
+        r: &'r R,
+        dht_op_hash: &DhtOpHash,
+    ) -> DatabaseResult<usize> {
+        let mut validators = HashSet::new();
+
+        let mut iter = self.list_receipts(r, dht_op_hash)?;
+        while let Some(v) = iter.next()? {
+            if v.receipt.validation_result.is_rejected() {
+                validators.insert(v.receipt.validator);
             }
         }
-        Ok(count)
+        Ok(validators.len())
     }
 
     /// Add this receipt if it isn't already in the database.
@@ -152,13 +104,16 @@ impl BufferedStore for ValidationReceiptsBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use holochain_keystore::KeystoreSender;
     use holochain_keystore::KeystoreSenderExt;
     use holochain_lmdb::env::ReadManager;
     use holochain_lmdb::prelude::*;
     use holochain_types::test_utils::fake_dht_op_hash;
+    use holochain_zome_types::signature::Signature;
 
     async fn fake_vr(
         dht_op_hash: &DhtOpHash,
+        validation_result: ValidationResult,
         keystore: &KeystoreSender,
     ) -> SignedValidationReceipt {
         let agent = keystore
@@ -168,7 +123,7 @@ mod tests {
             .unwrap();
         let receipt = ValidationReceipt {
             dht_op_hash: dht_op_hash.clone(),
-            validation_result: ValidationResult::Valid,
+            validation_result,
             validator: agent,
         };
         receipt.sign(keystore).await.unwrap()
@@ -183,8 +138,8 @@ mod tests {
         let keystore = holochain_lmdb::test_utils::test_keystore();
 
         let test_op_hash = fake_dht_op_hash(1);
-        let vr1 = fake_vr(&test_op_hash, &keystore).await;
-        let vr2 = fake_vr(&test_op_hash, &keystore).await;
+        let vr1 = fake_vr(&test_op_hash, ValidationResult::Valid, &keystore).await;
+        let vr2 = fake_vr(&test_op_hash, ValidationResult::Valid, &keystore).await;
 
         let env_ref = env.guard();
         {
@@ -230,4 +185,72 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_validation_receipts_db_count_by_result() -> DatabaseResult<()> {
+        observability::test_run().ok();
+
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let keystore = holochain_lmdb::test_utils::test_keystore();
+
+        let test_op_hash = fake_dht_op_hash(1);
+        let valid = fake_vr(&test_op_hash, ValidationResult::Valid, &keystore).await;
+        let rejected1 = fake_vr(&test_op_hash, ValidationResult::Rejected, &keystore).await;
+        let rejected2 = fake_vr(&test_op_hash, ValidationResult::Rejected, &keystore).await;
+        let abandoned = fake_vr(&test_op_hash, ValidationResult::Abandoned, &keystore).await;
+
+        let env_ref = env.guard();
+        {
+            let mut vr_buf = ValidationReceiptsBuf::new(&env)?;
+            for vr in vec![valid, rejected1, rejected2, abandoned] {
+                assert!(vr.verify().await.unwrap());
+                vr_buf.add_if_unique(vr)?;
+            }
+            env_ref.with_commit(|writer| vr_buf.flush_to_txn(writer))?;
+        }
+
+        let reader = env_ref.reader()?;
+        let vr_buf = ValidationReceiptsBuf::new(&env)?;
+
+        assert_eq!(1, vr_buf.count_valid(&reader, &test_op_hash)?);
+        // Abandoned is not a definitive negative
+        assert_eq!(2, vr_buf.count_rejected(&reader, &test_op_hash)?);
+        assert_eq!(4, vr_buf.list_receipts(&reader, &test_op_hash)?.count()?);
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_validation_receipts_db_count_distinct_validators() -> DatabaseResult<()> {
+        observability::test_run().ok();
+
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let keystore = holochain_lmdb::test_utils::test_keystore();
+
+        let test_op_hash = fake_dht_op_hash(1);
+        let vr1 = fake_vr(&test_op_hash, ValidationResult::Valid, &keystore).await;
+        // The same validator sending a differently signed receipt
+        let vr2 = SignedValidationReceipt {
+            receipt: vr1.receipt.clone(),
+            validator_signature: Signature(vec![0; 64]),
+        };
+
+        let env_ref = env.guard();
+        {
+            let mut vr_buf = ValidationReceiptsBuf::new(&env)?;
+            vr_buf.add_if_unique(vr1)?;
+            vr_buf.add_if_unique(vr2)?;
+            env_ref.with_commit(|writer| vr_buf.flush_to_txn(writer))?;
+        }
+
+        let reader = env_ref.reader()?;
+        let vr_buf = ValidationReceiptsBuf::new(&env)?;
+
+        assert_eq!(2, vr_buf.list_receipts(&reader, &test_op_hash)?.count()?);
+        assert_eq!(1, vr_buf.count_valid(&reader, &test_op_hash)?);
+
+        Ok(())
+    }
 }
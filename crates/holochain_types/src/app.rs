@@ -4,6 +4,7 @@ use derive_more::Into;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_zome_types::cell::CellId;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Placeholder used to identify installed apps
@@ -12,6 +13,10 @@ pub type InstalledAppId = String;
 /// A friendly (nick)name used by UIs to refer to the Cells which make up the app
 pub type CellNick = String;
 
+/// The Cells each Cell of an app may call, by [CellNick].
+/// A Cell without an entry may call any Cell on the conductor.
+pub type AppBridges = HashMap<CellNick, Vec<CellNick>>;
+
 /// The source of the DNA to be installed, either as binary data, or from a path
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -59,6 +64,10 @@ pub struct InstallAppDnaPayload {
     pub properties: Option<JsonProperties>,
     /// App-specific proof-of-membrane-membership, if required by this app
     pub membrane_proof: Option<MembraneProof>,
+    /// The nicks of the other Cells in this app that this Cell may call.
+    /// If `None` this Cell may call any Cell on the conductor.
+    #[serde(default)]
+    pub bridges: Option<Vec<CellNick>>,
 }
 
 impl InstallAppDnaPayload {
@@ -70,6 +79,7 @@ impl InstallAppDnaPayload {
             nick,
             properties: None,
             membrane_proof: None,
+            bridges: None,
         }
     }
     /// Create a payload with no JsonProperties or MembraneProof. Good for tests.
@@ -80,6 +90,7 @@ impl InstallAppDnaPayload {
             nick,
             properties: None,
             membrane_proof: None,
+            bridges: None,
         }
     }
 }
@@ -130,4 +141,7 @@ pub struct InstalledApp {
     pub installed_app_id: InstalledAppId,
     /// Cell data for this app
     pub cell_data: Vec<InstalledCell>,
+    /// The Cells each Cell of this app may call
+    #[serde(default)]
+    pub bridges: AppBridges,
 }
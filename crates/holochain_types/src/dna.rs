@@ -47,6 +47,19 @@ pub struct DnaDef {
 
     /// An array of zomes associated with your holochain application.
     pub zomes: Zomes,
+
+    /// How many validation receipts an op needs before its author
+    /// stops publishing it, for ops without an entry def of their own.
+    /// The default is not serialized so the [DnaHash] of existing DNAs doesn't change.
+    #[serde(default, skip_serializing_if = "RequiredValidations::is_default")]
+    #[builder(default)]
+    pub default_required_validations: RequiredValidations,
+
+    /// Application properties for individual entry types,
+    /// keyed by zome name and then by entry def id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[builder(default)]
+    pub entry_type_properties: BTreeMap<ZomeName, BTreeMap<String, SerializedBytes>>,
 }
 
 #[cfg(feature = "test_utils")]
@@ -88,6 +101,18 @@ impl DnaDef {
                 }
             })
     }
+
+    /// Return the properties declared for an entry type of a zome, if any
+    pub fn get_entry_type_properties(
+        &self,
+        zome_name: &ZomeName,
+        entry_def_id: &EntryDefId,
+    ) -> Option<&SerializedBytes> {
+        match entry_def_id {
+            EntryDefId::App(id) => self.entry_type_properties.get(zome_name)?.get(id),
+            EntryDefId::CapClaim | EntryDefId::CapGrant => None,
+        }
+    }
 }
 
 fn random_uuid() -> String {
@@ -9,6 +9,7 @@
 use derive_more::Constructor;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::zome::ZomeName;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use self::inline_zome::InlineZome;
@@ -136,10 +137,22 @@ impl ZomeDef {
     /// If not, return an error with the provided zome name
     pub fn wasm_hash(&self, zome_name: &ZomeName) -> DnaResult<holo_hash::WasmHash> {
         match self {
-            ZomeDef::Wasm(WasmZome { wasm_hash }) => Ok(wasm_hash.clone()),
+            ZomeDef::Wasm(WasmZome { wasm_hash, .. }) => Ok(wasm_hash.clone()),
             _ => Err(DnaError::NonWasmZome(zome_name.clone())),
         }
     }
+
+    /// The most access to host functions this zome can ever have.
+    /// Zomes that don't declare their host function groups can access them all.
+    pub fn host_fn_access(&self) -> HostFnAccess {
+        match self {
+            ZomeDef::Wasm(WasmZome {
+                host_fn_groups: Some(groups),
+                ..
+            }) => groups.into(),
+            _ => HostFnAccess::all(),
+        }
+    }
 }
 
 /// A zome defined by Wasm bytecode
@@ -149,6 +162,12 @@ impl ZomeDef {
 pub struct WasmZome {
     /// The WasmHash representing the WASM byte code for this zome.
     pub wasm_hash: holo_hash::WasmHash,
+    /// The groups of host functions this zome needs.
+    /// The zome can't call host functions outside these groups
+    /// even when the call context would allow it.
+    /// If this is not set the zome is not restricted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_fn_groups: Option<BTreeSet<HostFnGroup>>,
 }
 
 /// Access a call has to host functions
@@ -179,10 +198,45 @@ pub enum Permission {
     Deny,
 }
 
+impl Permission {
+    /// Only allow if both permissions allow
+    pub fn and(self, other: Permission) -> Permission {
+        match (self, other) {
+            (Permission::Allow, Permission::Allow) => Permission::Allow,
+            _ => Permission::Deny,
+        }
+    }
+}
+
+/// A group of host functions a zome can declare that it needs.
+/// There is a group for each field of [HostFnAccess].
+#[derive(Serialize, Deserialize, Hash, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum HostFnGroup {
+    /// Agent information
+    AgentInfo,
+    /// Reading the workspace
+    ReadWorkspace,
+    /// Writing to the workspace
+    WriteWorkspace,
+    /// Writing to the network
+    WriteNetwork,
+    /// DNA and zome specific data
+    DnaBindings,
+    /// Non-deterministic functions such as random bytes and the system time
+    NonDeterminism,
+    /// Functions that use the keystore in the conductor
+    Keystore,
+}
+
 impl ZomeDef {
     /// create a Zome from a holo_hash WasmHash instead of a holo_hash one
     pub fn from_hash(wasm_hash: holo_hash::WasmHash) -> Self {
-        WasmZome { wasm_hash }.into()
+        WasmZome {
+            wasm_hash,
+            host_fn_groups: None,
+        }
+        .into()
     }
 }
 
@@ -200,6 +254,19 @@ impl HostFnAccess {
         }
     }
 
+    /// Only the access allowed by both `self` and `other`
+    pub fn intersection(self, other: HostFnAccess) -> Self {
+        HostFnAccess {
+            read_workspace: self.read_workspace.and(other.read_workspace),
+            write_workspace: self.write_workspace.and(other.write_workspace),
+            agent_info: self.agent_info.and(other.agent_info),
+            non_determinism: self.non_determinism.and(other.non_determinism),
+            write_network: self.write_network.and(other.write_network),
+            keystore: self.keystore.and(other.keystore),
+            dna_bindings: self.dna_bindings.and(other.dna_bindings),
+        }
+    }
+
     /// Deny all access
     pub fn none() -> Self {
         HostFnAccess {
@@ -213,3 +280,73 @@ impl HostFnAccess {
         }
     }
 }
+
+impl From<&BTreeSet<HostFnGroup>> for HostFnAccess {
+    fn from(groups: &BTreeSet<HostFnGroup>) -> Self {
+        let permission = |group| {
+            if groups.contains(&group) {
+                Permission::Allow
+            } else {
+                Permission::Deny
+            }
+        };
+        HostFnAccess {
+            read_workspace: permission(HostFnGroup::ReadWorkspace),
+            write_workspace: permission(HostFnGroup::WriteWorkspace),
+            agent_info: permission(HostFnGroup::AgentInfo),
+            non_determinism: permission(HostFnGroup::NonDeterminism),
+            write_network: permission(HostFnGroup::WriteNetwork),
+            keystore: permission(HostFnGroup::Keystore),
+            dna_bindings: permission(HostFnGroup::DnaBindings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wasm_zome(host_fn_groups: Option<BTreeSet<HostFnGroup>>) -> ZomeDef {
+        WasmZome {
+            wasm_hash: holo_hash::WasmHash::from_raw_36(vec![0; 36]),
+            host_fn_groups,
+        }
+        .into()
+    }
+
+    #[test]
+    fn declared_host_fn_groups_limit_access() {
+        // Undeclared zomes aren't restricted
+        assert_eq!(wasm_zome(None).host_fn_access(), HostFnAccess::all());
+
+        let groups = vec![HostFnGroup::ReadWorkspace, HostFnGroup::DnaBindings];
+        let zome = wasm_zome(Some(groups.into_iter().collect()));
+        let mut expected = HostFnAccess::none();
+        expected.read_workspace = Permission::Allow;
+        expected.dna_bindings = Permission::Allow;
+        assert_eq!(zome.host_fn_access(), expected);
+
+        // The call context can take away access but not add it
+        let mut context = HostFnAccess::none();
+        context.read_workspace = Permission::Allow;
+        context.keystore = Permission::Allow;
+        let mut expected = HostFnAccess::none();
+        expected.read_workspace = Permission::Allow;
+        assert_eq!(context.intersection(zome.host_fn_access()), expected);
+    }
+
+    #[derive(Serialize)]
+    struct UndeclaredWasmZome {
+        wasm_hash: holo_hash::WasmHash,
+    }
+
+    #[test]
+    fn undeclared_host_fn_groups_are_not_serialized() {
+        // So the hashes of existing DNAs don't change
+        let wasm_hash = holo_hash::WasmHash::from_raw_36(vec![0; 36]);
+        assert_eq!(
+            holochain_serialized_bytes::encode(&wasm_zome(None)).unwrap(),
+            holochain_serialized_bytes::encode(&UndeclaredWasmZome { wasm_hash }).unwrap(),
+        );
+    }
+}
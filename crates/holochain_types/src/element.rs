@@ -123,7 +123,9 @@ impl<'a> ElementGroup<'a> {
                     wire.header
                         .into_header(entry_type.clone(), entry_hash.clone()),
                 )),
-                ValidationStatus::Abandoned => todo!(),
+                // Abandoned headers have no entry so they
+                // can't be part of an element group
+                ValidationStatus::Abandoned => {}
             }
         }
 
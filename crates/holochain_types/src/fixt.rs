@@ -156,6 +156,13 @@ fixturator!(
         zomes: ZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
+        default_required_validations: RequiredValidationsFixturator::new_indexed(
+            Empty,
+            get_fixt_index!()
+        )
+        .next()
+        .unwrap(),
+        entry_type_properties: Default::default(),
     };
 
     curve Unpredictable DnaDef {
@@ -171,6 +178,13 @@ fixturator!(
         zomes: ZomesFixturator::new_indexed(Unpredictable, get_fixt_index!())
             .next()
             .unwrap(),
+        default_required_validations: RequiredValidationsFixturator::new_indexed(
+            Unpredictable,
+            get_fixt_index!()
+        )
+        .next()
+        .unwrap(),
+        entry_type_properties: Default::default(),
     };
 
     curve Predictable DnaDef {
@@ -186,5 +200,12 @@ fixturator!(
         zomes: ZomesFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        default_required_validations: RequiredValidationsFixturator::new_indexed(
+            Predictable,
+            get_fixt_index!()
+        )
+        .next()
+        .unwrap(),
+        entry_type_properties: Default::default(),
     };
 );
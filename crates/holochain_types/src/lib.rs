@@ -26,6 +26,8 @@ pub mod prelude;
 pub mod signal;
 pub mod timestamp;
 pub mod validate;
+pub mod validation_receipt;
+pub mod warrant;
 
 // #[cfg(test)]
 pub mod test_utils;
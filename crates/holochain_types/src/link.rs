@@ -3,9 +3,12 @@
 use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
 use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Links interrelate entries in a source chain.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, SerializedBytes)]
@@ -40,6 +43,66 @@ pub struct GetLinksResponse {
     pub link_removes: Vec<(DeleteLink, Signature)>,
 }
 
+impl GetLinksResponse {
+    /// Merge the responses from several authorities.
+    /// This is the union of all the link adds and all the link removes
+    /// so an authority that is missing data can't hide a link or a removal.
+    ///
+    /// Headers that aren't signed by their author are dropped
+    /// so an authority can't make up links or removals.
+    /// A removal is only kept if it removes one of the merged link adds.
+    pub async fn merge<I: IntoIterator<Item = GetLinksResponse>>(responses: I) -> Self {
+        let mut link_adds = Vec::new();
+        let mut link_add_hashes = HashMap::new();
+        let mut link_removes = Vec::new();
+        let mut seen_removes = HashSet::new();
+        for response in responses {
+            for (link_add, signature) in response.link_adds {
+                let header = Header::CreateLink(link_add.clone());
+                let hash = HeaderHash::with_data_sync(&header);
+                if link_add_hashes.contains_key(&hash) || !is_signed(&header, &signature).await {
+                    continue;
+                }
+                link_add_hashes.insert(hash, link_add.base_address.clone());
+                link_adds.push((link_add, signature));
+            }
+            for (link_remove, signature) in response.link_removes {
+                let header = Header::DeleteLink(link_remove.clone());
+                let hash = HeaderHash::with_data_sync(&header);
+                if seen_removes.contains(&hash) || !is_signed(&header, &signature).await {
+                    continue;
+                }
+                seen_removes.insert(hash);
+                link_removes.push((link_remove, signature));
+            }
+        }
+        // Removals can be in a different response to their link adds
+        // so they are checked once all the link adds are known.
+        link_removes.retain(|(link_remove, _)| {
+            link_add_hashes.get(&link_remove.link_add_address) == Some(&link_remove.base_address)
+        });
+        GetLinksResponse {
+            link_adds,
+            link_removes,
+        }
+    }
+}
+
+/// Is this header signed by its author
+async fn is_signed(header: &Header, signature: &Signature) -> bool {
+    match header.author().verify_signature(signature, header).await {
+        Ok(true) => true,
+        Ok(false) => {
+            tracing::warn!(?header, "Dropping link header with a bad signature");
+            false
+        }
+        Err(e) => {
+            tracing::warn!(?header, ?e, "Failed to verify link header signature");
+            false
+        }
+    }
+}
+
 impl WireLinkMetaKey {
     /// Get the basis of this key
     pub fn basis(&self) -> AnyDhtHash {
@@ -104,3 +167,75 @@ impl<S: Into<String>> LinkMatch<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_keystore::test_keystore::spawn_test_keystore;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn merge_get_links_responses_is_union_of_signed_headers() {
+        let keystore = spawn_test_keystore().await.unwrap();
+        let author = AgentPubKey::new_from_pure_entropy(&keystore).await.unwrap();
+        let base_address = fixt!(EntryHash);
+
+        let mut adds = Vec::new();
+        for mut link_add in CreateLinkFixturator::new(Unpredictable).take(3) {
+            link_add.author = author.clone();
+            link_add.base_address = base_address.clone();
+            let signature = author
+                .sign(&keystore, &Header::CreateLink(link_add.clone()))
+                .await
+                .unwrap();
+            adds.push((link_add, signature));
+        }
+        let mut removes = Vec::new();
+        for (mut link_remove, (link_add, _)) in DeleteLinkFixturator::new(Unpredictable)
+            .zip(adds.iter())
+            .take(2)
+        {
+            link_remove.author = author.clone();
+            link_remove.base_address = base_address.clone();
+            link_remove.link_add_address =
+                HeaderHash::with_data_sync(&Header::CreateLink(link_add.clone()));
+            let signature = author
+                .sign(&keystore, &Header::DeleteLink(link_remove.clone()))
+                .await
+                .unwrap();
+            removes.push((link_remove, signature));
+        }
+
+        // A link add that its author never signed
+        let mut forged_add = adds[0].0.clone();
+        forged_add.tag = LinkTag::new("forged");
+        // A removal that points at a link add on another base
+        let mut wrong_base = removes[0].0.clone();
+        wrong_base.base_address = fixt!(EntryHash);
+        let wrong_base_signature = author
+            .sign(&keystore, &Header::DeleteLink(wrong_base.clone()))
+            .await
+            .unwrap();
+
+        // One authority is missing a link add and the other a link remove
+        let a = GetLinksResponse {
+            link_adds: adds[..2].to_vec(),
+            link_removes: removes[..1].to_vec(),
+        };
+        let b = GetLinksResponse {
+            link_adds: adds[1..]
+                .iter()
+                .cloned()
+                .chain(std::iter::once((forged_add, adds[0].1.clone())))
+                .collect(),
+            link_removes: removes
+                .iter()
+                .cloned()
+                .chain(std::iter::once((wrong_base, wrong_base_signature)))
+                .collect(),
+        };
+        let merged = GetLinksResponse::merge(vec![a, b]).await;
+        assert_eq!(merged.link_adds, adds);
+        assert_eq!(merged.link_removes, removes);
+    }
+}
@@ -32,6 +32,8 @@ pub use crate::signal::*;
 pub use crate::timestamp; // for timestmap::now()
 pub use crate::timestamp::*;
 pub use crate::validate::*;
+pub use crate::validation_receipt::*;
+pub use crate::warrant::*;
 
 pub use crate::fixt::TimestampFixturator;
 #[cfg(feature = "fixturators")]
@@ -11,8 +11,8 @@ use holochain_zome_types::prelude::*;
 /// an Interface
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum Signal {
-    /// Signal from a Cell, generated by `emit_signal`
-    App(CellId, AppSignal),
+    /// Signal from a zome in a Cell, generated by `emit_signal`
+    App(CellId, ZomeName, AppSignal),
     /// System-defined signals
     System(SystemSignal),
 }
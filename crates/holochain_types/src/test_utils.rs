@@ -37,13 +37,22 @@ pub fn fake_dna_zomes(uuid: &str, zomes: Vec<(ZomeName, DnaWasm)>) -> DnaFile {
             .unwrap(),
         uuid: uuid.to_string(),
         zomes: Vec::new(),
+        default_required_validations: Default::default(),
+        entry_type_properties: Default::default(),
     };
     tokio_safe_block_on::tokio_safe_block_forever_on(async move {
         let mut wasm_code = Vec::new();
         for (zome_name, wasm) in zomes {
             let wasm = crate::dna::wasm::DnaWasmHashed::from_content(wasm).await;
             let (wasm, wasm_hash) = wasm.into_inner();
-            dna.zomes.push((zome_name, WasmZome { wasm_hash }.into()));
+            dna.zomes.push((
+                zome_name,
+                WasmZome {
+                    wasm_hash,
+                    host_fn_groups: None,
+                }
+                .into(),
+            ));
             wasm_code.push(wasm);
         }
         DnaFile::new(dna, wasm_code).await
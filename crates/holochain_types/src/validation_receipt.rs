@@ -0,0 +1,114 @@
+//! Validation receipts, which validators send back to the author of a
+//! [DhtOp](crate::dht_op::DhtOp) once they have validated it.
+
+use holo_hash::AgentPubKey;
+use holo_hash::DhtOpHash;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreError;
+use holochain_keystore::KeystoreSender;
+use holochain_serialized_bytes::prelude::*;
+use holochain_zome_types::signature::Signature;
+use holochain_zome_types::validate::ValidationStatus;
+
+/// The result of a DhtOp Validation.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(tag = "type")]
+pub enum ValidationResult {
+    /// Successful validation.
+    Valid,
+    /// The op definitively failed validation.
+    Rejected,
+    /// The validator gave up on validating the op,
+    /// commonly because its dependencies could not be found.
+    Abandoned,
+}
+
+impl ValidationResult {
+    /// Is this a definitive result that the op is invalid.
+    /// Abandoned is not definitive because the op could
+    /// still be valid once its dependencies are found.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, ValidationResult::Rejected)
+    }
+}
+
+impl From<ValidationStatus> for ValidationResult {
+    fn from(status: ValidationStatus) -> Self {
+        match status {
+            ValidationStatus::Valid => ValidationResult::Valid,
+            ValidationStatus::Rejected => ValidationResult::Rejected,
+            ValidationStatus::Abandoned => ValidationResult::Abandoned,
+        }
+    }
+}
+
+/// Validation receipt content - to be signed.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+)]
+pub struct ValidationReceipt {
+    /// the op this validation receipt is for.
+    pub dht_op_hash: DhtOpHash,
+
+    /// the result of this validation.
+    pub validation_result: ValidationResult,
+
+    /// the remote validator which is signing this receipt.
+    pub validator: AgentPubKey,
+}
+
+impl ValidationReceipt {
+    /// Sign this validation receipt.
+    pub async fn sign(
+        self,
+        keystore: &KeystoreSender,
+    ) -> Result<SignedValidationReceipt, KeystoreError> {
+        let signature = self.validator.sign(keystore, self.clone()).await?;
+        Ok(SignedValidationReceipt {
+            receipt: self,
+            validator_signature: signature,
+        })
+    }
+}
+
+/// A full, signed validation receipt.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+)]
+pub struct SignedValidationReceipt {
+    /// the content of the validation receipt.
+    pub receipt: ValidationReceipt,
+
+    /// the signature of the remote validator.
+    pub validator_signature: Signature,
+}
+
+impl SignedValidationReceipt {
+    /// Check the receipt was signed by the validator.
+    pub async fn verify(&self) -> Result<bool, KeystoreError> {
+        self.receipt
+            .validator
+            .verify_signature(&self.validator_signature, self.receipt.clone())
+            .await
+    }
+}
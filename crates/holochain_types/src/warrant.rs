@@ -0,0 +1,39 @@
+//! Checking the evidence carried by [Warrant]s received from other agents.
+
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreError;
+use holochain_zome_types::prelude::*;
+
+/// Helpers for checking a [Warrant] before trusting it.
+#[async_trait::async_trait]
+pub trait WarrantExt {
+    /// Check the evidence in this warrant was really authored
+    /// by the warranted agent and, for a fork, that both headers
+    /// are different headers at the same position on the chain.
+    ///
+    /// This does not re-run validation on an
+    /// [Warrant::InvalidHeader], it only proves the agent
+    /// authored the header.
+    async fn verify(&self) -> Result<bool, KeystoreError>;
+}
+
+#[async_trait::async_trait]
+impl WarrantExt for Warrant {
+    async fn verify(&self) -> Result<bool, KeystoreError> {
+        if let Warrant::ChainFork(a, b) = self {
+            if a.header() == b.header()
+                || a.header().author() != b.header().author()
+                || a.header().header_seq() != b.header().header_seq()
+            {
+                return Ok(false);
+            }
+        }
+        for signed_header in self.evidence() {
+            let SignedHeader(header, signature) = signed_header;
+            if !header.author().verify_signature(signature, header).await? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
@@ -105,6 +105,10 @@ use util::*;
 mod websocket_config;
 pub use websocket_config::*;
 
+// tls for "wss" listeners and connections
+mod tls;
+pub use tls::*;
+
 // handles dispatching messages between the sender/receiver/sink/stream
 pub(crate) mod task_dispatch_incoming;
 
@@ -172,4 +176,162 @@ mod tests {
 
         assert_eq!("echo: test", &rsp.0,);
     }
+
+    #[tokio::test]
+    async fn tls_sanity_test() {
+        observability::test_run().ok();
+        let identity = TlsIdentity::self_signed(vec!["localhost".to_string()]).unwrap();
+
+        // A wss listener can't be bound without a certificate
+        assert!(websocket_bind(
+            url2!("wss://127.0.0.1:0"),
+            Arc::new(WebsocketConfig::default().scheme("wss")),
+        )
+        .await
+        .is_err());
+
+        let mut server = websocket_bind(
+            url2!("wss://127.0.0.1:0"),
+            Arc::new(
+                WebsocketConfig::default()
+                    .scheme("wss")
+                    .tls_identity(identity.clone()),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let port = server.local_addr().port().unwrap();
+
+        tokio::task::spawn(async move {
+            while let Some(maybe_con) = server.next().await {
+                let (_send, mut recv) = match maybe_con {
+                    Ok(con) => con,
+                    // The untrusting client fails the handshake
+                    Err(_) => continue,
+                };
+
+                tokio::task::spawn(async move {
+                    if let Some(WebsocketMessage::Request(data, respond)) = recv.next().await {
+                        let msg: TestMessage = data.try_into().unwrap();
+                        let msg = TestMessage(format!("echo: {}", msg.0));
+                        respond(msg.try_into().unwrap()).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        // The self-signed certificate isn't trusted by default
+        assert!(websocket_connect(
+            url2!("wss://localhost:{}", port),
+            Arc::new(WebsocketConfig::default().scheme("wss")),
+        )
+        .await
+        .is_err());
+
+        let (mut send, _recv) = websocket_connect(
+            url2!("wss://localhost:{}", port),
+            Arc::new(
+                WebsocketConfig::default()
+                    .scheme("wss")
+                    .tls_trusted_cert(identity.cert_pem().to_vec()),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let msg = TestMessage("test".to_string());
+        let rsp: TestMessage = send.request(msg).await.unwrap();
+
+        assert_eq!("echo: test", &rsp.0,);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tls_key_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir::TempDir::new("holochain_websocket").unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        // A key file from before that anyone can read
+        std::fs::write(&key_path, b"old key").unwrap();
+        std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let identity = TlsIdentity::self_signed(vec!["localhost".to_string()]).unwrap();
+        identity.write_pem_files(&cert_path, &key_path).unwrap();
+        let mode = std::fs::metadata(&key_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        TlsIdentity::from_pem_files(&cert_path, &key_path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_socket_sanity_test() {
+        use std::os::unix::fs::PermissionsExt;
+
+        observability::test_run().ok();
+        let dir = tempdir::TempDir::new("holochain_websocket").unwrap();
+        let path = dir.path().join("test.sock");
+        let mut server = websocket_bind_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        tokio::task::spawn(async move {
+            while let Some(maybe_con) = server.next().await {
+                let (_send, mut recv) = maybe_con.unwrap();
+
+                tokio::task::spawn(async move {
+                    if let Some(WebsocketMessage::Request(data, respond)) = recv.next().await {
+                        let msg: TestMessage = data.try_into().unwrap();
+                        let msg = TestMessage(format!("echo: {}", msg.0));
+                        respond(msg.try_into().unwrap()).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let (mut send, _recv) = websocket_connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+
+        let msg = TestMessage("test".to_string());
+        let rsp: TestMessage = send.request(msg).await.unwrap();
+
+        assert_eq!("echo: test", &rsp.0,);
+
+        // A socket that is still being listened on is not replaced
+        assert!(
+            websocket_bind_unix(&path, Arc::new(WebsocketConfig::default()))
+                .await
+                .is_err()
+        );
+        let (mut send, _recv) = websocket_connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+        let rsp: TestMessage = send
+            .request(TestMessage("again".to_string()))
+            .await
+            .unwrap();
+        assert_eq!("echo: again", &rsp.0,);
+
+        // A stale socket is replaced, but other files are left alone
+        let stale_path = dir.path().join("stale.sock");
+        drop(std::os::unix::net::UnixListener::bind(&stale_path).unwrap());
+        websocket_bind_unix(&stale_path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+        let mode = std::fs::metadata(&stale_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let file_path = dir.path().join("not_a_socket");
+        std::fs::write(&file_path, b"data").unwrap();
+        assert!(
+            websocket_bind_unix(&file_path, Arc::new(WebsocketConfig::default()))
+                .await
+                .is_err()
+        );
+    }
 }
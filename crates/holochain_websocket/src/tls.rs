@@ -0,0 +1,178 @@
+//! TLS support for "wss" listeners and connections
+
+use crate::*;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::internal::pemfile;
+use tokio_rustls::webpki;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::TlsConnector;
+
+/// The url scheme of websockets that run over TLS
+pub(crate) const TLS_SCHEME: &str = "wss";
+
+/// A PEM encoded certificate and private key that a "wss" listener
+/// presents to the clients connecting to it.
+#[derive(Clone)]
+pub struct TlsIdentity {
+    cert_pem: Vec<u8>,
+    key_pem: Vec<u8>,
+}
+
+impl std::fmt::Debug for TlsIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the private key
+        f.debug_struct("TlsIdentity")
+            .field("cert_pem", &String::from_utf8_lossy(&self.cert_pem))
+            .finish()
+    }
+}
+
+impl TlsIdentity {
+    /// Use a PEM encoded certificate (chain) and PKCS #8 private key.
+    pub fn from_pem(cert_pem: Vec<u8>, key_pem: Vec<u8>) -> Self {
+        Self { cert_pem, key_pem }
+    }
+
+    /// Read a PEM encoded certificate (chain) and PKCS #8 private key from files.
+    pub fn from_pem_files<P: AsRef<std::path::Path>>(cert_path: P, key_path: P) -> Result<Self> {
+        Ok(Self::from_pem(
+            std::fs::read(cert_path)?,
+            std::fs::read(key_path)?,
+        ))
+    }
+
+    /// Generate a new self-signed certificate for these host names and IP addresses.
+    /// Clients have to add the certificate to their
+    /// [tls_trusted_certs](struct.WebsocketConfig.html#structfield.tls_trusted_certs)
+    /// to connect, because no certificate authority vouches for it.
+    pub fn self_signed(subject_alt_names: Vec<String>) -> Result<Self> {
+        let mut params = rcgen::CertificateParams::default();
+        params.subject_alt_names = subject_alt_names
+            .into_iter()
+            .map(|name| match name.parse::<std::net::IpAddr>() {
+                Ok(ip) => rcgen::SanType::IpAddress(ip),
+                Err(_) => rcgen::SanType::DnsName(name),
+            })
+            .collect();
+        let cert =
+            rcgen::Certificate::from_params(params).map_err(|e| Error::new(ErrorKind::Other, e))?;
+        let cert_pem = cert
+            .serialize_pem()
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        let key_pem = cert.serialize_private_key_pem();
+        Ok(Self::from_pem(cert_pem.into_bytes(), key_pem.into_bytes()))
+    }
+
+    /// Write the certificate and private key to PEM files.
+    /// On unix the key file is only readable and writable by its owner.
+    pub fn write_pem_files<P: AsRef<std::path::Path>>(
+        &self,
+        cert_path: P,
+        key_path: P,
+    ) -> Result<()> {
+        std::fs::write(cert_path, &self.cert_pem)?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(key_path)?;
+        // The mode only applies when the file is created,
+        // so make sure a key file from before is not readable by others.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::io::Write::write_all(&mut file, &self.key_pem)
+    }
+
+    /// The PEM encoded certificate (chain).
+    pub fn cert_pem(&self) -> &[u8] {
+        &self.cert_pem
+    }
+
+    /// internal build the acceptor that runs the server side of the TLS handshake
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor> {
+        let certs = pem_certs(&self.cert_pem)?;
+        let key = pemfile::pkcs8_private_keys(&mut &self.key_pem[..])
+            .ok()
+            .and_then(|mut keys| keys.pop())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "no PKCS #8 private key"))?;
+        let mut config = rustls::ServerConfig::new(rustls::NoClientAuth::new());
+        config
+            .set_single_cert(certs, key)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        Ok(Arc::new(config).into())
+    }
+}
+
+/// internal read the certificates from PEM bytes
+fn pem_certs(pem: &[u8]) -> Result<Vec<rustls::Certificate>> {
+    match pemfile::certs(&mut &pem[..]) {
+        Ok(certs) if !certs.is_empty() => Ok(certs),
+        _ => Err(Error::new(ErrorKind::InvalidInput, "no PEM certificates")),
+    }
+}
+
+/// internal build the connector that runs the client side of the TLS handshake.
+/// Servers are trusted if they present one of the trusted certs from the config,
+/// or a certificate for their host name from a well known certificate authority.
+pub(crate) fn tls_connector(config: &WebsocketConfig) -> Result<TlsConnector> {
+    let mut trusted_certs = Vec::new();
+    for cert_pem in config.tls_trusted_certs.iter() {
+        trusted_certs.extend(pem_certs(cert_pem)?);
+    }
+    let mut client_config = rustls::ClientConfig::new();
+    client_config
+        .root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    client_config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(TrustedCertVerifier {
+            trusted_certs,
+            web_pki: rustls::WebPKIVerifier::new(),
+        }));
+    Ok(Arc::new(client_config).into())
+}
+
+/// internal the name to verify the server certificate against.
+/// Certificates can't be checked against IP addresses, so servers
+/// connected to by IP address are only trusted if their certificate is.
+pub(crate) fn tls_server_name(host: &str) -> Result<webpki::DNSName> {
+    let name = match host.parse::<std::net::IpAddr>() {
+        Ok(_) => "ip.address.invalid",
+        Err(_) => host,
+    };
+    Ok(webpki::DNSNameRef::try_from_ascii_str(name)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "invalid tls host name"))?
+        .to_owned())
+}
+
+/// Trusts the exact certificates it is given,
+/// otherwise falls back to the usual certificate authority checks.
+struct TrustedCertVerifier {
+    trusted_certs: Vec<rustls::Certificate>,
+    web_pki: rustls::WebPKIVerifier,
+}
+
+impl rustls::ServerCertVerifier for TrustedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        roots: &rustls::RootCertStore,
+        presented_certs: &[rustls::Certificate],
+        dns_name: webpki::DNSNameRef,
+        ocsp_response: &[u8],
+    ) -> std::result::Result<rustls::ServerCertVerified, rustls::TLSError> {
+        match presented_certs.first() {
+            Some(cert) if self.trusted_certs.contains(cert) => {
+                Ok(rustls::ServerCertVerified::assertion())
+            }
+            _ => self
+                .web_pki
+                .verify_server_cert(roots, presented_certs, dns_name, ocsp_response),
+        }
+    }
+}
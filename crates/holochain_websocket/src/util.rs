@@ -61,7 +61,13 @@ pub(crate) fn init_tracing() {
 }
 
 /// internal socket type
-pub(crate) type RawSocket = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+pub(crate) type RawSocket<S = tokio::net::TcpStream> = tokio_tungstenite::WebSocketStream<S>;
+
+/// internal helper to convert a unix socket path to a url
+#[cfg(unix)]
+pub(crate) fn unix_path_to_url(path: &std::path::Path) -> Url2 {
+    url2!("unix://{}", path.display())
+}
 
 /// internal helper to convert addrs to urls
 pub(crate) fn addr_to_url(a: SocketAddr, scheme: &str) -> Url2 {
@@ -1,9 +1,12 @@
 //! defines a builder-style config struct for setting up websockets
 
+use crate::TlsIdentity;
+
 /// A builder-style config struct for setting up websockets.
 #[derive(Debug)]
 pub struct WebsocketConfig {
     /// Scheme to use for urls - e.g. "ws" or "wss". [default = "ws"]
+    /// Listeners and connections with the "wss" scheme run over TLS.
     pub scheme: &'static str,
 
     /// Seconds after which the lib will stop tracking individual request ids.
@@ -26,6 +29,16 @@ pub struct WebsocketConfig {
 
     /// Maximum number of pending new incoming connections. [default = 255]
     pub max_pending_connections: usize,
+
+    /// The certificate and key a "wss" listener presents to clients.
+    /// Required to bind a "wss" listener. [default = None]
+    pub tls_identity: Option<TlsIdentity>,
+
+    /// PEM encoded certificates to trust exactly when connecting to a "wss" server,
+    /// on top of the well known certificate authorities,
+    /// e.g. the self-signed certificate of a remote conductor.
+    /// A server connected to by IP address must present one of these. [default = empty]
+    pub tls_trusted_certs: Vec<Vec<u8>>,
 }
 
 impl Default for WebsocketConfig {
@@ -38,6 +51,8 @@ impl Default for WebsocketConfig {
             max_message_size: 64 << 20,
             max_frame_size: 16 << 20,
             max_pending_connections: 255,
+            tls_identity: None,
+            tls_trusted_certs: Vec::new(),
         }
     }
 }
@@ -78,6 +93,18 @@ impl WebsocketConfig {
         self.max_frame_size = max;
         self
     }
+
+    /// Builder-style setter.
+    pub fn tls_identity(mut self, identity: TlsIdentity) -> Self {
+        self.tls_identity = Some(identity);
+        self
+    }
+
+    /// Builder-style setter. Adds a PEM encoded certificate to trust.
+    pub fn tls_trusted_cert(mut self, cert_pem: Vec<u8>) -> Self {
+        self.tls_trusted_certs.push(cert_pem);
+        self
+    }
 }
 
 /// internal helper to convert our configs into tungstenite configs
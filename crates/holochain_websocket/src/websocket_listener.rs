@@ -43,8 +43,23 @@ impl tokio::stream::Stream for WebsocketListener {
 
 /// Bind a new websocket listening socket, and begin awaiting incoming connections.
 /// Returns a [WebsocketListener](struct.WebsocketListener.html) instance.
+///
+/// If the config scheme is "wss" every connection runs over TLS,
+/// using the [tls_identity](struct.WebsocketConfig.html#structfield.tls_identity)
+/// from the config.
 pub async fn websocket_bind(addr: Url2, config: Arc<WebsocketConfig>) -> Result<WebsocketListener> {
     let addr = url_to_addr(&addr, config.scheme).await?;
+    let tls_acceptor = if config.scheme == TLS_SCHEME {
+        let identity = config.tls_identity.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "a 'wss' listener needs a tls_identity in its config",
+            )
+        })?;
+        Some(identity.acceptor()?)
+    } else {
+        None
+    };
     let socket = match &addr {
         SocketAddr::V4(_) => net2::TcpBuilder::new_v4()?,
         SocketAddr::V6(_) => net2::TcpBuilder::new_v6()?,
@@ -59,7 +74,7 @@ pub async fn websocket_bind(addr: Url2, config: Arc<WebsocketConfig>) -> Result<
     let socket = socket
         .map({
             let config = config.clone();
-            move |socket_result| connect(config.clone(), socket_result)
+            move |socket_result| connect(config.clone(), tls_acceptor.clone(), socket_result)
         })
         .buffer_unordered(config.max_pending_connections)
         .boxed();
@@ -78,6 +93,7 @@ pub async fn websocket_bind(addr: Url2, config: Arc<WebsocketConfig>) -> Result<
 /// Connects the new listener
 async fn connect(
     config: Arc<WebsocketConfig>,
+    tls_acceptor: Option<tokio_rustls::TlsAcceptor>,
     socket_result: std::io::Result<tokio::net::TcpStream>,
 ) -> Result<(WebsocketSender, WebsocketReceiver)> {
     match socket_result {
@@ -85,22 +101,136 @@ async fn connect(
             socket.set_keepalive(Some(std::time::Duration::from_secs(
                 config.tcp_keepalive_s as u64,
             )))?;
+            let peer_addr = socket.peer_addr()?;
             tracing::debug!(
                 message = "accepted incoming raw socket",
-                remote_addr = %socket.peer_addr()?,
+                remote_addr = %peer_addr,
             );
-            let socket = tokio_tungstenite::accept_async_with_config(
-                socket,
-                Some(tungstenite::protocol::WebSocketConfig {
-                    max_send_queue: Some(config.max_send_queue),
-                    max_message_size: Some(config.max_message_size),
-                    max_frame_size: Some(config.max_frame_size),
-                }),
-            )
-            .await
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
-            build_websocket_pair(config, socket)
+            let remote_addr = addr_to_url(peer_addr, config.scheme);
+            match tls_acceptor {
+                Some(tls_acceptor) => {
+                    let socket = tls_acceptor.accept(socket).await?;
+                    accept(config, remote_addr, socket).await
+                }
+                None => accept(config, remote_addr, socket).await,
+            }
         }
         Err(e) => Err(Error::new(ErrorKind::Other, e)),
     }
 }
+
+/// Runs the server side of the websocket handshake on an accepted socket
+async fn accept<S>(
+    config: Arc<WebsocketConfig>,
+    remote_addr: Url2,
+    socket: S,
+) -> Result<(WebsocketSender, WebsocketReceiver)>
+where
+    S: 'static + tokio::io::AsyncRead + tokio::io::AsyncWrite + std::marker::Unpin + Send,
+{
+    let socket = tokio_tungstenite::accept_async_with_config(socket, Some(config.to_tungstenite()))
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+    build_websocket_pair(config, remote_addr, socket)
+}
+
+/// Bind a new websocket listening socket on a unix domain socket at this path,
+/// and begin awaiting incoming connections.
+/// Returns a [WebsocketListener](struct.WebsocketListener.html) instance.
+///
+/// The socket file is only readable and writable by its owner, so only processes
+/// running as the same user can connect. A stale socket left at the path by a
+/// previous listener is replaced, but a socket something is still listening on,
+/// or any other existing file, is an error.
+#[cfg(unix)]
+pub async fn websocket_bind_unix<P: AsRef<std::path::Path>>(
+    path: P,
+    config: Arc<WebsocketConfig>,
+) -> Result<WebsocketListener> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = path.as_ref();
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            // Only replace the socket if nothing is listening on it any more
+            match std::os::unix::net::UnixStream::connect(path) {
+                Ok(_) => {
+                    return Err(Error::new(
+                        ErrorKind::AddrInUse,
+                        format!("'{}' is already being listened on", path.display()),
+                    ))
+                }
+                Err(e) if e.kind() == ErrorKind::ConnectionRefused => std::fs::remove_file(path)?,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(_) => {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("'{}' exists and is not a unix socket", path.display()),
+            ))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+
+    // Bind inside a directory only we can access and restrict the socket there,
+    // then move it into place, so it is never reachable with wider permissions.
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "socket path has no file name"))?;
+    let private_dir = path.with_file_name(format!(
+        ".{}.{}",
+        file_name.to_string_lossy(),
+        nanoid::nanoid!()
+    ));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)?;
+    let bound: Result<tokio::net::UnixListener> = (|| {
+        let private_path = private_dir.join(file_name);
+        let socket = tokio::net::UnixListener::bind(&private_path)?;
+        std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&private_path, path)?;
+        Ok(socket)
+    })();
+    std::fs::remove_dir_all(&private_dir)?;
+    let socket = bound?;
+
+    let local_addr = unix_path_to_url(path);
+    let socket = socket
+        .map({
+            let config = config.clone();
+            let local_addr = local_addr.clone();
+            move |socket_result| connect_unix(config.clone(), local_addr.clone(), socket_result)
+        })
+        .buffer_unordered(config.max_pending_connections)
+        .boxed();
+
+    tracing::info!(
+        message = "bind",
+        local_addr = %local_addr,
+    );
+    Ok(WebsocketListener {
+        config,
+        local_addr,
+        socket,
+    })
+}
+
+/// Connects the new unix socket listener
+#[cfg(unix)]
+async fn connect_unix(
+    config: Arc<WebsocketConfig>,
+    local_addr: Url2,
+    socket_result: std::io::Result<tokio::net::UnixStream>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    let socket = socket_result?;
+    tracing::debug!(
+        message = "accepted incoming unix socket",
+        %local_addr,
+    );
+    accept(config, local_addr, socket).await
+}
@@ -101,6 +101,11 @@ impl tokio::stream::Stream for WebsocketReceiver {
 /// [WebsocketSender](struct.WebsocketSender.html),
 /// [WebsocketReceiver](struct.WebsocketReceiver.html)
 /// ).
+///
+/// If the config scheme is "wss" the connection runs over TLS, and the server
+/// certificate must be trusted by the system or be one of the
+/// [tls_trusted_certs](struct.WebsocketConfig.html#structfield.tls_trusted_certs)
+/// from the config.
 pub async fn websocket_connect(
     url: Url2,
     config: Arc<WebsocketConfig>,
@@ -110,27 +115,63 @@ pub async fn websocket_connect(
     socket.set_keepalive(Some(std::time::Duration::from_secs(
         config.tcp_keepalive_s as u64,
     )))?;
-    let (socket, _) = tokio_tungstenite::client_async_with_config(
-        url.as_str(),
-        socket,
-        Some(config.to_tungstenite()),
-    )
-    .await
-    .map_err(|e| Error::new(ErrorKind::Other, e))?;
-    build_websocket_pair(config, socket)
+    let remote_addr = addr_to_url(socket.peer_addr()?, config.scheme);
+    if config.scheme == TLS_SCHEME {
+        // url_to_addr has already checked there is a host
+        let server_name = tls_server_name(url.host_str().unwrap_or_default())?;
+        let socket = tls_connector(&config)?
+            .connect(server_name.as_ref(), socket)
+            .await?;
+        client_handshake(config, url.as_str(), remote_addr, socket).await
+    } else {
+        client_handshake(config, url.as_str(), remote_addr, socket).await
+    }
+}
+
+/// Establish a new outgoing websocket connection over the unix domain socket
+/// at this path. Returns a split websocket connection pair: (
+/// [WebsocketSender](struct.WebsocketSender.html),
+/// [WebsocketReceiver](struct.WebsocketReceiver.html)
+/// ).
+#[cfg(unix)]
+pub async fn websocket_connect_unix<P: AsRef<std::path::Path>>(
+    path: P,
+    config: Arc<WebsocketConfig>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    let path = path.as_ref();
+    let socket = tokio::net::UnixStream::connect(path).await?;
+    // The host is ignored, but the handshake needs a valid websocket url.
+    client_handshake(config, "ws://localhost/", unix_path_to_url(path), socket).await
+}
+
+/// internal run the client side of the websocket handshake on a connected socket
+async fn client_handshake<S>(
+    config: Arc<WebsocketConfig>,
+    url: &str,
+    remote_addr: Url2,
+    socket: S,
+) -> Result<(WebsocketSender, WebsocketReceiver)>
+where
+    S: 'static + tokio::io::AsyncRead + tokio::io::AsyncWrite + std::marker::Unpin + Send,
+{
+    let (socket, _) =
+        tokio_tungstenite::client_async_with_config(url, socket, Some(config.to_tungstenite()))
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+    build_websocket_pair(config, remote_addr, socket)
 }
 
 /// internal set up the tokio tasks that keep a websocket running
 /// and produce the public (WebsocketSender, WebsocketReceiver) pair.
-pub(crate) fn build_websocket_pair(
+pub(crate) fn build_websocket_pair<S>(
     config: Arc<WebsocketConfig>,
-    socket: RawSocket,
-) -> Result<(WebsocketSender, WebsocketReceiver)> {
-    let remote_addr = url2!(
-        "{}#{}",
-        addr_to_url(socket.get_ref().peer_addr()?, config.scheme),
-        nanoid::nanoid!(),
-    );
+    remote_addr: Url2,
+    socket: RawSocket<S>,
+) -> Result<(WebsocketSender, WebsocketReceiver)>
+where
+    S: 'static + tokio::io::AsyncRead + tokio::io::AsyncWrite + std::marker::Unpin + Send,
+{
+    let remote_addr = url2!("{}#{}", remote_addr, nanoid::nanoid!());
 
     // split the sink and stream so we can handle them simultaneously
     use futures::stream::StreamExt;
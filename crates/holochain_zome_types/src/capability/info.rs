@@ -0,0 +1,32 @@
+use super::CapGrant;
+use super::ZomeCallCapGrant;
+use holo_hash::*;
+use holochain_serialized_bytes::prelude::*;
+
+/// A grant that is live on the local source chain,
+/// i.e. it has been committed and not since updated or deleted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct LiveCapGrant {
+    /// The header that committed the grant.
+    /// Pass this to update or delete the grant.
+    pub header_hash: HeaderHash,
+    /// The grant itself.
+    pub grant: ZomeCallCapGrant,
+}
+
+/// The capability that authorized the current zome call.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct CapInfo {
+    /// The grant that matched the function, caller and secret of the call.
+    /// This is `CapGrant::ChainAuthor` when the chain author is calling their own cell.
+    pub grant: CapGrant,
+    /// The agent that made the call.
+    pub provenance: AgentPubKey,
+}
+
+impl CapInfo {
+    /// Constructor
+    pub fn new(grant: CapGrant, provenance: AgentPubKey) -> Self {
+        Self { grant, provenance }
+    }
+}
@@ -40,6 +40,12 @@ pub struct GetOptions {
     /// If it is false you will get whatever is locally
     /// available on this conductor.
     pub strategy: GetStrategy,
+    /// If this is true the get will follow [Update](crate::header::Update)s
+    /// to the newest version of the entry or header.
+    /// Branching updates are resolved by taking the newest
+    /// update, then the highest header hash.
+    #[serde(default)]
+    pub follow_redirects: bool,
 }
 
 impl GetOptions {
@@ -54,6 +60,7 @@ impl GetOptions {
     pub fn latest() -> Self {
         Self {
             strategy: GetStrategy::Latest,
+            follow_redirects: false,
         }
     }
     /// Gets the content but does not
@@ -66,8 +73,16 @@ impl GetOptions {
     pub fn content() -> Self {
         Self {
             strategy: GetStrategy::Content,
+            follow_redirects: false,
         }
     }
+
+    /// Follow any updates to the newest version
+    /// instead of returning the requested version.
+    pub fn follow_redirects(mut self) -> Self {
+        self.follow_redirects = true;
+        self
+    }
 }
 
 impl Default for GetOptions {
@@ -38,7 +38,7 @@ impl Default for EntryVisibility {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct RequiredValidations(u8);
 
 impl From<u8> for RequiredValidations {
@@ -59,6 +59,13 @@ impl Default for RequiredValidations {
     }
 }
 
+impl RequiredValidations {
+    /// Is this the default number of required validations
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 impl EntryVisibility {
     /// converts entry visibility enum into boolean value on public
     pub fn is_public(&self) -> bool {
@@ -0,0 +1,34 @@
+//! Information about the environment a zome function is running in.
+
+use crate::entry_def::RequiredValidations;
+use crate::zome::ZomeName;
+use holo_hash::AgentPubKey;
+use holo_hash::DnaHash;
+use holochain_serialized_bytes::prelude::*;
+
+/// The environment the current zome call is running in,
+/// as reported by the host.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct EnvInfo {
+    /// The hash of the dna the zome belongs to
+    pub dna_hash: DnaHash,
+    /// The name of the zome being called
+    pub zome_name: ZomeName,
+    /// The agent running the call
+    pub agent_pubkey: AgentPubKey,
+    /// The version of the conductor hosting the cell
+    pub conductor_version: String,
+    /// The network the cell is running on
+    pub network: NetworkInfo,
+}
+
+/// The network parameters of the current dna.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct NetworkInfo {
+    /// The uuid of the dna. Cells of dnas with different uuids
+    /// are on separate networks.
+    pub uuid: String,
+    /// How many validation receipts an op needs before its author stops
+    /// publishing it, for ops without an entry def of their own.
+    pub default_required_validations: RequiredValidations,
+}
@@ -24,6 +24,7 @@ pub mod element;
 pub mod entry;
 #[allow(missing_docs)]
 pub mod entry_def;
+pub mod env_info;
 #[allow(missing_docs)]
 pub mod header;
 #[allow(missing_docs)]
@@ -38,6 +39,7 @@ pub mod post_commit;
 pub mod prelude;
 pub mod query;
 pub mod request;
+pub mod schedule;
 pub mod signal;
 pub mod signature;
 pub mod timestamp;
@@ -130,6 +130,145 @@ impl Links {
     }
 }
 
+/// Which page of links a paginated get links query returns.
+/// Links are paged in the order they were created,
+/// with ties broken by the hash of their [CreateLink] header.
+#[derive(Default, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct LinkPageQuery {
+    /// Only links created at or after this time
+    pub since: Option<crate::Timestamp>,
+    /// Only links created before this time
+    pub until: Option<crate::Timestamp>,
+    /// The most links to look at for this page.
+    /// Set to `None` to get every link in one page.
+    /// A limit of zero is rejected, as it would never get past the first page.
+    pub limit: Option<std::num::NonZeroU32>,
+    /// Start just after the link this cursor was taken from.
+    /// This is the `next` cursor of the previous [LinkPage].
+    pub after: Option<LinkCursor>,
+}
+
+impl LinkPageQuery {
+    /// A query for every link in one page
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only links created at or after this time
+    pub fn since(mut self, since: crate::Timestamp) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only links created before this time
+    pub fn until(mut self, until: crate::Timestamp) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Look at no more than this many links
+    pub fn limit(mut self, limit: std::num::NonZeroU32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Continue from the cursor of a previous page
+    pub fn after(mut self, after: LinkCursor) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Is a link created at this time with this header in the range of this query?
+    pub fn contains(&self, timestamp: &crate::Timestamp, create_link_hash: &HeaderHash) -> bool {
+        self.since.as_ref().map_or(true, |since| timestamp >= since)
+            && self.until.as_ref().map_or(true, |until| timestamp < until)
+            && self.after.as_ref().map_or(true, |after| {
+                (timestamp, create_link_hash) > (&after.timestamp, &after.create_link_hash)
+            })
+    }
+
+    /// The earliest creation time a link in this page can have
+    pub fn start(&self) -> Option<crate::Timestamp> {
+        match (self.since, self.after.as_ref().map(|after| after.timestamp)) {
+            (Some(since), Some(after)) => Some(since.max(after)),
+            (since, after) => since.or(after),
+        }
+    }
+
+    /// Take the page this query asks for out of some links in any order.
+    /// `key` gets the creation time and [CreateLink] header hash of a link.
+    /// Also returns the cursor of the next page if the limit was reached.
+    pub fn page<T, F>(&self, links: Vec<T>, key: F) -> (Vec<T>, Option<LinkCursor>)
+    where
+        F: Fn(&T) -> (crate::Timestamp, HeaderHash),
+    {
+        let mut links: Vec<_> = links
+            .into_iter()
+            .map(|link| (key(&link), link))
+            .filter(|((timestamp, hash), _)| self.contains(timestamp, hash))
+            .collect();
+        links.sort_by(|(a, _), (b, _)| a.cmp(b));
+        links.dedup_by(|(a, _), (b, _)| a == b);
+        let next = match self.limit {
+            Some(limit) if links.len() >= limit.get() as usize => {
+                links.truncate(limit.get() as usize);
+                links
+                    .last()
+                    .map(|((timestamp, create_link_hash), _)| LinkCursor {
+                        timestamp: *timestamp,
+                        create_link_hash: create_link_hash.clone(),
+                    })
+            }
+            _ => None,
+        };
+        (links.into_iter().map(|(_, link)| link).collect(), next)
+    }
+}
+
+/// Where a page of links ends.
+/// Opaque to the app, which passes it back to get the next page.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct LinkCursor {
+    timestamp: crate::Timestamp,
+    create_link_hash: HeaderHash,
+}
+
+/// Zome IO inner type for paginated link queries.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct GetLinksPageInput {
+    pub base_address: holo_hash::EntryHash,
+    pub tag_prefix: Option<crate::link::LinkTag>,
+    pub query: LinkPageQuery,
+}
+
+impl GetLinksPageInput {
+    pub fn new(
+        base_address: holo_hash::EntryHash,
+        tag_prefix: Option<crate::link::LinkTag>,
+        query: LinkPageQuery,
+    ) -> Self {
+        Self {
+            base_address,
+            tag_prefix,
+            query,
+        }
+    }
+}
+
+/// A page of the live links on a base.
+/// This can hold fewer links than the limit, or none, when some of the links
+/// looked at have been deleted, so only a `next` of `None` means there
+/// are no more pages.
+#[derive(
+    serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq, Clone, Debug, Default,
+)]
+pub struct LinkPage {
+    /// The live links in this page in the order they were created
+    pub links: Vec<Link>,
+    /// The cursor to get the next page with
+    pub next: Option<LinkCursor>,
+}
+
 type CreateLinkWithDeleteLinks = Vec<(SignedHeaderHashed, Vec<SignedHeaderHashed>)>;
 #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 /// CreateLinks with and DeleteLinks on them
@@ -153,3 +292,90 @@ impl LinkDetails {
         self.into()
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "fixturators")]
+mod tests {
+    use super::LinkPageQuery;
+    use crate::Timestamp;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::HeaderHashFixturator;
+    use holo_hash::HeaderHash;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn link_pages_are_in_time_order() {
+        let mut hashes: Vec<HeaderHash> = HeaderHashFixturator::new(Predictable).take(5).collect();
+        hashes.sort();
+        // Two links share a timestamp so the hash breaks the tie
+        let links: Vec<_> = vec![
+            (Timestamp(4, 0), hashes[4].clone()),
+            (Timestamp(2, 0), hashes[1].clone()),
+            (Timestamp(1, 0), hashes[0].clone()),
+            (Timestamp(3, 0), hashes[3].clone()),
+            (Timestamp(2, 0), hashes[2].clone()),
+        ];
+        let key = |l: &(Timestamp, HeaderHash)| l.clone();
+        let mut sorted = links.clone();
+        sorted.sort();
+
+        // No query is everything in one page
+        assert_eq!(
+            LinkPageQuery::new().page(links.clone(), key),
+            (sorted.clone(), None)
+        );
+
+        // Paging with a limit gets every link exactly once
+        let mut query = LinkPageQuery::new().limit(NonZeroU32::new(2).unwrap());
+        let mut pages = Vec::new();
+        loop {
+            let (page, next) = query.page(links.clone(), key);
+            pages.push(page);
+            match next {
+                Some(next) => query = query.after(next),
+                None => break,
+            }
+        }
+        assert_eq!(
+            pages,
+            vec![
+                sorted[..2].to_vec(),
+                sorted[2..4].to_vec(),
+                sorted[4..].to_vec()
+            ]
+        );
+
+        // Since is inclusive and until is exclusive
+        let query = LinkPageQuery::new()
+            .since(Timestamp(2, 0))
+            .until(Timestamp(4, 0));
+        assert_eq!(query.page(links, key), (sorted[1..4].to_vec(), None));
+    }
+
+    #[test]
+    fn zero_limit_is_rejected() {
+        #[derive(serde::Serialize)]
+        struct UncheckedQuery {
+            since: Option<Timestamp>,
+            until: Option<Timestamp>,
+            limit: Option<u32>,
+            after: Option<()>,
+        }
+        let query = |limit| {
+            holochain_serialized_bytes::encode(&UncheckedQuery {
+                since: None,
+                until: None,
+                limit: Some(limit),
+                after: None,
+            })
+            .unwrap()
+        };
+        let zero: Result<LinkPageQuery, _> = holochain_serialized_bytes::decode(&query(0));
+        assert!(zero.is_err());
+        let three: LinkPageQuery = holochain_serialized_bytes::decode(&query(3)).unwrap();
+        assert_eq!(
+            three,
+            LinkPageQuery::new().limit(NonZeroU32::new(3).unwrap())
+        );
+    }
+}
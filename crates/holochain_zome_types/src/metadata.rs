@@ -50,6 +50,10 @@ pub struct EntryDetails {
     /// These are also the headers that created this entry.
     /// but did not pass validation.
     pub rejected_headers: Vec<SignedHeaderHashed>,
+    /// Abandoned create relationships.
+    /// These are headers that created this entry
+    /// but could not be validated.
+    pub abandoned_headers: Vec<SignedHeaderHashed>,
     /// ## Delete relationships
     /// These are the deletes that have the
     /// `deletes_entry_address` set to the above Entry.
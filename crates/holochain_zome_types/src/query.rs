@@ -38,7 +38,7 @@ pub struct AgentActivity {
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
     /// Warrants about this AgentActivity.
-    /// Placeholder for future.
+    /// These are the evidence for an invalid or forked status.
     pub warrants: Vec<Warrant>,
 }
 
@@ -71,9 +71,9 @@ pub struct HighestObserved {
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 /// Status of the agent activity chain
-// TODO: In the future we will most likely be replaced
-// by warrants instead of Forked / Invalid so we can provide
-// evidence of why the chain has a status.
+///
+/// The evidence for a Forked or Invalid status
+/// is returned as [Warrant]s alongside the status.
 pub enum ChainStatus {
     /// This authority has no information on the chain.
     Empty,
@@ -15,7 +15,10 @@ pub struct MetadataRequest {
     pub all_deletes: bool,
     /// Get all the updates on an entry or header
     pub all_updates: bool,
-    /// Placeholder
+    // TODO: Implement when get_meta is implemented
+    /// Return the metadata for the newest update of
+    /// the basis instead of the basis itself.
+    /// See [GetOptions::follow_redirects](crate::entry::GetOptions::follow_redirects)
     pub follow_redirects: bool,
     /// Request the status of an entry.
     /// This is faster then getting all the headers
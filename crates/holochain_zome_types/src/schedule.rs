@@ -0,0 +1,45 @@
+//! Types for scheduling zome functions to be called by the host at a later time.
+
+use crate::zome::FunctionName;
+use holochain_serialized_bytes::prelude::*;
+
+/// Ask the host to call a function in the current zome after a delay,
+/// optionally repeating at a fixed interval.
+///
+/// The scheduled function is called by the author of the chain with `()` as
+/// its input. Scheduling a function that is already scheduled replaces the
+/// existing schedule for that function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct Schedule {
+    /// The function in the calling zome to call.
+    pub fn_name: FunctionName,
+    /// How long to wait before the first call.
+    pub delay: core::time::Duration,
+    /// If set, the function will be called again each time this much time
+    /// has elapsed since the previous call.
+    pub interval: Option<core::time::Duration>,
+}
+
+impl Schedule {
+    /// Call the function once, after the delay.
+    pub fn once(fn_name: FunctionName, delay: core::time::Duration) -> Self {
+        Self {
+            fn_name,
+            delay,
+            interval: None,
+        }
+    }
+
+    /// Call the function after the delay, then every interval after that.
+    pub fn recurring(
+        fn_name: FunctionName,
+        delay: core::time::Duration,
+        interval: core::time::Duration,
+    ) -> Self {
+        Self {
+            fn_name,
+            delay,
+            interval: Some(interval),
+        }
+    }
+}
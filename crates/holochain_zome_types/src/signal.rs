@@ -5,14 +5,40 @@ use holochain_serialized_bytes::prelude::*;
 
 /// A signal emitted by an app via `emit_signal`
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[repr(transparent)]
-#[serde(transparent)]
-pub struct AppSignal(crate::ExternIO);
+pub struct AppSignal {
+    /// An optional app-defined type for the signal.
+    /// Clients can subscribe to signals by type and use it as a hint for
+    /// what to deserialize the payload to.
+    signal_type: Option<String>,
+    /// The app-defined payload of the signal.
+    payload: crate::ExternIO,
+}
 
 impl AppSignal {
     /// Constructor
     pub fn new(extern_io: crate::ExternIO) -> Self {
-        Self(extern_io)
+        Self {
+            signal_type: None,
+            payload: extern_io,
+        }
+    }
+
+    /// Constructor for a signal with an app-defined type
+    pub fn typed(signal_type: String, extern_io: crate::ExternIO) -> Self {
+        Self {
+            signal_type: Some(signal_type),
+            payload: extern_io,
+        }
+    }
+
+    /// Accessor for the signal type
+    pub fn signal_type(&self) -> Option<&str> {
+        self.signal_type.as_deref()
+    }
+
+    /// Accessor for the payload
+    pub fn payload(&self) -> &crate::ExternIO {
+        &self.payload
     }
 }
 
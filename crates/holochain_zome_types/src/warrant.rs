@@ -1,6 +1,56 @@
 //! Types for warrants
+//!
+//! A warrant is a piece of evidence, signed by the offending agent,
+//! that proves an agent has broken the rules of the DNA.
+//! Agent activity authorities create warrants when they find invalid
+//! or forked chains and hand them out alongside the chain status
+//! so that anyone can check the evidence for themselves.
+use crate::element::SignedHeader;
+use holo_hash::AgentPubKey;
 pub use holochain_serialized_bytes::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+/// Evidence that an agent has broken the rules.
+pub enum Warrant {
+    /// This header was authored by the agent and
+    /// failed validation.
+    InvalidHeader(SignedHeader),
+    /// The agent authored both of these headers at the same
+    /// header sequence, forking their chain.
+    ChainFork(SignedHeader, SignedHeader),
+}
+
+impl Warrant {
+    /// Create a chain fork warrant.
+    /// The headers are ordered by signature so the same fork
+    /// always produces the same warrant.
+    pub fn chain_fork(a: SignedHeader, b: SignedHeader) -> Self {
+        if a.signature() <= b.signature() {
+            Warrant::ChainFork(a, b)
+        } else {
+            Warrant::ChainFork(b, a)
+        }
+    }
+
+    /// The agent this warrant is against.
+    pub fn agent(&self) -> &AgentPubKey {
+        match self {
+            Warrant::InvalidHeader(h) | Warrant::ChainFork(h, _) => h.header().author(),
+        }
+    }
+
+    /// The header sequence this warrant is about.
+    pub fn header_seq(&self) -> u32 {
+        match self {
+            Warrant::InvalidHeader(h) | Warrant::ChainFork(h, _) => h.header().header_seq(),
+        }
+    }
+
+    /// The signed headers that make up the evidence.
+    pub fn evidence(&self) -> Vec<&SignedHeader> {
+        match self {
+            Warrant::InvalidHeader(h) => vec![h],
+            Warrant::ChainFork(a, b) => vec![a, b],
+        }
+    }
+}
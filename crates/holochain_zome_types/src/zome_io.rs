@@ -89,14 +89,14 @@ wasm_io_types! {
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
 
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List all the live local capability claims, optionally filtered by tag.
+    fn capability_claims (Option<String>) -> Vec<zt::capability::CapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the live local capability grants, optionally filtered by tag.
+    fn capability_grants (Option<String>) -> Vec<zt::capability::LiveCapGrant>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapInfo;
 
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::HeaderHash;
@@ -120,8 +120,8 @@ wasm_io_types! {
     // Emit a Signal::App to subscribers on the interface
     fn emit_signal (zt::signal::AppSignal) -> ();
 
-    // @todo
-    fn entry_type_properties (()) -> ();
+    // Get the properties the dna declares for one of the current zome's entry types.
+    fn entry_type_properties (zt::entry_def::EntryDefId) -> Option<SerializedBytes>;
 
     fn get_agent_activity (zt::agent_info::GetAgentActivityInput) -> zt::query::AgentActivity;
 
@@ -132,6 +132,9 @@ wasm_io_types! {
     // Get links by entry hash from the cascade.
     fn get_links (zt::link::GetLinksInput) -> zt::link::Links;
 
+    // Get a page of links by entry hash from the cascade.
+    fn get_links_page (zt::link::GetLinksPageInput) -> zt::link::LinkPage;
+
     // Attempt to get a live entry from the cascade.
     fn get (zt::entry::GetInput) -> Option<zt::element::Element>;
 
@@ -153,11 +156,11 @@ wasm_io_types! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called by the host later.
+    fn schedule (zt::schedule::Schedule) -> ();
 
-    // @todo
-    fn show_env (()) -> ();
+    // Information about the environment the current zome call is running in.
+    fn show_env (()) -> zt::env_info::EnvInfo;
 
     // Attempt to have the keystore sign some data
     // The pubkey in the input needs to be found in the keystore for this to work
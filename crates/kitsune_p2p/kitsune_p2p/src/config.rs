@@ -1,4 +1,5 @@
 use ghost_actor::dependencies::tracing;
+use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
 use std::collections::HashMap;
 use url2::Url2;
 
@@ -23,6 +24,10 @@ pub struct KitsuneP2pTuningParams {
     pub default_rpc_multi_remote_agent_count: u32,
     pub default_rpc_multi_timeout_ms: u32,
     pub agent_info_expires_after_ms: u32,
+    pub gossip_peer_count: u32,
+    pub storage_arc_half_length: u32,
+    pub gossip_time_bucket_count: u32,
+    pub gossip_time_bucket_window_s: u32,
 }
 
 impl Default for KitsuneP2pTuningParams {
@@ -35,6 +40,10 @@ impl Default for KitsuneP2pTuningParams {
             default_rpc_multi_remote_agent_count: 2,
             default_rpc_multi_timeout_ms: 2000,
             agent_info_expires_after_ms: 1000 * 60 * 20, // 20 minutes
+            gossip_peer_count: 5,
+            storage_arc_half_length: MAX_HALF_LENGTH, // the whole dht
+            gossip_time_bucket_count: 12,
+            gossip_time_bucket_window_s: 60 * 10, // 10 minutes
         }
     }
 }
@@ -74,6 +83,19 @@ impl serde::Serialize for KitsuneP2pTuningParams {
             "agent_info_expires_after_ms",
             &format!("{}", self.agent_info_expires_after_ms),
         )?;
+        m.serialize_entry("gossip_peer_count", &format!("{}", self.gossip_peer_count))?;
+        m.serialize_entry(
+            "storage_arc_half_length",
+            &format!("{}", self.storage_arc_half_length),
+        )?;
+        m.serialize_entry(
+            "gossip_time_bucket_count",
+            &format!("{}", self.gossip_time_bucket_count),
+        )?;
+        m.serialize_entry(
+            "gossip_time_bucket_window_s",
+            &format!("{}", self.gossip_time_bucket_window_s),
+        )?;
         m.end()
     }
 }
@@ -115,6 +137,22 @@ impl<'de> serde::Deserialize<'de> for KitsuneP2pTuningParams {
                     Ok(v) => out.agent_info_expires_after_ms = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
                 },
+                "gossip_peer_count" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_peer_count = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "storage_arc_half_length" => match v.parse::<u32>() {
+                    Ok(v) => out.storage_arc_half_length = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "gossip_time_bucket_count" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_time_bucket_count = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "gossip_time_bucket_window_s" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_time_bucket_window_s = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
                 _ => tracing::warn!("INVALID TUNING PARAM: '{}'", k),
             }
         }
@@ -9,6 +9,7 @@ use crate::KitsuneBinType;
 use crate::KitsuneSignature;
 use crate::KitsuneSpace;
 use ::fixt::prelude::*;
+use kitsune_p2p_types::dht_arc::DhtArc;
 use url2::url2;
 
 fixturator!(
@@ -51,9 +52,16 @@ fixturator!(
     from SixtyFourBytes;
 );
 
+fixturator!(
+    DhtArc;
+    curve Empty DhtArc::new(0, 0);
+    curve Unpredictable DhtArc::new(fixt!(U32), fixt!(U32));
+    curve Predictable DhtArc::full(0);
+);
+
 fixturator!(
     AgentInfo;
-    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, U64, U64);
+    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, U64, U64, DhtArc);
 );
 
 fixturator!(
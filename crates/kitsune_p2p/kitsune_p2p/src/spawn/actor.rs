@@ -193,18 +193,10 @@ impl KitsuneP2pActor {
                                     from_agent,
                                     to_agent,
                                     dht_arc,
-                                    since_utc_epoch_s,
-                                    until_utc_epoch_s,
-                                    last_count,
+                                    buckets,
                                 }) => {
-                                    let input = ReqOpHashesEvt::new(
-                                        from_agent,
-                                        to_agent,
-                                        dht_arc,
-                                        since_utc_epoch_s,
-                                        until_utc_epoch_s,
-                                        Default::default(),
-                                    );
+                                    let input =
+                                        ReqOpHashesEvt::new(from_agent, to_agent, dht_arc, buckets);
                                     let (hashes, agent_hashes) = match local_req_op_hashes(
                                         &evt_sender,
                                         space,
@@ -222,19 +214,6 @@ impl KitsuneP2pActor {
                                         }
                                         Ok(r) => r,
                                     };
-                                    let hashes = match last_count {
-                                        OpCount::Consistent(last_count) => {
-                                            // Requester is consistent,
-                                            // now check if we are consistent.
-                                            if last_count == hashes.len() as u64 {
-                                                OpConsistency::Consistent
-                                            } else {
-                                                OpConsistency::Variance(hashes)
-                                            }
-                                        }
-                                        // Requester has a variance so we must return hashes.
-                                        OpCount::Variance => OpConsistency::Variance(hashes),
-                                    };
                                     let resp =
                                         wire::Wire::fetch_op_hashes_response(hashes, agent_hashes)
                                             .encode_vec()
@@ -1,6 +1,10 @@
+use crate::types::agent_store::AgentInfo;
 use crate::types::agent_store::AgentInfoSigned;
 use crate::types::KitsuneBinType;
+use crate::types::KitsuneP2pError;
 use crate::types::KitsuneSpace;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519PubKey;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519Signature;
 use once_cell::sync::Lazy;
 use once_cell::sync::OnceCell;
 use std::convert::TryFrom;
@@ -24,6 +28,10 @@ const RANDOM_LIMIT_DEFAULT: u32 = 16;
 /// relative to the expiry times.
 pub static NOW_OFFSET_MILLIS: OnceCell<i64> = OnceCell::new();
 
+/// How far our clock may be from the clock of the agent that signed an agent info
+/// before we reject the info as signed in the future or expired.
+const SIGNED_AT_SKEW_MILLIS: u64 = 1000 * 60 * 5;
+
 /// The HTTP header name for setting the op on POST requests.
 const OP_HEADER: &str = "X-Op";
 /// The header op to tell the service to put a signed agent info.
@@ -87,7 +95,7 @@ pub async fn put(
 }
 
 /// Simple wrapper to get the local time as milliseconds, to be compared against the remote time.
-fn local_now() -> crate::types::actor::KitsuneP2pResult<u64> {
+pub(crate) fn local_now() -> crate::types::actor::KitsuneP2pResult<u64> {
     Ok(std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)?
         .as_millis()
@@ -198,6 +206,75 @@ pub async fn random(
     Ok(ret?)
 }
 
+/// Check an agent info handed to us by the bootstrap service before we trust it.
+///
+/// The bootstrap service is not trusted to have done any of this for us, so we check that:
+/// - the agent key is well formed
+/// - the outer agent matches the agent in the signed info
+/// - the signature is valid for the signed bytes against the agent's key
+/// - the info is for the space we asked about
+/// - the info is not expired or signed in the future relative to `now`,
+///   give or take [SIGNED_AT_SKEW_MILLIS] of clock drift
+/// - there is at least one url and every url has a host we could connect to
+///
+/// Returns the decoded agent info if everything checks out.
+pub(crate) async fn validate_agent_info(
+    space: &KitsuneSpace,
+    agent_info_signed: &AgentInfoSigned,
+    now: u64,
+) -> crate::types::actor::KitsuneP2pResult<AgentInfo> {
+    let invalid = |reason: &str| Err(KitsuneP2pError::InvalidAgentInfo(reason.into()));
+
+    // Anything but a full 32 byte key plus 4 location bytes cannot be a valid agent.
+    if agent_info_signed.as_agent_ref().0.len() != 36 {
+        return invalid("agent key is the wrong length");
+    }
+
+    let agent_info = AgentInfo::try_from(agent_info_signed)?;
+    if agent_info.as_agent_ref() != agent_info_signed.as_agent_ref() {
+        return invalid("signed agent does not match the agent info");
+    }
+
+    let pub_key: SignEd25519PubKey = agent_info_signed.as_agent_ref().get_bytes().to_vec().into();
+    let signature: SignEd25519Signature = agent_info_signed.as_signature_ref().0.to_vec().into();
+    let data = Arc::new(agent_info_signed.as_agent_info_ref().to_vec());
+    if !pub_key
+        .verify(data, signature)
+        .await
+        .map_err(KitsuneP2pError::other)?
+    {
+        return invalid("signature does not verify against the agent key");
+    }
+
+    if agent_info.as_space_ref() != space {
+        return invalid("agent info is for a different space");
+    }
+
+    if agent_info.signed_at_ms() > now.saturating_add(SIGNED_AT_SKEW_MILLIS) {
+        return invalid("agent info was signed in the future");
+    }
+    match agent_info
+        .signed_at_ms()
+        .checked_add(agent_info.expires_after_ms())
+    {
+        Some(expires_at_ms) if expires_at_ms.saturating_add(SIGNED_AT_SKEW_MILLIS) > now => (),
+        _ => return invalid("agent info has expired"),
+    }
+
+    if agent_info.as_urls_ref().is_empty() {
+        return invalid("agent info has no urls");
+    }
+    if agent_info
+        .as_urls_ref()
+        .iter()
+        .any(|url| url.cannot_be_a_base() || url.host_str().map_or(true, str::is_empty))
+    {
+        return invalid("agent info has a url without a host");
+    }
+
+    Ok(agent_info)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +284,7 @@ mod tests {
     use crate::types::KitsuneBinType;
     use crate::types::KitsuneSignature;
     use ::fixt::prelude::*;
+    use kitsune_p2p_types::dht_arc::DhtArc;
     use lair_keystore_api::internal::sign_ed25519::sign_ed25519_keypair_new_from_entropy;
     use std::convert::TryInto;
 
@@ -227,6 +305,7 @@ mod tests {
             urls,
             (millis - 100).try_into().unwrap(),
             1000 * 60 * 20,
+            DhtArc::full(0),
         );
         let mut data = Vec::new();
         kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
@@ -312,6 +391,7 @@ mod tests {
                 fixt!(Urls),
                 now,
                 1000 * 60 * 20,
+                DhtArc::full(0),
             );
             let mut data = Vec::new();
             kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
@@ -362,4 +442,136 @@ mod tests {
         assert!(random_single.len() == 1);
         assert!(expected[0] == random_single[0] || expected[1] == random_single[0]);
     }
+
+    /// Sign an agent info with a fresh keypair.
+    async fn signed_agent_info(
+        space: KitsuneSpace,
+        urls: Urls,
+        signed_at_ms: u64,
+        expires_after_ms: u64,
+    ) -> (KitsuneAgent, AgentInfoSigned) {
+        let keypair = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let agent = KitsuneAgent::new((*keypair.pub_key.0).clone());
+        let agent_info = AgentInfo::new(
+            space,
+            agent.clone(),
+            urls,
+            signed_at_ms,
+            expires_after_ms,
+            DhtArc::full(0),
+        );
+        let mut data = Vec::new();
+        kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
+        let signature = keypair
+            .sign(std::sync::Arc::new(data.clone()))
+            .await
+            .unwrap();
+        let agent_info_signed = AgentInfoSigned::try_new(
+            agent.clone(),
+            KitsuneSignature((*signature.0).clone()),
+            data,
+        )
+        .unwrap();
+        (agent, agent_info_signed)
+    }
+
+    fn quic_urls() -> Urls {
+        vec![url2::url2!("kitsune-quic://127.0.0.1:5678")]
+    }
+
+    fn assert_invalid(result: crate::types::actor::KitsuneP2pResult<AgentInfo>) {
+        assert!(matches!(
+            result,
+            Err(crate::KitsuneP2pError::InvalidAgentInfo(_))
+        ));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_validate_agent_info() {
+        let space = fixt!(KitsuneSpace);
+        let now = 1_000_000_000;
+        let expires = 1000 * 60 * 20;
+
+        // A freshly signed info is accepted.
+        let (agent, good) = signed_agent_info(space.clone(), quic_urls(), now, expires).await;
+        let info = super::validate_agent_info(&space, &good, now)
+            .await
+            .unwrap();
+        assert_eq!(info.as_agent_ref(), &agent);
+
+        // It is rejected once it has expired, allowing for clock drift.
+        assert!(super::validate_agent_info(&space, &good, now + expires)
+            .await
+            .is_ok());
+        assert_invalid(
+            super::validate_agent_info(&space, &good, now + expires + super::SIGNED_AT_SKEW_MILLIS)
+                .await,
+        );
+
+        // It is rejected for any other space.
+        assert_invalid(super::validate_agent_info(&fixt!(KitsuneSpace), &good, now).await);
+
+        // Small clock drift is fine but an info signed well in the future is not.
+        let (_, drifted) = signed_agent_info(space.clone(), quic_urls(), now + 1000, expires).await;
+        assert!(super::validate_agent_info(&space, &drifted, now)
+            .await
+            .is_ok());
+        let (_, future) = signed_agent_info(
+            space.clone(),
+            quic_urls(),
+            now + super::SIGNED_AT_SKEW_MILLIS + 1,
+            expires,
+        )
+        .await;
+        assert_invalid(super::validate_agent_info(&space, &future, now).await);
+
+        // Infos without usable urls are rejected.
+        let (_, no_urls) = signed_agent_info(space.clone(), Vec::new(), now, expires).await;
+        assert_invalid(super::validate_agent_info(&space, &no_urls, now).await);
+        let (_, no_host) = signed_agent_info(
+            space.clone(),
+            vec![url2::url2!("data:text/plain,hello")],
+            now,
+            expires,
+        )
+        .await;
+        assert_invalid(super::validate_agent_info(&space, &no_host, now).await);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_validate_agent_info_signature() {
+        let space = fixt!(KitsuneSpace);
+        let now = 1_000_000_000;
+        let (_, good) = signed_agent_info(space.clone(), quic_urls(), now, 1000).await;
+
+        // A bad signature is rejected.
+        let mut bad_signature = good.as_signature_ref().clone();
+        bad_signature.0[0] ^= 0xff;
+        let tampered = AgentInfoSigned::try_new(
+            good.as_agent_ref().clone(),
+            bad_signature,
+            good.as_agent_info_ref().to_vec(),
+        )
+        .unwrap();
+        assert_invalid(super::validate_agent_info(&space, &tampered, now).await);
+
+        // Another agent cannot pass off a signed info as its own.
+        let (other, _) = signed_agent_info(space.clone(), quic_urls(), now, 1000).await;
+        let wrong_agent = AgentInfoSigned::try_new(
+            other,
+            good.as_signature_ref().clone(),
+            good.as_agent_info_ref().to_vec(),
+        )
+        .unwrap();
+        assert_invalid(super::validate_agent_info(&space, &wrong_agent, now).await);
+
+        // A short agent key is rejected rather than panicking.
+        let short_agent = AgentInfoSigned::try_new(
+            KitsuneAgent(vec![0; 2]),
+            good.as_signature_ref().clone(),
+            good.as_agent_info_ref().to_vec(),
+        )
+        .unwrap();
+        assert_invalid(super::validate_agent_info(&space, &short_agent, now).await);
+    }
 }
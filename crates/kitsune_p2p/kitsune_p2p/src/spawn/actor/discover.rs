@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 use super::*;
 use crate::agent_store::AgentInfo;
+use crate::agent_store::AgentInfoSigned;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
 use kitsune_p2p_types::codec::Codec;
 use std::collections::HashSet;
@@ -346,7 +347,7 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         }
 
         if let Ok(list) = super::bootstrap::random(
-            bootstrap_service,
+            bootstrap_service.clone(),
             super::bootstrap::RandomQuery {
                 space: space.clone(),
                 // grap a couple extra incase they happen to be local
@@ -356,8 +357,7 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
-                if let Ok(info) = AgentInfo::try_from(&item) {
+                if let Some(info) = validate_bootstrap_agent_info(&space, &item).await {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
@@ -400,7 +400,7 @@ pub(crate) fn add_5_or_less_non_local_agents(
 ) -> MustBoxFuture<'static, KitsuneP2pResult<()>> {
     async move {
         if let Ok(list) = super::bootstrap::random(
-            Some(bootstrap_service),
+            Some(bootstrap_service.clone()),
             super::bootstrap::RandomQuery {
                 space: space.clone(),
                 limit: 8.into(),
@@ -409,8 +409,7 @@ pub(crate) fn add_5_or_less_non_local_agents(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
-                if let Ok(info) = AgentInfo::try_from(&item) {
+                if let Some(info) = validate_bootstrap_agent_info(&space, &item).await {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
@@ -434,3 +433,36 @@ pub(crate) fn add_5_or_less_non_local_agents(
     .boxed()
     .into()
 }
+
+/// Validate an agent info that came from the bootstrap service, so that a bad
+/// or malicious service cannot put junk into our peer store.
+/// Rejected infos are logged and counted in the metrics.
+/// Times are checked against our own clock, never the bootstrap service's,
+/// so a service that serves stale infos can't also make them look fresh.
+async fn validate_bootstrap_agent_info(
+    space: &KitsuneSpace,
+    agent_info_signed: &AgentInfoSigned,
+) -> Option<AgentInfo> {
+    let now = match super::bootstrap::local_now() {
+        Ok(now) => now,
+        Err(e) => {
+            tracing::warn!(
+                "could not read the local clock to check an agent info: {}",
+                e
+            );
+            return None;
+        }
+    };
+    match super::bootstrap::validate_agent_info(space, agent_info_signed, now).await {
+        Ok(info) => Some(info),
+        Err(e) => {
+            tracing::warn!(
+                agent = ?agent_info_signed.as_agent_ref(),
+                "rejected agent info from bootstrap service: {}",
+                e
+            );
+            KitsuneMetrics::count(KitsuneMetrics::AgentInfoRejected, 1);
+            None
+        }
+    }
+}
@@ -1,5 +1,5 @@
-//! This is a temporary quick-hack gossip module for use with the
-//! in-memory / full-sync / non-sharded networking module
+//! Sharded gossip. Each agent stores the ops in its [DhtArc] and
+//! only gossips the part of that arc it shares with its gossip partner.
 
 use crate::types::actor::KitsuneP2pResult;
 use crate::types::gossip::*;
@@ -8,11 +8,14 @@ use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures;
 use ghost_actor::GhostError;
 use kitsune_p2p_types::dht_arc::DhtArc;
-use std::collections::HashMap;
+use rand::seq::SliceRandom;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 use std::sync::Arc;
 
+/// A gossip round between two agents over the overlap of their arcs.
+type GossipPair = (Arc<KitsuneAgent>, Arc<KitsuneAgent>, DhtArc);
+
 ghost_actor::ghost_chan! {
     /// "Event" requests emitted by the gossip module
     pub chan GossipEvent<crate::KitsuneP2pError> {
@@ -54,7 +57,7 @@ async fn gossip_loop(
     config: Arc<KitsuneP2pConfig>,
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
 ) -> KitsuneP2pResult<()> {
-    let mut gossip_data = GossipData::new(evt_send);
+    let mut gossip_data = GossipData::new(evt_send, config.clone());
     loop {
         match gossip_data.take_action().await {
             Err(KitsuneP2pError::GhostError(GhostError::Disconnected)) => {
@@ -76,16 +79,19 @@ async fn gossip_loop(
 
 struct GossipData {
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
-    pending_gossip_list: Vec<(Arc<KitsuneAgent>, Arc<KitsuneAgent>)>,
-    last_counts: HashMap<Arc<KitsuneAgent>, (u64, u64)>,
+    config: Arc<KitsuneP2pConfig>,
+    pending_gossip_list: Vec<GossipPair>,
 }
 
 impl GossipData {
-    pub fn new(evt_send: futures::channel::mpsc::Sender<GossipEvent>) -> Self {
+    pub fn new(
+        evt_send: futures::channel::mpsc::Sender<GossipEvent>,
+        config: Arc<KitsuneP2pConfig>,
+    ) -> Self {
         Self {
             evt_send,
+            config,
             pending_gossip_list: Vec::new(),
-            last_counts: HashMap::new(),
         }
     }
 
@@ -100,97 +106,81 @@ impl GossipData {
 
     async fn fetch_pending_gossip_list(&mut self) -> KitsuneP2pResult<()> {
         let (local_agents, remote_agents) = self.evt_send.list_neighbor_agents().await?;
-        // super naive gossip just processes all combinations
-        // also causes duplication because it runs pairs from both sides
-        for (i, a1) in local_agents.iter().enumerate() {
-            for a2 in local_agents.iter().skip(i) {
-                // at the very least, avoid gossiping with ourselves
-                if a1 != a2 {
-                    self.pending_gossip_list.push((a1.clone(), a2.clone()));
-                }
-            }
-            for a2 in remote_agents.iter() {
-                self.pending_gossip_list.push((a1.clone(), a2.clone()));
-            }
-        }
+        self.pending_gossip_list = gossip_pairs(
+            &local_agents,
+            &remote_agents,
+            self.config.tuning_params.gossip_peer_count as usize,
+        );
         Ok(())
     }
 
     #[tracing::instrument(skip(self))]
     async fn process_next_gossip(&mut self) -> KitsuneP2pResult<()> {
         // !is_empty() checked above in take_action
-        let (from_agent, to_agent) = self.pending_gossip_list.remove(0);
-        let span = tracing::debug_span!("next_gossip", ?from_agent, ?to_agent);
-
-        // Get the last count for this interaction
-        let last_count = self.last_counts.entry(to_agent.clone()).or_insert((0, 0));
+        let (from_agent, to_agent, dht_arc) = self.pending_gossip_list.remove(0);
+        let span = tracing::debug_span!("next_gossip", ?from_agent, ?to_agent, ?dht_arc);
 
         // required so from_iters below know the build_hasher type
         type S = HashSet<Arc<KitsuneOpHash>>;
         type A = HashSet<(Arc<KitsuneAgent>, u64)>;
 
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_from, agent_info_from) = self
+        let windows = time_windows(
+            now_s(),
+            self.config.tuning_params.gossip_time_bucket_window_s as i64,
+            self.config.tuning_params.gossip_time_bucket_count as usize,
+        );
+
+        // only the ops in the overlap of our arcs
+        let (bucket_hashes_from, agent_info_from) = self
             .evt_send
             .req_op_hashes(ReqOpHashesEvt::new(
                 from_agent.clone(), // from not to because we're initiating
                 from_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-                Default::default(), // This is ignored because requesting from self
+                dht_arc,
+                // No fingerprints so we get every bucket back
+                windows
+                    .iter()
+                    .map(|(since, until)| OpBucket::new(*since, *until, None))
+                    .collect(),
             ))
             .await?;
-        let op_hashes_from = match op_hashes_from {
-            OpConsistency::Variance(h) => h,
-            // Not currently used
-            OpConsistency::Consistent => {
-                unreachable!("We don't track consistency of hashes for local requests")
-            }
-        };
-        let op_count = if last_count.0 == op_hashes_from.len() as u64 {
-            // We have nothing new for them but
-            // they might still have something new
-            // for us.
-            OpCount::Consistent(last_count.1)
-        } else {
-            // We have new gossip for them so
-            // they need to tell us what they have
-            // so we can compute the difference.
-            OpCount::Variance
-        };
-        last_count.0 = op_hashes_from.len() as u64;
-
-        let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
-        let agent_info_from: A = HashSet::from_iter(agent_info_from);
-        span.in_scope(|| {
-            tracing::debug!(from_has_len = ?op_hashes_from.len());
-        });
+        let bucket_hashes_from = bucket_hashes_from
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect::<Vec<_>>();
+        let buckets = windows
+            .iter()
+            .zip(bucket_hashes_from.iter())
+            .map(|((since, until), hashes)| {
+                OpBucket::new(*since, *until, Some(OpFingerprint::new(hashes)))
+            })
+            .collect();
 
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_to, agent_info_to) = self
+        // they only send the hashes for buckets that differ from ours
+        let (bucket_hashes_to, agent_info_to) = self
             .evt_send
             .req_op_hashes(ReqOpHashesEvt::new(
                 from_agent.clone(),
                 to_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-                op_count,
+                dht_arc,
+                buckets,
             ))
             .await?;
-        let op_hashes_to = match op_hashes_to {
-            OpConsistency::Variance(h) => {
-                last_count.1 = h.len() as u64;
-                h
-            }
-            // There's no new gossip from us or them
-            // so our job is done.
-            OpConsistency::Consistent => {
-                return Ok(());
+
+        // so we only compare the hashes in those buckets
+        let mut op_hashes_from = S::default();
+        let mut op_hashes_to = S::default();
+        for (from, to) in bucket_hashes_from.into_iter().zip(bucket_hashes_to) {
+            if let Some(to) = to {
+                op_hashes_from.extend(from);
+                op_hashes_to.extend(to);
             }
-        };
-        let op_hashes_to: S = HashSet::from_iter(op_hashes_to);
+        }
+        let agent_info_from: A = HashSet::from_iter(agent_info_from);
+        span.in_scope(|| {
+            tracing::debug!(from_has_len = ?op_hashes_from.len());
+        });
+
         let agent_info_to: A = HashSet::from_iter(agent_info_to);
         span.in_scope(|| {
             tracing::debug!(to_has_len = ?op_hashes_to.len());
@@ -291,3 +281,178 @@ impl GossipData {
         Ok(())
     }
 }
+
+/// Split all of time into `count` windows of op authoring time.
+/// Ops authored recently are the most likely to differ between agents,
+/// so the first window is at least `window_s` long and each window going
+/// back in time is about twice as long as the one before it. The last
+/// window reaches back to the start of time and the first one forward
+/// to the end of time.
+/// Each window starts on a multiple of its own length since the epoch,
+/// so agents with slightly different clocks still agree on the windows,
+/// and older windows only move once per their length.
+fn time_windows(now_s: i64, window_s: i64, count: usize) -> Vec<(i64, i64)> {
+    let window_s = std::cmp::max(window_s, 1);
+    let mut windows = Vec::with_capacity(count);
+    let mut until = i64::MAX;
+    let mut len = window_s;
+    for i in 0..std::cmp::max(count, 1) {
+        let since = if i + 1 >= count {
+            i64::MIN
+        } else {
+            let start = now_s.div_euclid(len).saturating_mul(len);
+            // Only the first window holds the current one,
+            // the rest are entirely in the past.
+            if i == 0 {
+                start
+            } else {
+                start.saturating_sub(len)
+            }
+        };
+        windows.push((since, until));
+        until = since;
+        len = len.saturating_mul(2);
+    }
+    windows
+}
+
+fn now_s() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Choose who each local agent gossips with this round.
+/// Local agents gossip with every other local agent they share part of
+/// the dht with, as that doesn't touch the network.
+/// Each local agent also gossips with up to `peer_count` randomly chosen
+/// remote agents whose arcs overlap its own, so the number of gossip
+/// rounds grows with the number of local agents, not the network size.
+fn gossip_pairs(
+    local_agents: &[(Arc<KitsuneAgent>, DhtArc)],
+    remote_agents: &[(Arc<KitsuneAgent>, DhtArc)],
+    peer_count: usize,
+) -> Vec<GossipPair> {
+    let mut rng = rand::thread_rng();
+    let mut pairs = Vec::new();
+    for (i, (a1, arc1)) in local_agents.iter().enumerate() {
+        // Both sides are gossiped each round so only add local pairs once
+        for (a2, arc2) in local_agents.iter().skip(i + 1) {
+            if let Some(overlap) = arc1.overlap(arc2) {
+                pairs.push((a1.clone(), a2.clone(), overlap));
+            }
+        }
+        let neighbors = remote_agents
+            .iter()
+            .filter_map(|(a2, arc2)| arc1.overlap(arc2).map(|overlap| (a2, overlap)))
+            .collect::<Vec<_>>();
+        for (a2, overlap) in neighbors.choose_multiple(&mut rng, peer_count) {
+            pairs.push((a1.clone(), (*a2).clone(), *overlap));
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use ::fixt::prelude::*;
+
+    fn agents(arcs: &[DhtArc]) -> Vec<(Arc<KitsuneAgent>, DhtArc)> {
+        arcs.iter()
+            .map(|arc| (Arc::new(fixt!(KitsuneAgent, Unpredictable)), *arc))
+            .collect()
+    }
+
+    #[test]
+    fn gossip_pairs_only_overlapping_arcs() {
+        let quarter = u32::MAX / 4;
+        let local = agents(&[
+            DhtArc::new(0, quarter),
+            DhtArc::new(quarter * 2 + 1000, 100),
+        ]);
+        let remote = agents(&[
+            DhtArc::new(quarter, quarter),
+            DhtArc::new(quarter * 2 + 10_000, 100),
+            DhtArc::full(0),
+        ]);
+
+        let pairs = gossip_pairs(&local, &remote, 10);
+        let has_pair = |a: &Arc<KitsuneAgent>, b: &Arc<KitsuneAgent>| {
+            pairs
+                .iter()
+                .find(|(a1, a2, _)| a1 == a && a2 == b)
+                .map(|p| p.2)
+        };
+
+        // The local agents don't overlap each other
+        assert_eq!(has_pair(&local[0].0, &local[1].0), None);
+        assert_eq!(has_pair(&local[1].0, &local[0].0), None);
+
+        // Gossip is limited to the overlap
+        assert_eq!(
+            has_pair(&local[0].0, &remote[0].0),
+            local[0].1.overlap(&remote[0].1)
+        );
+        assert_eq!(has_pair(&local[0].0, &remote[2].0), Some(local[0].1));
+        assert_eq!(has_pair(&local[1].0, &remote[2].0), Some(local[1].1));
+        assert_eq!(has_pair(&local[0].0, &remote[1].0), None);
+        assert_eq!(has_pair(&local[1].0, &remote[1].0), None);
+        assert_eq!(pairs.len(), 3);
+    }
+
+    #[test]
+    fn gossip_pairs_bounded_by_peer_count() {
+        let local = agents(&[DhtArc::full(0), DhtArc::full(0)]);
+        let remote = agents(&[DhtArc::full(0); 100]);
+
+        let pairs = gossip_pairs(&local, &remote, 5);
+
+        // One local pair and 5 remote agents for each local agent
+        assert_eq!(pairs.len(), 1 + 5 * 2);
+    }
+
+    #[test]
+    fn time_windows_cover_all_time() {
+        let windows = time_windows(1_000_005, 10, 4);
+        assert_eq!(
+            windows,
+            vec![
+                (1_000_000, i64::MAX),
+                (999_980, 1_000_000),
+                (999_960, 999_980),
+                (i64::MIN, 999_960),
+            ]
+        );
+
+        // Agents with slightly different clocks agree on the windows
+        assert_eq!(time_windows(1_000_009, 10, 4), windows);
+
+        // Only the newest window moves when the time crosses its boundary
+        assert_eq!(
+            time_windows(1_000_015, 10, 4),
+            vec![
+                (1_000_010, i64::MAX),
+                (999_980, 1_000_010),
+                (999_960, 999_980),
+                (i64::MIN, 999_960),
+            ]
+        );
+
+        assert_eq!(time_windows(1_000_005, 10, 1), vec![(i64::MIN, i64::MAX)]);
+        assert_eq!(time_windows(1_000_005, 10, 0), vec![(i64::MIN, i64::MAX)]);
+    }
+
+    #[test]
+    fn op_fingerprint_ignores_order() {
+        let hashes = (0..10u8)
+            .map(|i| Arc::new(KitsuneOpHash::new(vec![i; 36])))
+            .collect::<Vec<_>>();
+        let fingerprint = OpFingerprint::new(&hashes);
+        assert_eq!(OpFingerprint::new(hashes.iter().rev()), fingerprint);
+        assert_ne!(OpFingerprint::new(&hashes[1..]), fingerprint);
+        assert_ne!(OpFingerprint::new(&hashes[..0]), fingerprint);
+    }
+}
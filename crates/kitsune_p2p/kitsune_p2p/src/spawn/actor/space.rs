@@ -4,6 +4,8 @@ use super::*;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 
@@ -64,12 +66,11 @@ impl gossip::GossipEventHandler for Space {
     fn handle_list_neighbor_agents(
         &mut self,
     ) -> gossip::GossipEventHandlerResult<ListNeighborAgents> {
-        // while full-sync this is just a clone of list_by_basis
         let local_agents = self
             .local_joined_agents
             .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
+            .map(|a| (a.clone(), self.storage_arc(a)))
+            .collect::<HashMap<_, _>>();
         let agent = self.local_joined_agents.iter().next().cloned();
         let fut = match agent {
             Some(agent) => self
@@ -84,8 +85,14 @@ impl gossip::GossipEventHandler for Space {
             let remote_agents = fut
                 .await?
                 .into_iter()
-                .map(|ai| Arc::new(ai.into_agent()))
-                .filter(|a| !local_agents.contains(a))
+                .filter(|ai| !local_agents.contains_key(ai.as_agent_ref()))
+                .filter_map(|ai| match types::agent_store::AgentInfo::try_from(&ai) {
+                    Ok(info) => Some((Arc::new(ai.into_agent()), info.storage_arc())),
+                    Err(e) => {
+                        tracing::warn!(msg = "skipping undecodable agent info", ?e);
+                        None
+                    }
+                })
                 .collect::<Vec<_>>();
             let local_agents = local_agents.into_iter().collect::<Vec<_>>();
             Ok((local_agents, remote_agents))
@@ -100,19 +107,13 @@ impl gossip::GossipEventHandler for Space {
     ) -> gossip::GossipEventHandlerResult<OpHashesAgentHashes> {
         if self.local_joined_agents.contains(&input.to_agent) {
             let fut = local_req_op_hashes(&self.evt_sender, self.space.clone(), input);
-            Ok(
-                async move { fut.await.map(|r| (OpConsistency::Variance(r.0), r.1)) }
-                    .boxed()
-                    .into(),
-            )
+            Ok(async move { fut.await }.boxed().into())
         } else {
             let ReqOpHashesEvt {
                 to_agent,
                 dht_arc,
-                since_utc_epoch_s,
-                until_utc_epoch_s,
                 from_agent,
-                op_count,
+                buckets,
             } = input;
             let transport_tx = self.transport.clone();
             let evt_sender = self.evt_sender.clone();
@@ -129,16 +130,9 @@ impl gossip::GossipEventHandler for Space {
                     None => return Err(KitsuneP2pError::RoutingAgentError(to_agent)),
                     Some(i) => i,
                 };
-                let data = wire::Wire::fetch_op_hashes(
-                    space,
-                    from_agent,
-                    to_agent,
-                    dht_arc,
-                    since_utc_epoch_s,
-                    until_utc_epoch_s,
-                    op_count,
-                )
-                .encode_vec()?;
+                let data =
+                    wire::Wire::fetch_op_hashes(space, from_agent, to_agent, dht_arc, buckets)
+                        .encode_vec()?;
                 let info = types::agent_store::AgentInfo::try_from(&info)?;
                 let url = info.as_urls_ref().get(0).unwrap().clone();
                 let (_, mut write, read) = transport_tx.create_channel(url).await?;
@@ -274,21 +268,32 @@ pub fn local_req_op_hashes(
     evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     space: Arc<KitsuneSpace>,
     input: ReqOpHashesEvt,
-) -> impl std::future::Future<Output = Result<LocalOpHashesAgentHashes, KitsuneP2pError>> {
+) -> impl std::future::Future<Output = Result<OpHashesAgentHashes, KitsuneP2pError>> {
     let ReqOpHashesEvt {
         to_agent,
         dht_arc,
-        since_utc_epoch_s,
-        until_utc_epoch_s,
+        buckets,
         ..
     } = input;
-    let fut = evt_sender.fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
-        space: space.clone(),
-        agent: to_agent.clone(),
-        dht_arc,
-        since_utc_epoch_s,
-        until_utc_epoch_s,
-    });
+    let bucket_futs = buckets
+        .into_iter()
+        .map(|bucket| {
+            let fut = evt_sender.fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
+                space: space.clone(),
+                agent: to_agent.clone(),
+                dht_arc,
+                since_utc_epoch_s: bucket.since_utc_epoch_s,
+                until_utc_epoch_s: bucket.until_utc_epoch_s,
+            });
+            async move {
+                // Don't send hashes the requester already has
+                fut.await.map(|hashes| match bucket.fingerprint {
+                    Some(fingerprint) if fingerprint == OpFingerprint::new(&hashes) => None,
+                    _ => Some(hashes),
+                })
+            }
+        })
+        .collect::<Vec<_>>();
     let peer_fut = evt_sender.query_agent_info_signed(QueryAgentInfoSignedEvt {
         space,
         agent: to_agent,
@@ -303,7 +308,10 @@ pub fn local_req_op_hashes(
                 Ok((Arc::new(ai.into()), time))
             })
             .collect::<Result<Vec<_>, KitsuneP2pError>>()?;
-        Ok((fut.await?, agent_infos))
+        Ok((
+            futures::future::try_join_all(bucket_futs).await?,
+            agent_infos,
+        ))
     }
 }
 
@@ -428,7 +436,11 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
+        let agent_list: Vec<(Arc<KitsuneAgent>, DhtArc)> = self
+            .local_joined_agents
+            .iter()
+            .map(|a| (a.clone(), self.storage_arc(a)))
+            .collect();
         let bound_url = self.transport.bound_url();
         let evt_sender = self.evt_sender.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
@@ -439,13 +451,14 @@ impl SpaceInternalHandler for Space {
                 .query_pairs()
                 .map(|(_, sub_url)| url2::url2!("{}", sub_url))
                 .collect::<Vec<_>>();
-            for agent in agent_list {
+            for (agent, storage_arc) in agent_list {
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
                     (*agent).clone(),
                     urls.clone(),
                     crate::spawn::actor::bootstrap::now_once(None).await?,
                     expires_after,
+                    storage_arc,
                 );
                 let mut data = Vec::new();
                 kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info)?;
@@ -719,6 +732,15 @@ impl Space {
         }
     }
 
+    /// The arc of the dht a local agent stores and gossips ops for.
+    /// It is centered on the agent's own location.
+    fn storage_arc(&self, agent: &KitsuneAgent) -> DhtArc {
+        DhtArc::new(
+            agent.get_loc(),
+            self.config.tuning_params.storage_arc_half_length,
+        )
+    }
+
     /// actual logic for handle_rpc_multi ...
     /// the top-level handler may or may not spawn a task for this
     #[tracing::instrument(skip(self, input))]
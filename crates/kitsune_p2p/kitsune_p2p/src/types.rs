@@ -44,6 +44,10 @@ pub enum KitsuneP2pError {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// Agent info received from elsewhere did not pass validation.
+    #[error("Invalid Agent Info: {0}")]
+    InvalidAgentInfo(Box<str>),
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -4,6 +4,7 @@ use crate::types::KitsuneAgent;
 use crate::types::KitsuneP2pError;
 use crate::types::KitsuneSignature;
 use crate::types::KitsuneSpace;
+use kitsune_p2p_types::dht_arc::DhtArc;
 use url2::Url2;
 
 /// A list of Urls.
@@ -85,6 +86,15 @@ pub struct AgentInfo {
     // The expiry ttl for the agent info relative to the signing time.
     #[as_ref(ignore)]
     expires_after_ms: u64,
+    // The arc of the dht this agent stores and gossips ops for.
+    // Agent info signed before arcs were advertised is treated as holding everything.
+    #[as_ref(ignore)]
+    #[serde(default = "full_storage_arc")]
+    storage_arc: DhtArc,
+}
+
+fn full_storage_arc() -> DhtArc {
+    DhtArc::full(0)
 }
 
 impl std::convert::TryFrom<&AgentInfoSigned> for AgentInfo {
@@ -104,6 +114,7 @@ impl AgentInfo {
         urls: Urls,
         signed_at_ms: u64,
         expires_after_ms: u64,
+        storage_arc: DhtArc,
     ) -> Self {
         Self {
             space,
@@ -111,6 +122,7 @@ impl AgentInfo {
             urls,
             signed_at_ms,
             expires_after_ms,
+            storage_arc,
         }
     }
 }
@@ -146,6 +158,11 @@ impl AgentInfo {
     pub fn expires_after_ms(&self) -> u64 {
         self.expires_after_ms
     }
+
+    /// Accessor for storage_arc.
+    pub fn storage_arc(&self) -> DhtArc {
+        self.storage_arc
+    }
 }
 
 impl From<AgentInfoSigned> for KitsuneAgent {
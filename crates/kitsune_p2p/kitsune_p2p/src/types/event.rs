@@ -12,9 +12,9 @@ pub struct FetchOpHashesForConstraintsEvt {
     pub agent: Arc<super::KitsuneAgent>,
     /// The dht arc to query.
     pub dht_arc: kitsune_p2p_types::dht_arc::DhtArc,
-    /// Only retreive items received since this time (INCLUSIVE).
+    /// Only retreive items authored since this time (INCLUSIVE).
     pub since_utc_epoch_s: i64,
-    /// Only retreive items received until this time (EXCLUSIVE).
+    /// Only retreive items authored until this time (EXCLUSIVE).
     pub until_utc_epoch_s: i64,
 }
 
@@ -17,12 +17,51 @@ pub struct ReqOpHashesEvt {
     pub to_agent: Arc<KitsuneAgent>,
     /// The arc on the dht that you want ops from.
     pub dht_arc: DhtArc,
-    /// Get ops from this time.
+    /// The windows of time you want ops from.
+    pub buckets: Vec<OpBucket>,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, derive_more::Constructor, serde::Serialize, serde::Deserialize,
+)]
+/// A window of time to request op hashes for.
+pub struct OpBucket {
+    /// Get ops authored from this time.
     pub since_utc_epoch_s: i64,
-    /// Get ops till this time.
+    /// Get ops authored till this time.
     pub until_utc_epoch_s: i64,
-    /// Count of the hashes from the requesting agent
-    pub op_count: OpCount,
+    /// The requester's fingerprint of the ops it holds in this window.
+    /// If it matches the responder's fingerprint the hashes aren't sent.
+    pub fingerprint: Option<OpFingerprint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A compact summary of a set of op hashes.
+/// The same hashes always have the same fingerprint,
+/// regardless of the order they are in.
+pub struct OpFingerprint {
+    /// The number of hashes.
+    count: u64,
+    /// Every hash XORed together.
+    #[serde(with = "serde_bytes")]
+    xor: Vec<u8>,
+}
+
+impl OpFingerprint {
+    /// Fingerprint a set of op hashes.
+    pub fn new<'a>(hashes: impl IntoIterator<Item = &'a Arc<KitsuneOpHash>>) -> Self {
+        let mut out = Self::default();
+        for hash in hashes {
+            if out.xor.len() < hash.len() {
+                out.xor.resize(hash.len(), 0);
+            }
+            for (x, b) in out.xor.iter_mut().zip(hash.iter()) {
+                *x ^= b;
+            }
+            out.count += 1;
+        }
+        out
+    }
 }
 
 #[derive(Debug, derive_more::Constructor)]
@@ -51,47 +90,19 @@ pub struct GossipEvt {
     pub agents: Vec<AgentInfoSigned>,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-/// Type for responding to a request for dht op hashes.
-/// If the count hasn't changed then the hashes are consistent
-/// otherwise there is a change in the data and new hashes are sent.
-pub enum OpConsistency {
-    /// There is new hashes since last gossip request.
-    Variance(OpHashes),
-    /// Gossip is consistent since the last call.
-    Consistent,
-}
-
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-/// The count of ops last seen from the requester.
-/// If the requester has a variance then the
-/// responder needs to reply with hashes.
-pub enum OpCount {
-    /// Requestor has a variance since they last
-    /// gossiped to this agent.
-    Variance,
-    /// Requestor is consistent since last gossip
-    /// and this is the count they saw from
-    /// this agent.
-    Consistent(u64),
-}
-
 /// Dht Op hashes that an agent holds
 pub type OpHashes = Vec<Arc<KitsuneOpHash>>;
 
-/// Dht op and agent hashes that the agent has information on.
-pub type OpHashesAgentHashes = (OpConsistency, Vec<(Arc<KitsuneAgent>, u64)>);
+/// Dht Op hashes in each requested [OpBucket].
+/// A bucket is `None` if the requester's fingerprint matched.
+pub type BucketOpHashes = Vec<Option<OpHashes>>;
 
 /// Dht op and agent hashes that the agent has information on.
-/// Same as [OpHashesAgentHashes] but without consistency information.
-pub type LocalOpHashesAgentHashes = (OpHashes, Vec<(Arc<KitsuneAgent>, u64)>);
+pub type OpHashesAgentHashes = (BucketOpHashes, Vec<(Arc<KitsuneAgent>, u64)>);
 /// The Dht op data and agent store information
 pub type OpDataAgentInfo = (Vec<(Arc<KitsuneOpHash>, Vec<u8>)>, Vec<AgentInfoSigned>);
-/// Local and remote neighbors.
-pub type ListNeighborAgents = (Vec<Arc<KitsuneAgent>>, Vec<Arc<KitsuneAgent>>);
-
-impl Default for OpCount {
-    fn default() -> Self {
-        OpCount::Variance
-    }
-}
+/// Local and remote neighbors, with the arc each of them stores.
+pub type ListNeighborAgents = (
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+);
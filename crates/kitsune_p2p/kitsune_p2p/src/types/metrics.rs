@@ -14,7 +14,8 @@ observability::metrics!(
     AgentInfoQueryResp,
     Gossip,
     GossipResp,
-    Fail
+    Fail,
+    AgentInfoRejected
 );
 
 /// Print all metrics as tracing events
@@ -69,6 +70,11 @@ pub fn print_all_metrics() {
                     )
                     .expect("Failed to print metrics");
                 }
+                // Counts agent infos rather than bytes.
+                AgentInfoRejected => {
+                    writeln!(out, "metric: {:?} {}", metric, count)
+                        .expect("Failed to print metrics");
+                }
             }
         }
         writeln!(
@@ -8,7 +8,9 @@ use std::ops::RangeBounds;
 #[cfg(test)]
 use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, From, Into)]
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, From, Into,
+)]
 /// Type for representing a location that can wrap around
 /// a u32 dht arc
 pub struct DhtLocation(pub Wrapping<u32>);
@@ -20,7 +22,10 @@ pub struct DhtLocation(pub Wrapping<u32>);
 /// 1 more is added to represent the middle point of an odd length array
 pub const MAX_HALF_LENGTH: u32 = (u32::MAX / 2) + 1 + 1;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+/// The number of locations on the dht arc.
+const ARC_SIZE: u64 = u32::MAX as u64 + 1;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
 /// Represents how much of a dht arc is held
 /// center_loc is where the hash is.
 /// The center_loc is the center of the arc
@@ -56,6 +61,87 @@ impl DhtArc {
         }
     }
 
+    /// Create an Arc that covers the entire dht, centered on a location
+    pub fn full<I: Into<DhtLocation>>(center_loc: I) -> Self {
+        Self::new(center_loc, MAX_HALF_LENGTH)
+    }
+
+    /// Does this arc hold nothing
+    pub fn is_empty(&self) -> bool {
+        self.half_length == 0
+    }
+
+    /// Does this arc hold every location
+    pub fn is_full(&self) -> bool {
+        self.half_length == MAX_HALF_LENGTH
+    }
+
+    /// The smallest arc that covers every location held by both
+    /// this arc and the other arc.
+    /// Returns None if the arcs have nothing in common.
+    ///
+    /// Two large arcs can overlap at both of their ends. The true
+    /// overlap is then two separate ranges which a single arc can't
+    /// represent, so the smaller of the two arcs is returned instead.
+    /// The result may hold a few more locations than the true overlap
+    /// (arcs always have an odd length) but never fewer.
+    pub fn overlap(&self, other: &DhtArc) -> Option<DhtArc> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        if self.is_full() {
+            return Some(*other);
+        }
+        if other.is_full() {
+            return Some(*self);
+        }
+        let (a_start, a_len) = self.start_and_len();
+        let (b_start, b_len) = other.start_and_len();
+        // Work relative to the start of this arc so that
+        // this arc is the range 0..a_len.
+        let b_offset = (Wrapping(b_start) - Wrapping(a_start)).0 as u64;
+        let b_end = b_offset + b_len;
+        let mut pieces = Vec::with_capacity(2);
+        if b_offset < a_len {
+            pieces.push((b_offset, std::cmp::min(b_end, a_len)));
+        }
+        if b_end > ARC_SIZE {
+            pieces.push((0, std::cmp::min(b_end - ARC_SIZE, a_len)));
+        }
+        match pieces.as_slice() {
+            [] => None,
+            [(start, end)] => Some(Self::covering(
+                (Wrapping(a_start) + Wrapping(*start as u32)).0,
+                end - start,
+            )),
+            _ => {
+                if a_len <= b_len {
+                    Some(*self)
+                } else {
+                    Some(*other)
+                }
+            }
+        }
+    }
+
+    /// The first location and the number of locations held by
+    /// a non-empty arc that isn't full.
+    fn start_and_len(&self) -> (u32, u64) {
+        let start = (self.center_loc.0 - Wrapping(self.half_length - 1)).0;
+        (start, 2 * self.half_length as u64 - 1)
+    }
+
+    /// The smallest arc holding `len` locations from `start`.
+    fn covering(start: u32, len: u64) -> Self {
+        // Arcs hold an odd number of locations so round up
+        let half_length = (len + 2) / 2;
+        if half_length >= MAX_HALF_LENGTH as u64 {
+            return Self::full(start);
+        }
+        let half_length = half_length as u32;
+        Self::new((Wrapping(start) + Wrapping(half_length - 1)).0, half_length)
+    }
+
     /// Check if a location is contained in this arc
     pub fn contains<I: Into<DhtLocation>>(&self, other_location: I) -> bool {
         let other_location = other_location.into();
@@ -315,4 +401,65 @@ mod tests {
         );
         check_bounds_full(0, MAX_HALF_LENGTH, half, half - 1);
     }
+
+    #[test]
+    fn test_arc_overlap() {
+        let quarter = (u32::MAX as f64 / 4.0).round() as u32;
+        let half = (u32::MAX as f64 / 2.0).round() as u32;
+
+        // Nothing in common
+        assert_eq!(DhtArc::new(0, 0).overlap(&DhtArc::full(0)), None);
+        assert_eq!(DhtArc::new(0, 10).overlap(&DhtArc::new(half, 10)), None);
+        assert_eq!(DhtArc::new(0, 10).overlap(&DhtArc::new(19, 10)), None);
+
+        // Full arcs overlap with everything
+        assert_eq!(
+            DhtArc::full(0).overlap(&DhtArc::new(quarter, 10)),
+            Some(DhtArc::new(quarter, 10))
+        );
+        assert_eq!(
+            DhtArc::new(quarter, 10).overlap(&DhtArc::full(half)),
+            Some(DhtArc::new(quarter, 10))
+        );
+
+        // One arc inside the other
+        assert_eq!(
+            DhtArc::new(0, quarter).overlap(&DhtArc::new(10, 5)),
+            Some(DhtArc::new(10, 5))
+        );
+
+        // Arcs that touch at a single location
+        assert_eq!(
+            DhtArc::new(0, 10).overlap(&DhtArc::new(18, 10)),
+            Some(DhtArc::new(9, 1))
+        );
+
+        // Partial overlap, including across zero
+        let overlap = DhtArc::new(0, quarter)
+            .overlap(&DhtArc::new(quarter, quarter))
+            .unwrap();
+        assert!(overlap.contains(1));
+        assert!(overlap.contains(quarter - 1));
+        assert!(!overlap.contains(0));
+        assert!(!overlap.contains(quarter + 1));
+
+        let overlap = DhtArc::new(10, 20)
+            .overlap(&DhtArc::new(u32::MAX, 20))
+            .unwrap();
+        assert!(overlap.contains(u32::MAX - 8));
+        assert!(overlap.contains(18));
+        assert!(!overlap.contains(u32::MAX - 10));
+        assert!(!overlap.contains(20));
+
+        // Overlap at both ends falls back to the smaller arc
+        assert_eq!(
+            DhtArc::new(0, half - 10).overlap(&DhtArc::new(half, quarter + quarter / 2)),
+            Some(DhtArc::new(half, quarter + quarter / 2))
+        );
+
+        // Overlap is the same from either side
+        let a = DhtArc::new(quarter, quarter);
+        let b = DhtArc::new(half, 1000);
+        assert_eq!(a.overlap(&b), b.overlap(&a));
+    }
 }
@@ -106,3 +106,18 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     )?;
     Ok(())
 }
+
+#[hdk_extern]
+fn list_cap_grants(tag: Option<String>) -> ExternResult<Vec<LiveCapGrant>> {
+    capability_grants(tag)
+}
+
+#[hdk_extern]
+fn list_cap_claims(tag: Option<String>) -> ExternResult<Vec<CapClaim>> {
+    capability_claims(tag)
+}
+
+#[hdk_extern]
+fn cap_info(_: ()) -> ExternResult<CapInfo> {
+    capability_info()
+}
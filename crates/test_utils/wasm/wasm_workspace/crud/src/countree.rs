@@ -44,6 +44,11 @@ impl CounTree {
         )
     }
 
+    /// follows the updates to the newest countree
+    pub fn newest<H: Into<AnyDhtHash>>(hash: H) -> ExternResult<Option<Element>> {
+        get(hash, GetOptions::latest().follow_redirects())
+    }
+
     /// increments the given header hash by 1 or creates it if not found
     /// this is silly as being offline resets the counter >.<
     pub fn incsert(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
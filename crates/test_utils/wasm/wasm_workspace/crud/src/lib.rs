@@ -18,6 +18,16 @@ fn entry_details(entry_hash: EntryHash) -> ExternResult<Option<Details>> {
     countree::CounTree::entry_details(entry_hash)
 }
 
+#[hdk_extern]
+fn newest_entry(entry_hash: EntryHash) -> ExternResult<Option<Element>> {
+    countree::CounTree::newest(entry_hash)
+}
+
+#[hdk_extern]
+fn newest_header(header_hash: HeaderHash) -> ExternResult<Option<Element>> {
+    countree::CounTree::newest(header_hash)
+}
+
 #[hdk_extern]
 fn entry_hash(countree: crate::countree::CounTree) -> ExternResult<EntryHash> {
     hash_entry(&countree)
@@ -12,3 +12,30 @@ fn bar(_: ()) -> ExternResult<String> {
     let foo: String = foo(())?;
     Ok(format!("{}{}", foo, "bar"))
 }
+
+#[hdk_extern]
+fn infinite_loop(_: ()) -> ExternResult<()> {
+    let mut i: u64 = 0;
+    loop {
+        // The volatile write stops the loop being optimised away
+        i = i.wrapping_add(1);
+        unsafe { std::ptr::write_volatile(&mut i, i) };
+    }
+}
+
+#[hdk_extern]
+fn grow_memory(_: ()) -> ExternResult<u64> {
+    let bytes = vec![1_u8; 64 * 1024 * 1024];
+    Ok(bytes.iter().map(|b| u64::from(*b)).sum())
+}
+
+#[hdk_extern]
+fn call_foo(_: ()) -> ExternResult<ZomeCallResponse> {
+    call(
+        None,
+        "hdk_extern".to_string().into(),
+        "foo".into(),
+        None,
+        &(),
+    )
+}
@@ -31,6 +31,11 @@ fn get_links(_: ()) -> ExternResult<Links> {
     hdk3::prelude::get_links(base()?, None)
 }
 
+#[hdk_extern]
+fn get_links_page(query: LinkPageQuery) -> ExternResult<LinkPage> {
+    hdk3::prelude::get_links_page(base()?, None, query)
+}
+
 #[hdk_extern]
 fn delete_all_links(_: ()) -> ExternResult<()> {
     for link in hdk3::prelude::get_links(base()?, None)?.into_inner() {
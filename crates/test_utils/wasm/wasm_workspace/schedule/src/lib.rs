@@ -0,0 +1,15 @@
+use hdk3::prelude::*;
+
+#[hdk_extern]
+fn schedule(_: ()) -> ExternResult<()> {
+    hdk3::prelude::schedule_recurring(
+        "scheduled_fn",
+        core::time::Duration::from_millis(0),
+        core::time::Duration::from_millis(10),
+    )
+}
+
+#[hdk_extern]
+fn scheduled_fn(_: ()) -> ExternResult<()> {
+    emit_signal(&())
+}
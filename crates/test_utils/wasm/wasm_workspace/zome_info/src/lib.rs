@@ -4,3 +4,13 @@ use hdk3::prelude::*;
 fn zome_info(_: ()) -> ExternResult<ZomeInfo> {
     hdk3::prelude::zome_info()
 }
+
+#[hdk_extern]
+fn show_env(_: ()) -> ExternResult<EnvInfo> {
+    hdk3::prelude::show_env()
+}
+
+#[hdk_extern]
+fn entry_type_properties(entry_def_id: EntryDefId) -> ExternResult<Option<SerializedBytes>> {
+    hdk3::prelude::entry_type_properties(entry_def_id)
+}